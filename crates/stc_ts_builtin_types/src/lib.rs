@@ -131,6 +131,23 @@ impl Ord for Lib {
     }
 }
 
+/// Returns the name of the file for `lib`, without `.d.ts`.
+pub fn lib_file_name(lib: &str) -> String {
+    let name = lib.to_ascii_lowercase();
+    let name = name.strip_prefix("lib.").unwrap_or(&name);
+    let name = name.strip_suffix(".d.ts").unwrap_or(name);
+
+    match name {
+        "es6" => "es2015".into(),
+        "es7" => "es2016".into(),
+        // These are stored with `.generated` suffix.
+        "dom" => "dom.generated".into(),
+        "webworker" => "webworker.generated".into(),
+        "webworker.iterable" => "webworker.iterable.generated".into(),
+        _ => name.into(),
+    }
+}
+
 /// Merge definitions
 pub fn load(libs: &[Lib]) -> Vec<&'static TsNamespaceDecl> {
     libs.into_par_iter().map(|lib| lib.body()).collect()
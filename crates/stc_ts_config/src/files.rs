@@ -0,0 +1,172 @@
+//! Expansion of `files`, `include` and `exclude`.
+//!
+//! See https://www.typescriptlang.org/tsconfig#include
+
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Error;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use walkdir::{DirEntry, WalkDir};
+
+/// All paths and patterns should be absolute.
+///
+/// `exclude` only affects files matched by `include`.
+pub(crate) fn expand(files: &[PathBuf], include: &[String], exclude: &[String]) -> Result<Vec<PathBuf>, Error> {
+    let mut result = files.to_vec();
+
+    if !include.is_empty() {
+        let include_set = build_glob_set(include)?;
+        let exclude_set = build_glob_set(exclude)?;
+
+        for base in walk_roots(include) {
+            if !base.exists() {
+                continue;
+            }
+
+            for entry in WalkDir::new(&base)
+                .follow_links(true)
+                .into_iter()
+                .filter_entry(|e| e.path() == base || (!is_package_folder(e) && !exclude_set.is_match(e.path())))
+            {
+                let entry = entry?;
+                if !entry.file_type().is_file() || !is_supported_file(entry.path()) {
+                    continue;
+                }
+
+                if include_set.is_match(entry.path()) {
+                    result.push(entry.path().to_path_buf());
+                }
+            }
+        }
+    }
+
+    result.sort();
+    result.dedup();
+
+    Ok(result)
+}
+
+/// Matches paths against `include` and `exclude` without walking directories,
+/// for paths reported by a file watcher.
+#[derive(Debug, Clone)]
+pub struct InputMatcher {
+    roots: Vec<PathBuf>,
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+impl Default for InputMatcher {
+    fn default() -> Self {
+        InputMatcher {
+            roots: vec![],
+            include: GlobSet::empty(),
+            exclude: GlobSet::empty(),
+        }
+    }
+}
+
+impl InputMatcher {
+    /// All paths and patterns should be absolute.
+    pub(crate) fn new(include: &[String], exclude: &[String]) -> Result<Self, Error> {
+        Ok(InputMatcher {
+            roots: walk_roots(include),
+            include: build_glob_set(include)?,
+            exclude: build_glob_set(exclude)?,
+        })
+    }
+
+    /// Directories which contain all files matched by `include`.
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Returns true if creating or removing `path` can change the files
+    /// matched by `include` and `exclude`.
+    ///
+    /// Paths which are not input files are considered as directories, because
+    /// removed paths cannot be inspected.
+    pub fn may_change_inputs(&self, path: &Path) -> bool {
+        let root = match self.roots.iter().find(|root| path.starts_with(root)) {
+            Some(v) => v,
+            None => return false,
+        };
+
+        // Same as the filter used by `expand`.
+        let skipped = path
+            .ancestors()
+            .take_while(|p| *p != root.as_path())
+            .any(|p| self.exclude.is_match(p) || p.file_name().map(is_package_folder_name).unwrap_or(false));
+        if skipped {
+            return false;
+        }
+
+        !is_supported_file(path) || self.include.is_match(path)
+    }
+}
+
+/// Wildcards do not match package folders, even if `exclude` is overriden.
+fn is_package_folder(entry: &DirEntry) -> bool {
+    entry.file_type().is_dir() && is_package_folder_name(entry.file_name())
+}
+
+fn is_package_folder_name(name: &OsStr) -> bool {
+    matches!(name.to_str(), Some("node_modules" | "bower_components" | "jspm_packages"))
+}
+
+fn is_supported_file(path: &Path) -> bool {
+    let s = path.to_string_lossy();
+
+    s.ends_with(".ts") || s.ends_with(".tsx") || s.ends_with(".mts") || s.ends_with(".cts")
+}
+
+fn has_wildcard(s: &str) -> bool {
+    s.contains(['*', '?', '['])
+}
+
+/// Returns the directories to walk, which are the longest wildcard-free
+/// prefixes of the patterns.
+fn walk_roots(patterns: &[String]) -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = vec![];
+
+    for pat in patterns {
+        let mut base = PathBuf::new();
+        for c in Path::new(pat).components() {
+            if has_wildcard(&c.as_os_str().to_string_lossy()) {
+                break;
+            }
+            base.push(c);
+        }
+
+        if roots.iter().any(|r| base.starts_with(r)) {
+            continue;
+        }
+        roots.retain(|r| !r.starts_with(&base));
+        roots.push(base);
+    }
+
+    roots
+}
+
+/// `*` does not match `/`, as in `tsc`.
+fn glob(pat: &str) -> Result<globset::Glob, Error> {
+    Ok(GlobBuilder::new(pat).literal_separator(true).build()?)
+}
+
+/// Patterns without wildcards match directories, so we add `/**` for them.
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet, Error> {
+    let mut builder = GlobSetBuilder::new();
+
+    for pat in patterns {
+        let pat = pat.trim_end_matches('/');
+
+        builder.add(glob(pat)?);
+        if !has_wildcard(pat) {
+            builder.add(glob(&format!("{}/**", pat))?);
+        }
+    }
+
+    Ok(builder.build()?)
+}
@@ -0,0 +1,129 @@
+//! `tsconfig.json` allows comments and trailing commas, so we strip them
+//! before handing the content to `serde_json`.
+
+use anyhow::{Context, Error};
+use serde_json::Value;
+
+pub(crate) fn parse(content: &str) -> Result<Value, Error> {
+    let stripped = strip(content);
+
+    serde_json::from_str(&stripped).context("failed to parse json")
+}
+
+/// Replaces comments with whitespace and removes trailing commas.
+///
+/// Newlines are preserved so that the line numbers reported by `serde_json`
+/// still match the original file.
+pub(crate) fn strip(content: &str) -> String {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+
+    let mut buf = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    // Index of the last comma in `buf` which may be a trailing comma.
+    let mut pending_comma = None;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                pending_comma = None;
+                buf.push(c);
+
+                while let Some(c) = chars.next() {
+                    buf.push(c);
+                    match c {
+                        '\\' => {
+                            if let Some(escaped) = chars.next() {
+                                buf.push(escaped);
+                            }
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+
+            '/' if chars.peek() == Some(&'/') => {
+                chars.next();
+                buf.push_str("  ");
+
+                while let Some(&c) = chars.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+                    chars.next();
+                    buf.push(' ');
+                }
+            }
+
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                buf.push_str("  ");
+
+                let mut prev = '\0';
+                for c in chars.by_ref() {
+                    buf.push(if c == '\n' { '\n' } else { ' ' });
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+
+            ',' => {
+                pending_comma = Some(buf.len());
+                buf.push(c);
+            }
+
+            '}' | ']' => {
+                if let Some(idx) = pending_comma.take() {
+                    buf.replace_range(idx..idx + 1, " ");
+                }
+                buf.push(c);
+            }
+
+            _ => {
+                if !c.is_whitespace() {
+                    pending_comma = None;
+                }
+                buf.push(c);
+            }
+        }
+    }
+
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comments() {
+        let v = parse(
+            r#"{
+                // line comment
+                "a": 1, /* block
+                comment */ "b": "// not a comment"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(v["a"], 1);
+        assert_eq!(v["b"], "// not a comment");
+    }
+
+    #[test]
+    fn trailing_commas() {
+        let v = parse(r#"{ "a": [1, 2,], "b": { "c": true, }, }"#).unwrap();
+
+        assert_eq!(v["a"].as_array().unwrap().len(), 2);
+        assert_eq!(v["b"]["c"], true);
+    }
+
+    #[test]
+    fn escaped_quote() {
+        let v = parse(r#"{ "a": "\"//\"", }"#).unwrap();
+
+        assert_eq!(v["a"], "\"//\"");
+    }
+}
@@ -0,0 +1,544 @@
+//! Loader for `tsconfig.json`.
+//!
+//! This crate resolves `extends` chains and converts `compilerOptions` into
+//! the types used by the type checker, so every frontend shares the same
+//! option semantics.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Error};
+use path_clean::PathClean;
+use serde_json::{Map, Value};
+pub use stc_ts_builtin_types::lib_file_name;
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, JsxConfig, ModuleConfig, Rule};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::{node16::ModuleResolution, paths::PathsResolver};
+use swc_ecma_ast::EsVersion;
+use swc_ecma_parser::TsConfig;
+
+pub use self::{files::InputMatcher, options::CompilerOptions};
+
+mod files;
+mod jsonc;
+mod options;
+
+/// A `tsconfig.json` with all `extends` resolved.
+#[derive(Debug, Clone)]
+pub struct ParsedConfig {
+    /// Absolute path to the config file.
+    pub path: PathBuf,
+
+    /// Input files matched by `files`, `include` and `exclude`.
+    pub files: Vec<PathBuf>,
+
+    /// `include` and `exclude`, used to find files added to the project.
+    pub inputs: InputMatcher,
+
+    pub compiler_options: CompilerOptions,
+}
+
+/// Loads a config file and the files it includes.
+///
+/// `path` may be a directory, in which case `tsconfig.json` in the directory
+/// is used.
+pub fn load(path: &Path) -> Result<ParsedConfig, Error> {
+    let path = if path.is_dir() {
+        path.join("tsconfig.json")
+    } else {
+        path.to_path_buf()
+    };
+    if !path.is_file() {
+        bail!("cannot find a tsconfig.json file at `{}`", path.display());
+    }
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("failed to canonicalize `{}`", path.display()))?;
+    let dir = path.parent().unwrap_or_else(|| Path::new("/")).to_path_buf();
+
+    let raw = load_raw(&path, &mut vec![])?;
+
+    let compiler_options: CompilerOptions = serde_json::from_value(Value::Object(raw.compiler_options))
+        .with_context(|| format!("failed to parse `compilerOptions` of `{}`", path.display()))?;
+
+    for pattern in compiler_options.paths.iter().flat_map(|paths| paths.keys()) {
+        if pattern.matches('*').count() > 1 {
+            bail!("pattern `{}` of `paths` can have at most one `*` character", pattern);
+        }
+    }
+    if let Some(s) = &compiler_options.target {
+        if parse_target(s).is_none() {
+            bail!("error TS6046: `{}` in `target` of `{}` is not a known target", s, path.display());
+        }
+    }
+    if let Some(s) = &compiler_options.module {
+        if parse_module(s).is_none() {
+            bail!(
+                "error TS6046: `{}` in `module` of `{}` is not a known module system",
+                s,
+                path.display()
+            );
+        }
+    }
+    if let Some(s) = &compiler_options.module_resolution {
+        s.parse::<ModuleResolution>()
+            .with_context(|| format!("invalid `moduleResolution` in `{}`", path.display()))?;
+    }
+    for name in compiler_options.lib.iter().flatten() {
+        if Lib::load(&lib_file_name(name)).is_empty() {
+            bail!("error TS6046: `{}` in `lib` of `{}` is not a known library", name, path.display());
+        }
+    }
+
+    let include = match (raw.include, &raw.files) {
+        (Some(include), _) => include,
+        (None, Some(..)) => vec![],
+        (None, None) => vec![pattern(&dir, "**/*")],
+    };
+    let exclude = match raw.exclude {
+        Some(exclude) => exclude,
+        None => {
+            let mut v = vec![
+                pattern(&dir, "node_modules"),
+                pattern(&dir, "bower_components"),
+                pattern(&dir, "jspm_packages"),
+            ];
+            v.extend(
+                [&compiler_options.out_dir, &compiler_options.declaration_dir]
+                    .into_iter()
+                    .flatten()
+                    .map(|p| p.to_string_lossy().into_owned()),
+            );
+            v
+        }
+    };
+
+    let files = files::expand(raw.files.as_deref().unwrap_or_default(), &include, &exclude)?;
+    let inputs = InputMatcher::new(&include, &exclude)?;
+
+    Ok(ParsedConfig {
+        path,
+        files,
+        inputs,
+        compiler_options,
+    })
+}
+
+impl ParsedConfig {
+    /// The directory containing the config file.
+    pub fn dir(&self) -> &Path {
+        self.path.parent().unwrap_or_else(|| Path::new("/"))
+    }
+
+    pub fn target(&self) -> EsVersion {
+        match &self.compiler_options.target {
+            Some(s) => parse_target(s).unwrap_or_else(EsVersion::latest),
+            None => EsVersion::Es3,
+        }
+    }
+
+    pub fn module(&self) -> ModuleConfig {
+        match &self.compiler_options.module {
+            Some(s) => parse_module(s).unwrap_or(ModuleConfig::EsNext),
+            None => match self.target() {
+                EsVersion::Es3 | EsVersion::Es5 => ModuleConfig::CommonJs,
+                _ => ModuleConfig::Es2015,
+            },
+        }
+    }
+
+    /// If `moduleResolution` is not specified, `node16` and `nodenext` of
+    /// `module` select the resolution with the same name.
+    pub fn module_resolution(&self) -> Result<ModuleResolution, Error> {
+        if let Some(s) = &self.compiler_options.module_resolution {
+            return s.parse();
+        }
+
+        Ok(match self.module() {
+            ModuleConfig::Node16 => ModuleResolution::Node16,
+            ModuleConfig::NodeNext => ModuleResolution::NodeNext,
+            _ => ModuleResolution::Node10,
+        })
+    }
+
+    /// An unknown `jsx` is ignored, like a missing one.
+    pub fn jsx(&self) -> JsxConfig {
+        let o = &self.compiler_options;
+
+        JsxConfig {
+            mode: o.jsx.as_deref().and_then(|s| s.to_ascii_lowercase().parse().ok()),
+            factory: o.jsx_factory.as_deref().map(From::from),
+            fragment_factory: o.jsx_fragment_factory.as_deref().map(From::from),
+            import_source: o.jsx_import_source.as_deref().map(From::from),
+        }
+    }
+
+    /// Creates a [Rule], with `strict` expanded to the options it enables.
+    ///
+    /// An option specified explicitly takes precedence over `strict`.
+    pub fn rule(&self) -> Rule {
+        let o = &self.compiler_options;
+        let strict = o.strict.unwrap_or(false);
+
+        Rule {
+            no_implicit_any: o.no_implicit_any.unwrap_or(strict),
+            no_implicit_this: o.no_implicit_this.unwrap_or(strict),
+            always_strict: o.always_strict.unwrap_or(strict),
+            strict_null_checks: o.strict_null_checks.unwrap_or(strict),
+            strict_function_types: o.strict_function_types.unwrap_or(strict),
+
+            allow_unreachable_code: o.allow_unreachable_code.unwrap_or(false),
+            allow_unused_labels: o.allow_unused_labels.unwrap_or(false),
+            no_fallthrough_cases_in_switch: o.no_fallthrough_cases_in_switch.unwrap_or(false),
+            no_implicit_returns: o.no_implicit_returns.unwrap_or(false),
+            suppress_excess_property_errors: o.suppress_excess_property_errors.unwrap_or(false),
+            suppress_implicit_any_index_errors: o.suppress_implicit_any_index_errors.unwrap_or(false),
+            no_strict_generic_checks: o.no_strict_generic_checks.unwrap_or(false),
+            no_unused_locals: o.no_unused_locals.unwrap_or(false),
+            no_unused_parameters: o.no_unused_parameters.unwrap_or(false),
+            no_unchecked_indexed_access: o.no_unchecked_indexed_access.unwrap_or(false),
+            exact_optional_property_types: o.exact_optional_property_types.unwrap_or(false),
+            use_define_property_for_class_fields: o.use_define_for_class_fields.unwrap_or(self.target() >= EsVersion::Es2022),
+        }
+    }
+
+    /// Builtin libraries, including their dependencies.
+    ///
+    /// If `lib` is not specified, the default libraries of `target` are used.
+    pub fn libs(&self) -> Vec<Lib> {
+        if self.compiler_options.no_lib.unwrap_or(false) {
+            return vec![];
+        }
+
+        let mut libs = match &self.compiler_options.lib {
+            Some(names) => names.iter().flat_map(|name| Lib::load(&lib_file_name(name))).collect::<Vec<_>>(),
+            None => Lib::load(default_lib(self.target())),
+        };
+        libs.sort();
+        libs.dedup();
+        libs
+    }
+
+    pub fn parser_config(&self) -> TsConfig {
+        TsConfig {
+            decorators: self.compiler_options.experimental_decorators.unwrap_or(false),
+            ..Default::default()
+        }
+    }
+
+    pub fn env(&self) -> Env {
+        Env::simple(self.rule(), self.target(), self.module(), &self.libs()).with_jsx(self.jsx())
+    }
+
+    /// Creates a resolver which applies `baseUrl`, `paths` and `rootDirs`.
+    pub fn resolver(&self) -> PathsResolver {
+        let o = &self.compiler_options;
+
+        PathsResolver {
+            base_url: o.base_url.clone(),
+            paths_base: o
+                .base_url
+                .clone()
+                .or_else(|| o.paths_base_path.clone())
+                .unwrap_or_else(|| self.dir().to_path_buf()),
+            paths: o
+                .paths
+                .iter()
+                .flatten()
+                .map(|(pattern, substitutions)| (pattern.clone(), substitutions.clone()))
+                .collect(),
+            root_dirs: o.root_dirs.clone().unwrap_or_default(),
+            // Validated by `load`.
+            module_resolution: self.module_resolution().unwrap_or_default(),
+        }
+    }
+
+    /// The directory whose layout is mirrored by emitted files.
+    ///
+    /// If `rootDir` is not specified, this is the longest common directory of
+    /// input files, excluding declaration files.
+    pub fn root_dir(&self) -> PathBuf {
+        if let Some(dir) = &self.compiler_options.root_dir {
+            return dir.clone();
+        }
+
+        let mut common: Option<PathBuf> = None;
+        for file in self.files.iter().filter(|file| !is_dts(file)) {
+            let dir = file.parent().unwrap_or_else(|| Path::new("/"));
+
+            common = Some(match common {
+                Some(common) => common
+                    .ancestors()
+                    .find(|a| dir.starts_with(a))
+                    .unwrap_or_else(|| Path::new("/"))
+                    .to_path_buf(),
+                None => dir.to_path_buf(),
+            });
+        }
+
+        common.unwrap_or_else(|| self.dir().to_path_buf())
+    }
+
+    /// Returns pairs of an input file and the path of the declaration file
+    /// for it. Declaration files in the input are skipped.
+    ///
+    /// Declaration files are placed in `declarationDir`, or in `outDir`, as
+    /// [ParsedConfig::root_dir] is laid out. If neither is specified, they
+    /// are placed next to input files.
+    pub fn declaration_files(&self) -> Result<Vec<(PathBuf, PathBuf)>, Error> {
+        let o = &self.compiler_options;
+        let root_dir = self.root_dir();
+        let out_dir = o.declaration_dir.as_ref().or(o.out_dir.as_ref());
+
+        self.files
+            .iter()
+            .filter(|file| !is_dts(file))
+            .map(|file| {
+                let rel = match file.strip_prefix(&root_dir) {
+                    Ok(rel) => rel,
+                    Err(..) => bail!(
+                        "file `{}` is not under `rootDir` `{}`; `rootDir` is expected to contain all source files",
+                        file.display(),
+                        root_dir.display()
+                    ),
+                };
+                let dts = match out_dir {
+                    Some(out_dir) => out_dir.join(rel),
+                    None => file.clone(),
+                };
+
+                Ok((file.clone(), dts_file_name(&dts)))
+            })
+            .collect()
+    }
+}
+
+fn is_dts(path: &Path) -> bool {
+    let name = path.file_name().map(|s| s.to_string_lossy()).unwrap_or_default();
+
+    name.ends_with(".d.ts") || name.ends_with(".d.mts") || name.ends_with(".d.cts")
+}
+
+/// `a.ts` and `a.tsx` become `a.d.ts`, while `a.mts` becomes `a.d.mts`.
+fn dts_file_name(path: &Path) -> PathBuf {
+    let ext = match path.extension().and_then(|s| s.to_str()) {
+        Some("mts") => "d.mts",
+        Some("cts") => "d.cts",
+        _ => "d.ts",
+    };
+
+    path.with_extension(ext)
+}
+
+fn parse_target(s: &str) -> Option<EsVersion> {
+    Some(match &*s.to_ascii_lowercase() {
+        "es3" => EsVersion::Es3,
+        "es5" => EsVersion::Es5,
+        "es6" | "es2015" => EsVersion::Es2015,
+        "es2016" => EsVersion::Es2016,
+        "es2017" => EsVersion::Es2017,
+        "es2018" => EsVersion::Es2018,
+        "es2019" => EsVersion::Es2019,
+        "es2020" => EsVersion::Es2020,
+        "es2021" => EsVersion::Es2021,
+        "es2022" => EsVersion::Es2022,
+        "esnext" => EsVersion::latest(),
+        _ => return None,
+    })
+}
+
+/// `es2022` is checked like `esnext`.
+fn parse_module(s: &str) -> Option<ModuleConfig> {
+    match &*s.to_ascii_lowercase() {
+        "es2022" => Some(ModuleConfig::EsNext),
+        s => s.parse().ok(),
+    }
+}
+
+fn default_lib(target: EsVersion) -> &'static str {
+    match target {
+        EsVersion::Es3 | EsVersion::Es5 => "es5.full",
+        EsVersion::Es2015 => "es2015.full",
+        EsVersion::Es2016 => "es2016.full",
+        EsVersion::Es2017 => "es2017.full",
+        EsVersion::Es2018 => "es2018.full",
+        EsVersion::Es2019 => "es2019.full",
+        EsVersion::Es2020 => "es2020.full",
+        _ => "esnext.full",
+    }
+}
+
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+    path.to_path_buf().clean()
+}
+
+fn pattern(dir: &Path, pat: &str) -> String {
+    normalize(&dir.join(pat)).to_string_lossy().into_owned()
+}
+
+/// Content of a config file, merged with the files it extends.
+#[derive(Debug, Default)]
+struct RawConfig {
+    compiler_options: Map<String, Value>,
+    /// Absolute paths.
+    files: Option<Vec<PathBuf>>,
+    /// Absolute glob patterns.
+    include: Option<Vec<String>>,
+    /// Absolute glob patterns.
+    exclude: Option<Vec<String>>,
+}
+
+impl RawConfig {
+    /// Options of `other` takes precedence.
+    fn merge(&mut self, other: RawConfig) {
+        self.compiler_options.extend(other.compiler_options);
+
+        if other.files.is_some() {
+            self.files = other.files;
+        }
+        if other.include.is_some() {
+            self.include = other.include;
+        }
+        if other.exclude.is_some() {
+            self.exclude = other.exclude;
+        }
+    }
+}
+
+/// `stack` is used to detect circular `extends`.
+fn load_raw(path: &Path, stack: &mut Vec<PathBuf>) -> Result<RawConfig, Error> {
+    if stack.iter().any(|p| p == path) {
+        bail!("circularity detected while resolving configuration: {}", path.display());
+    }
+    stack.push(path.to_path_buf());
+
+    let content = fs::read_to_string(path).with_context(|| format!("failed to read `{}`", path.display()))?;
+    let value = jsonc::parse(&content).with_context(|| format!("failed to parse `{}`", path.display()))?;
+    let mut obj = match value {
+        Value::Object(obj) => obj,
+        _ => bail!("`{}` should contain an object", path.display()),
+    };
+    let dir = path.parent().unwrap_or_else(|| Path::new("/"));
+
+    let bases = match obj.remove("extends") {
+        Some(Value::String(s)) => vec![s],
+        Some(Value::Array(items)) => items
+            .into_iter()
+            .map(|v| match v {
+                Value::String(s) => Ok(s),
+                _ => bail!("`extends` of `{}` should be a string or an array of strings", path.display()),
+            })
+            .collect::<Result<_, _>>()?,
+        Some(_) => bail!("`extends` of `{}` should be a string or an array of strings", path.display()),
+        None => vec![],
+    };
+
+    let mut config = RawConfig::default();
+
+    for base in bases {
+        let base_path = resolve_extends(dir, &base).with_context(|| format!("failed to resolve `extends` of `{}`", path.display()))?;
+        let base = load_raw(&base_path, stack)?;
+        config.merge(base);
+    }
+
+    let compiler_options = match obj.remove("compilerOptions") {
+        Some(Value::Object(mut options)) => {
+            options::normalize_paths(&mut options, dir);
+            options
+        }
+        Some(Value::Null) | None => Default::default(),
+        Some(_) => bail!("`compilerOptions` of `{}` should be an object", path.display()),
+    };
+
+    let own = RawConfig {
+        compiler_options,
+        files: string_list(&obj, "files", path)?.map(|files| files.iter().map(|f| normalize(&dir.join(f))).collect()),
+        include: string_list(&obj, "include", path)?.map(|pats| pats.iter().map(|p| pattern(dir, p)).collect()),
+        exclude: string_list(&obj, "exclude", path)?.map(|pats| pats.iter().map(|p| pattern(dir, p)).collect()),
+    };
+    config.merge(own);
+
+    stack.pop();
+
+    Ok(config)
+}
+
+fn string_list(obj: &Map<String, Value>, key: &str, path: &Path) -> Result<Option<Vec<String>>, Error> {
+    match obj.get(key) {
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|v| match v {
+                Value::String(s) => Ok(s.clone()),
+                _ => bail!("`{}` of `{}` should be an array of strings", key, path.display()),
+            })
+            .collect::<Result<_, _>>()
+            .map(Some),
+        Some(Value::Null) | None => Ok(None),
+        Some(_) => bail!("`{}` of `{}` should be an array of strings", key, path.display()),
+    }
+}
+
+/// Resolves `extends`, which is a relative path or a package name.
+fn resolve_extends(dir: &Path, specifier: &str) -> Result<PathBuf, Error> {
+    let is_path = specifier.starts_with("./") || specifier.starts_with("../") || Path::new(specifier).is_absolute();
+
+    if is_path {
+        let path = normalize(&dir.join(specifier));
+        if path.is_file() {
+            return Ok(path);
+        }
+        let with_ext = path.with_file_name(format!(
+            "{}.json",
+            path.file_name().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default()
+        ));
+        if with_ext.is_file() {
+            return Ok(with_ext);
+        }
+
+        bail!("file `{}` not found", path.display());
+    }
+
+    let mut cur = Some(dir);
+    while let Some(d) = cur {
+        let pkg = d.join("node_modules").join(specifier);
+
+        if pkg.is_file() {
+            return Ok(pkg);
+        }
+        let with_ext = PathBuf::from(format!("{}.json", pkg.display()));
+        if with_ext.is_file() {
+            return Ok(with_ext);
+        }
+        if pkg.is_dir() {
+            // `tsconfig` field of package.json
+            if let Some(entry) = tsconfig_field_of_package(&pkg) {
+                return Ok(entry);
+            }
+
+            let entry = pkg.join("tsconfig.json");
+            if entry.is_file() {
+                return Ok(entry);
+            }
+        }
+
+        cur = d.parent();
+    }
+
+    bail!("package `{}` not found", specifier)
+}
+
+fn tsconfig_field_of_package(pkg_dir: &Path) -> Option<PathBuf> {
+    let content = fs::read_to_string(pkg_dir.join("package.json")).ok()?;
+    let pkg: Value = serde_json::from_str(&content).ok()?;
+    let entry = pkg.get("tsconfig")?.as_str()?;
+
+    let path = normalize(&pkg_dir.join(entry));
+    if path.is_file() {
+        Some(path)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,164 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// `compilerOptions` of `tsconfig.json`.
+///
+/// All paths are absolute and normalized, as they are resolved relative to
+/// the config file which declared them.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompilerOptions {
+    #[serde(default)]
+    pub target: Option<String>,
+
+    #[serde(default)]
+    pub module: Option<String>,
+
+    #[serde(default)]
+    pub lib: Option<Vec<String>>,
+
+    #[serde(default)]
+    pub no_lib: Option<bool>,
+
+    #[serde(default)]
+    pub types: Option<Vec<String>>,
+
+    #[serde(default)]
+    pub type_roots: Option<Vec<PathBuf>>,
+
+    #[serde(default)]
+    pub base_url: Option<PathBuf>,
+
+    #[serde(default)]
+    pub paths: Option<BTreeMap<String, Vec<String>>>,
+
+    /// Directory of the config file which declared `paths`, which is used if
+    /// `baseUrl` is not specified. This is not a real option, and like `tsc`,
+    /// it's recorded while loading config files.
+    #[serde(default)]
+    pub paths_base_path: Option<PathBuf>,
+
+    #[serde(default)]
+    pub root_dirs: Option<Vec<PathBuf>>,
+
+    #[serde(default)]
+    pub module_resolution: Option<String>,
+
+    #[serde(default)]
+    pub out_dir: Option<PathBuf>,
+
+    #[serde(default)]
+    pub root_dir: Option<PathBuf>,
+
+    #[serde(default)]
+    pub declaration: Option<bool>,
+
+    #[serde(default)]
+    pub emit_declaration_only: Option<bool>,
+
+    #[serde(default)]
+    pub declaration_map: Option<bool>,
+
+    #[serde(default)]
+    pub declaration_dir: Option<PathBuf>,
+
+    #[serde(default)]
+    pub jsx: Option<String>,
+
+    #[serde(default)]
+    pub jsx_factory: Option<String>,
+
+    #[serde(default)]
+    pub jsx_fragment_factory: Option<String>,
+
+    #[serde(default)]
+    pub jsx_import_source: Option<String>,
+
+    #[serde(default)]
+    pub experimental_decorators: Option<bool>,
+
+    #[serde(default)]
+    pub strict: Option<bool>,
+
+    #[serde(default)]
+    pub no_implicit_any: Option<bool>,
+
+    #[serde(default)]
+    pub no_implicit_this: Option<bool>,
+
+    #[serde(default)]
+    pub always_strict: Option<bool>,
+
+    #[serde(default)]
+    pub strict_null_checks: Option<bool>,
+
+    #[serde(default)]
+    pub strict_function_types: Option<bool>,
+
+    #[serde(default)]
+    pub allow_unreachable_code: Option<bool>,
+
+    #[serde(default)]
+    pub allow_unused_labels: Option<bool>,
+
+    #[serde(default)]
+    pub no_fallthrough_cases_in_switch: Option<bool>,
+
+    #[serde(default)]
+    pub no_implicit_returns: Option<bool>,
+
+    #[serde(default)]
+    pub suppress_excess_property_errors: Option<bool>,
+
+    #[serde(default)]
+    pub suppress_implicit_any_index_errors: Option<bool>,
+
+    #[serde(default)]
+    pub no_strict_generic_checks: Option<bool>,
+
+    #[serde(default)]
+    pub no_unused_locals: Option<bool>,
+
+    #[serde(default)]
+    pub no_unused_parameters: Option<bool>,
+
+    #[serde(default)]
+    pub no_unchecked_indexed_access: Option<bool>,
+
+    #[serde(default)]
+    pub exact_optional_property_types: Option<bool>,
+
+    #[serde(default)]
+    pub use_define_for_class_fields: Option<bool>,
+}
+
+/// Options which are paths relative to the declaring config file.
+const PATH_OPTIONS: &[&str] = &["baseUrl", "outDir", "rootDir", "declarationDir", "tsBuildInfoFile"];
+
+/// Options which are lists of paths relative to the declaring config file.
+const PATH_LIST_OPTIONS: &[&str] = &["typeRoots", "rootDirs"];
+
+/// Makes path-like options absolute.
+pub(crate) fn normalize_paths(options: &mut Map<String, Value>, dir: &std::path::Path) {
+    if options.contains_key("paths") {
+        options.insert("pathsBasePath".into(), Value::String(dir.to_string_lossy().into_owned()));
+    }
+
+    for &key in PATH_OPTIONS {
+        if let Some(Value::String(s)) = options.get_mut(key) {
+            *s = crate::normalize(&dir.join(&*s)).to_string_lossy().into_owned();
+        }
+    }
+
+    for &key in PATH_LIST_OPTIONS {
+        if let Some(Value::Array(items)) = options.get_mut(key) {
+            for item in items {
+                if let Value::String(s) = item {
+                    *s = crate::normalize(&dir.join(&*s)).to_string_lossy().into_owned();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1 @@
+declare const g: number;
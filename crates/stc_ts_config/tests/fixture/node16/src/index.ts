@@ -0,0 +1 @@
+export { util } from "./util.js";
@@ -0,0 +1 @@
+export const templates = 1;
@@ -0,0 +1 @@
+export const exact = 1;
@@ -0,0 +1,5 @@
+import { a } from "@app/a";
+import { b } from "@app/b";
+import { exact } from "exact";
+import { c } from "src/lib/c";
+import { templates } from "./templates";
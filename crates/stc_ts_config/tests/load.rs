@@ -0,0 +1,240 @@
+use std::path::{Path, PathBuf};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_config::{load, CompilerOptions, ParsedConfig};
+use stc_ts_module_loader::resolvers::{node16::ModuleResolution, paths::PathsResolver};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+use swc_ecma_loader::resolve::Resolve;
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixture")
+        .join(name)
+        .canonicalize()
+        .unwrap()
+}
+
+fn file_names(dir: &Path, files: &[PathBuf]) -> Vec<String> {
+    files
+        .iter()
+        .map(|f| f.strip_prefix(dir).unwrap().to_string_lossy().replace('\\', "/"))
+        .collect()
+}
+
+#[test]
+fn extends() {
+    let dir = fixture("extends");
+    let config = load(&dir).unwrap();
+
+    assert_eq!(config.target(), EsVersion::Es2015);
+    assert!(config.parser_config().decorators);
+
+    let rule = config.rule();
+    assert!(rule.no_implicit_any);
+    assert!(rule.strict_function_types);
+    // Overriden by the root config.
+    assert!(!rule.strict_null_checks);
+
+    // Relative to the config which declared it.
+    assert_eq!(config.compiler_options.base_url.as_deref(), Some(&*dir));
+    assert_eq!(config.compiler_options.out_dir, Some(dir.join("out")));
+
+    let libs = config.libs();
+    assert!(libs.contains(&Lib::Es2015Core));
+    assert!(libs.contains(&Lib::Dom));
+}
+
+#[test]
+fn extends_overrides_exclude() {
+    let dir = fixture("extends");
+    let config = load(&dir.join("tsconfig.json")).unwrap();
+
+    assert_eq!(file_names(&dir, &config.files), vec!["out/d.ts", "src/a.ts", "src/nested/b.tsx"]);
+}
+
+#[test]
+fn defaults() {
+    let dir = fixture("default");
+    let config = load(&dir).unwrap();
+
+    assert_eq!(config.target(), EsVersion::Es3);
+    assert!(!config.rule().strict_null_checks);
+    assert!(!config.rule().use_define_property_for_class_fields);
+    assert!(config.libs().contains(&Lib::Es5));
+
+    // `outDir` and `declarationDir` are excluded.
+    assert_eq!(file_names(&dir, &config.files), vec!["index.ts"]);
+}
+
+#[test]
+fn inputs() {
+    let dir = fixture("default");
+    let inputs = load(&dir).unwrap().inputs;
+
+    assert_eq!(inputs.roots(), &[dir.clone()][..]);
+    assert!(inputs.may_change_inputs(&dir.join("new").join("a.ts")));
+    // It may be a directory containing input files.
+    assert!(inputs.may_change_inputs(&dir.join("new")));
+    assert!(!inputs.may_change_inputs(&dir.join("dist").join("a.ts")));
+    assert!(!inputs.may_change_inputs(&dir.join("node_modules").join("pkg").join("a.ts")));
+    assert!(!inputs.may_change_inputs(&dir.parent().unwrap().join("a.ts")));
+}
+
+#[test]
+fn unknown_lib() {
+    let err = load(&fixture("unknown_lib")).unwrap_err();
+
+    assert!(format!("{:#}", err).contains("TS6046"), "{:#}", err);
+}
+
+#[test]
+fn unknown_target_and_module() {
+    let err = load(&fixture("unknown_target")).unwrap_err();
+    assert!(format!("{:#}", err).contains("`es2099` in `target`"), "{:#}", err);
+
+    let err = load(&fixture("unknown_module")).unwrap_err();
+    assert!(format!("{:#}", err).contains("`commonjs2` in `module`"), "{:#}", err);
+}
+
+#[test]
+fn use_define_for_class_fields() {
+    let config = |target: &str| ParsedConfig {
+        path: PathBuf::new(),
+        files: vec![],
+        inputs: Default::default(),
+        compiler_options: CompilerOptions {
+            target: Some(target.into()),
+            ..Default::default()
+        },
+    };
+
+    assert!(!config("es2021").rule().use_define_property_for_class_fields);
+    assert!(config("es2022").rule().use_define_property_for_class_fields);
+    assert!(config("esnext").rule().use_define_property_for_class_fields);
+}
+
+#[test]
+fn circular() {
+    let err = load(&fixture("circular")).unwrap_err();
+
+    assert!(format!("{:#}", err).contains("circularity"), "{:#}", err);
+}
+
+#[test]
+fn declaration_files() {
+    let dir = fixture("declaration");
+    let mut config = load(&dir).unwrap();
+
+    let outputs = |config: &ParsedConfig| {
+        let files = config.declaration_files().unwrap();
+        file_names(&dir, &files.into_iter().map(|(_, dts)| dts).collect::<Vec<_>>())
+    };
+
+    // `rootDir` defaults to the common directory of input files.
+    assert_eq!(config.root_dir(), dir.join("src"));
+    assert_eq!(outputs(&config), vec!["types/index.d.ts", "types/nested/b.d.ts"]);
+
+    config.compiler_options.root_dir = Some(dir.clone());
+    assert_eq!(outputs(&config), vec!["types/src/index.d.ts", "types/src/nested/b.d.ts"]);
+
+    config.compiler_options.declaration_dir = None;
+    assert_eq!(outputs(&config), vec!["lib/src/index.d.ts", "lib/src/nested/b.d.ts"]);
+
+    config.compiler_options.out_dir = None;
+    assert_eq!(outputs(&config), vec!["src/index.d.ts", "src/nested/b.d.ts"]);
+
+    config.compiler_options.root_dir = Some(dir.join("src").join("nested"));
+    assert!(config.declaration_files().is_err());
+}
+
+#[test]
+fn paths() {
+    let dir = fixture("paths");
+    let resolver = load(&dir).unwrap().resolver();
+    let base = FileName::Real(dir.join("src").join("index.ts"));
+
+    let resolve = |target: &str| match resolver.resolve(&base, target) {
+        Ok(FileName::Real(path)) => Some(path.strip_prefix(&dir).unwrap().to_string_lossy().replace('\\', "/")),
+        _ => None,
+    };
+
+    assert_eq!(resolve("@app/a").as_deref(), Some("src/app/a.ts"));
+    // The second substitution is used.
+    assert_eq!(resolve("@app/b").as_deref(), Some("src/fallback/b.ts"));
+    assert_eq!(resolve("@app/missing"), None);
+    assert_eq!(resolve("exact").as_deref(), Some("src/exact.ts"));
+    // `baseUrl`
+    assert_eq!(resolve("src/lib/c").as_deref(), Some("src/lib/c.ts"));
+    // `rootDirs`
+    assert_eq!(resolve("./templates").as_deref(), Some("generated/templates.ts"));
+    // `.` is relative, so neither `paths` nor `baseUrl` is used.
+    assert_eq!(resolve(".").as_deref(), Some("src/index.ts"));
+}
+
+#[test]
+fn node16() {
+    let dir = fixture("node16");
+    let config = load(&dir).unwrap();
+    assert_eq!(config.module_resolution().unwrap(), ModuleResolution::NodeNext);
+
+    let mut resolver = config.resolver();
+    let resolve = |resolver: &PathsResolver, from: &str, target: &str| match resolver.resolve(&FileName::Real(dir.join(from)), target) {
+        Ok(FileName::Real(path)) => Some(path.strip_prefix(&dir).unwrap().to_string_lossy().replace('\\', "/")),
+        _ => None,
+    };
+
+    assert_eq!(resolve(&resolver, "src/index.ts", "./util.js").as_deref(), Some("src/util.ts"));
+    // `type` of `package.json` is `module`, so extensions are required.
+    assert_eq!(resolve(&resolver, "src/index.ts", "./util"), None);
+    assert_eq!(resolve(&resolver, "src/legacy.cts", "./util").as_deref(), Some("src/util.ts"));
+
+    // Conditional exports
+    assert_eq!(
+        resolve(&resolver, "src/index.ts", "pkg").as_deref(),
+        Some("node_modules/pkg/esm/index.d.ts")
+    );
+    assert_eq!(
+        resolve(&resolver, "src/legacy.cts", "pkg").as_deref(),
+        Some("node_modules/pkg/cjs/index.d.ts")
+    );
+    assert_eq!(
+        resolve(&resolver, "src/index.ts", "pkg/feature/a").as_deref(),
+        Some("node_modules/pkg/esm/feature/a.d.mts")
+    );
+    assert_eq!(
+        resolve(&resolver, "src/legacy.cts", "pkg/feature/a").as_deref(),
+        Some("node_modules/pkg/cjs/feature/a.d.cts")
+    );
+    assert_eq!(resolve(&resolver, "src/index.ts", "pkg/private/a"), None);
+    assert_eq!(resolve(&resolver, "src/index.ts", "pkg/esm/index.js"), None);
+
+    // `typesVersions`
+    assert_eq!(
+        resolve(&resolver, "src/index.ts", "old").as_deref(),
+        Some("node_modules/old/ts4/index.d.ts")
+    );
+    assert_eq!(
+        resolve(&resolver, "src/index.ts", "old/sub").as_deref(),
+        Some("node_modules/old/ts4/sub.d.ts")
+    );
+
+    // `imports`
+    assert_eq!(
+        resolve(&resolver, "src/index.ts", "#internal/x").as_deref(),
+        Some("src/internal/x.ts")
+    );
+    assert_eq!(resolve(&resolver, "src/index.ts", "#missing"), None);
+
+    // Directories can't be imported by ECMAScript imports.
+    assert_eq!(resolve(&resolver, "src/internal/x.ts", ".."), None);
+
+    resolver.module_resolution = ModuleResolution::Bundler;
+    assert_eq!(resolve(&resolver, "src/index.ts", "./util").as_deref(), Some("src/util.ts"));
+    assert_eq!(resolve(&resolver, "src/internal/x.ts", "..").as_deref(), Some("src/index.ts"));
+    assert_eq!(
+        resolve(&resolver, "src/legacy.cts", "pkg").as_deref(),
+        Some("node_modules/pkg/esm/index.d.ts")
+    );
+}
@@ -0,0 +1,175 @@
+//! Checks for `isolatedDeclarations`.
+
+use fxhash::FxHashSet;
+use stc_ts_ast_rnode::{
+    RClass, RClassDecl, RClassMember, RDecl, RDefaultDecl, RExportDecl, RExportDefaultDecl, RExportDefaultExpr, RExpr, RFnDecl, RFunction,
+    RLit, RModuleDecl, RModuleItem, RParamOrTsParamProp, RPat, RStmt, RTsParamPropParam, RVarDecl,
+};
+use stc_ts_errors::Error;
+use stc_ts_types::Id;
+use stc_ts_utils::{find_ids_in_pat, PatExt};
+use swc_common::Spanned;
+use swc_ecma_ast::{Accessibility, MethodKind, VarDeclKind};
+
+/// Reports declarations which will be emitted without types, as types are
+/// not inferred in isolated mode.
+///
+/// `used_types` and `used_vars` are used to find non-exported declarations
+/// which are referenced by the public interface of a module.
+pub(crate) struct MissingTypeChecker<'a> {
+    pub is_module: bool,
+    pub used_types: &'a FxHashSet<Id>,
+    pub used_vars: &'a FxHashSet<Id>,
+    pub errors: Vec<Error>,
+}
+
+impl MissingTypeChecker<'_> {
+    pub fn check_items(&mut self, items: &[RModuleItem]) {
+        for item in items {
+            match item {
+                RModuleItem::ModuleDecl(RModuleDecl::ExportDecl(RExportDecl { decl, .. })) => self.check_decl(decl, true),
+
+                RModuleItem::ModuleDecl(RModuleDecl::ExportDefaultDecl(RExportDefaultDecl { decl, .. })) => match decl {
+                    RDefaultDecl::Class(c) => self.check_class(&c.class),
+                    RDefaultDecl::Fn(f) => self.check_fn(&f.function, f.function.span),
+                    RDefaultDecl::TsInterfaceDecl(..) => {}
+                },
+
+                RModuleItem::ModuleDecl(RModuleDecl::ExportDefaultExpr(RExportDefaultExpr { span, expr, .. })) => match &**expr {
+                    RExpr::Ident(..) => {}
+                    _ => self.errors.push(Error::IsolatedDeclarationsDefaultExport { span: *span }),
+                },
+
+                RModuleItem::Stmt(RStmt::Decl(decl)) => self.check_decl(decl, !self.is_module),
+
+                _ => {}
+            }
+        }
+    }
+
+    /// Non-exported declarations are checked only if they are emitted.
+    fn check_decl(&mut self, decl: &RDecl, exported: bool) {
+        match decl {
+            RDecl::Class(RClassDecl { ident, class, .. }) => {
+                if exported || self.used_types.contains(&Id::from(ident)) {
+                    self.check_class(class);
+                }
+            }
+            RDecl::Fn(RFnDecl { ident, function, .. }) => {
+                if exported || self.used_types.contains(&Id::from(ident)) {
+                    self.check_fn(function, ident.span);
+                }
+            }
+            RDecl::Var(var) => self.check_var(var, exported),
+            // Types are always explicit, and namespaces are emitted as-is.
+            RDecl::TsInterface(..) | RDecl::TsTypeAlias(..) | RDecl::TsEnum(..) | RDecl::TsModule(..) => {}
+        }
+    }
+
+    fn check_var(&mut self, var: &RVarDecl, exported: bool) {
+        for decl in &var.decls {
+            if !exported {
+                let ids: Vec<Id> = find_ids_in_pat(&decl.name);
+                if !ids.iter().any(|id| self.used_vars.contains(id)) {
+                    continue;
+                }
+            }
+
+            if decl.name.get_ty().is_some() {
+                continue;
+            }
+
+            // Literals are preserved by the dts passes.
+            let is_literal = match decl.init.as_deref() {
+                Some(RExpr::Lit(RLit::Num(..))) => true,
+                Some(RExpr::Lit(RLit::Str(..) | RLit::Bool(..) | RLit::BigInt(..))) => var.kind == VarDeclKind::Const,
+                _ => false,
+            };
+            if is_literal {
+                continue;
+            }
+
+            self.errors.push(Error::IsolatedDeclarationsVarType { span: decl.name.span() });
+        }
+    }
+
+    /// `span` is used if the return type is missing.
+    fn check_fn(&mut self, function: &RFunction, span: swc_common::Span) {
+        // Overload signatures are emitted instead of the implementation.
+        if function.body.is_some() && function.return_type.is_none() {
+            self.errors.push(Error::IsolatedDeclarationsFnReturnType { span });
+        }
+
+        self.check_params(function.params.iter().map(|p| &p.pat));
+    }
+
+    fn check_class(&mut self, class: &RClass) {
+        for member in &class.body {
+            match member {
+                RClassMember::Constructor(c) => {
+                    if c.accessibility == Some(Accessibility::Private) {
+                        continue;
+                    }
+
+                    for param in &c.params {
+                        match param {
+                            RParamOrTsParamProp::Param(p) => self.check_params(Some(&p.pat)),
+                            RParamOrTsParamProp::TsParamProp(p) => {
+                                let has_type = match &p.param {
+                                    RTsParamPropParam::Ident(i) => i.type_ann.is_some(),
+                                    RTsParamPropParam::Assign(a) => a.type_ann.is_some() || a.left.get_ty().is_some(),
+                                };
+                                if !has_type {
+                                    self.errors.push(Error::IsolatedDeclarationsParamType { span: p.span });
+                                }
+                            }
+                        }
+                    }
+                }
+
+                RClassMember::Method(m) => {
+                    // Private methods are emitted as properties without types.
+                    if m.accessibility == Some(Accessibility::Private) {
+                        continue;
+                    }
+
+                    match m.kind {
+                        MethodKind::Method => {
+                            if m.function.body.is_some() && m.function.return_type.is_none() {
+                                self.errors.push(Error::IsolatedDeclarationsMethodReturnType { span: m.key.span() });
+                            }
+                            self.check_params(m.function.params.iter().map(|p| &p.pat));
+                        }
+                        MethodKind::Getter => {
+                            if m.function.return_type.is_none() {
+                                self.errors.push(Error::IsolatedDeclarationsAccessorType { span: m.key.span() });
+                            }
+                        }
+                        MethodKind::Setter => {}
+                    }
+                }
+
+                RClassMember::ClassProp(p) => {
+                    if p.accessibility != Some(Accessibility::Private) && p.type_ann.is_none() {
+                        self.errors.push(Error::IsolatedDeclarationsPropType { span: p.key.span() });
+                    }
+                }
+
+                _ => {}
+            }
+        }
+    }
+
+    fn check_params<'b>(&mut self, params: impl IntoIterator<Item = &'b RPat>) {
+        for pat in params {
+            let has_type = match pat {
+                RPat::Assign(a) => a.type_ann.is_some() || a.left.get_ty().is_some(),
+                _ => pat.get_ty().is_some(),
+            };
+
+            if !has_type {
+                self.errors.push(Error::IsolatedDeclarationsParamType { span: pat.span() });
+            }
+        }
+    }
+}
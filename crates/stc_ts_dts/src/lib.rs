@@ -12,6 +12,7 @@ use stc_ts_ast_rnode::{
     RPat, RPrivateName, RPrivateProp, RPropName, RStmt, RTsEntityName, RTsEnumDecl, RTsIndexSignature, RTsInterfaceDecl, RTsKeywordType,
     RTsModuleDecl, RTsParamProp, RTsParamPropParam, RTsPropertySignature, RTsType, RTsTypeAliasDecl, RTsTypeAnn, RVarDecl, RVarDeclarator,
 };
+use stc_ts_errors::Error;
 use stc_ts_types::{Id, ModuleTypeData};
 use stc_ts_utils::{find_ids_in_pat, MapWithMut};
 use swc_common::DUMMY_SP;
@@ -20,17 +21,40 @@ use swc_ecma_ast::*;
 use self::{
     ambient::RealImplRemover,
     dce::{get_used, DceForDts},
+    isolated::MissingTypeChecker,
+};
+pub use crate::{
+    mutations::apply_mutations,
+    print::{print_dts, PrintedDts},
 };
-pub use crate::mutations::apply_mutations;
 
 mod ambient;
 mod dce;
+mod isolated;
 mod mutations;
+mod print;
 
 /// Make `module` suitable for .d.ts file.
 ///
 /// - Removes function body
 pub fn cleanup_module_for_dts(module: &mut Vec<RModuleItem>, type_data: &ModuleTypeData) {
+    cleanup(module, type_data, None);
+}
+
+/// Make `module` suitable for .d.ts file without analyzing it, like
+/// `isolatedDeclarations` of `tsc`.
+///
+/// As types are not inferred, this returns errors for declarations emitted
+/// without explicit types.
+pub fn isolated_dts(module: &mut Vec<RModuleItem>) -> Vec<Error> {
+    let mut errors = vec![];
+    cleanup(module, &Default::default(), Some(&mut errors));
+    errors
+}
+
+/// If `isolated_errors` is [Some], `type_data` is not used and declarations
+/// without types are reported.
+fn cleanup(module: &mut Vec<RModuleItem>, type_data: &ModuleTypeData, isolated_errors: Option<&mut Vec<Error>>) {
     let is_module = module.iter().any(|item| match item {
         RModuleItem::ModuleDecl(_) => true,
         RModuleItem::Stmt(_) => false,
@@ -53,14 +77,39 @@ pub fn cleanup_module_for_dts(module: &mut Vec<RModuleItem>, type_data: &ModuleT
         (v.used_types, v.used_vars)
     };
 
+    let isolated = isolated_errors.is_some();
+    if let Some(errors) = isolated_errors {
+        let mut checker = MissingTypeChecker {
+            is_module,
+            used_types: &used_types,
+            used_vars: &used_vars,
+            errors: vec![],
+        };
+        checker.check_items(module);
+        errors.extend(checker.errors);
+    }
+
     module.visit_mut_with(&mut Dts {
         preserve_stmt: false,
         used_types,
         used_vars,
     });
 
+    let used = if isolated {
+        // Interfaces which are not used are already removed.
+        module
+            .iter()
+            .filter_map(|item| match item {
+                RModuleItem::Stmt(RStmt::Decl(RDecl::TsInterface(i))) => Some(Id::from(&i.id)),
+                _ => None,
+            })
+            .collect()
+    } else {
+        get_used(&type_data)
+    };
+
     module.visit_mut_with(&mut DceForDts {
-        used: get_used(&type_data),
+        used,
         info: &type_data,
         current_class: None,
         in_declare: false,
@@ -0,0 +1,93 @@
+use std::{
+    path::{Component, Path},
+    sync::Arc,
+};
+
+use anyhow::{Context, Error};
+use swc_common::{source_map::SourceMapGenConfig, FileName, SourceMap};
+use swc_ecma_ast::Module;
+use swc_ecma_codegen::{text_writer::JsWriter, Emitter};
+
+/// A printed `.d.ts` file.
+#[derive(Debug, Clone)]
+pub struct PrintedDts {
+    pub code: String,
+
+    /// Content of the declaration map (`.d.ts.map`), if requested.
+    pub map: Option<String>,
+}
+
+/// Prints `module`, which is a module processed by
+/// [crate::cleanup_module_for_dts].
+///
+/// If `map_for` is the path of the `.d.ts` file, a declaration map is
+/// generated, and the code ends with a `sourceMappingURL` comment referencing
+/// `<file>.map`. Sources in the map are relative to the directory of the
+/// `.d.ts` file, like `tsc` does.
+pub fn print_dts(cm: Arc<SourceMap>, module: &Module, map_for: Option<&Path>) -> Result<PrintedDts, Error> {
+    let mut buf = vec![];
+    let mut mappings = vec![];
+    {
+        let mut emitter = Emitter {
+            cfg: Default::default(),
+            comments: None,
+            cm: cm.clone(),
+            wr: box JsWriter::new(cm.clone(), "\n", &mut buf, map_for.map(|_| &mut mappings)),
+        };
+
+        emitter.emit_module(module).context("failed to emit module")?;
+    }
+    let mut code = String::from_utf8(buf).context("emitted code is not utf-8")?;
+
+    let dts = match map_for {
+        Some(dts) => dts,
+        None => return Ok(PrintedDts { code, map: None }),
+    };
+    let dir = dts.parent().unwrap_or_else(|| Path::new("/"));
+    let file_name = dts.file_name().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+
+    let mut map = cm.build_source_map_with_config(&mut mappings, None, DtsMapConfig { dir });
+    map.set_file(Some(&file_name));
+
+    let mut map_buf = vec![];
+    map.to_writer(&mut map_buf).context("failed to write declaration map")?;
+
+    if !code.ends_with('\n') {
+        code.push('\n');
+    }
+    code.push_str(&format!("//# sourceMappingURL={}.map\n", file_name));
+
+    Ok(PrintedDts {
+        code,
+        map: Some(String::from_utf8(map_buf).context("declaration map is not utf-8")?),
+    })
+}
+
+struct DtsMapConfig<'a> {
+    /// Directory of the `.d.ts` file.
+    dir: &'a Path,
+}
+
+impl SourceMapGenConfig for DtsMapConfig<'_> {
+    fn file_name_to_source(&self, f: &FileName) -> String {
+        match f {
+            FileName::Real(path) => relative_path(self.dir, path),
+            _ => f.to_string(),
+        }
+    }
+
+    fn inline_sources_content(&self, _: &FileName) -> bool {
+        false
+    }
+}
+
+/// Returns `path` relative to `base`, separated by `/`.
+fn relative_path(base: &Path, path: &Path) -> String {
+    let base = base.components().filter(|c| *c != Component::CurDir).collect::<Vec<_>>();
+    let path = path.components().filter(|c| *c != Component::CurDir).collect::<Vec<_>>();
+    let common = base.iter().zip(&path).take_while(|(a, b)| a == b).count();
+
+    let mut parts = vec![String::from(".."); base.len() - common];
+    parts.extend(path[common..].iter().map(|c| c.as_os_str().to_string_lossy().into_owned()));
+    parts.join("/")
+}
@@ -0,0 +1,5 @@
+export function foo(a: number): number {
+    return a;
+}
+
+export const bar: string = '';
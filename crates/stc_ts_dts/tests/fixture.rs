@@ -19,7 +19,7 @@ use rnode::{NodeIdGenerator, RNode};
 use stc_testing::get_git_root;
 use stc_ts_ast_rnode::RModule;
 use stc_ts_builtin_types::Lib;
-use stc_ts_dts::{apply_mutations, cleanup_module_for_dts};
+use stc_ts_dts::{apply_mutations, cleanup_module_for_dts, print_dts};
 use stc_ts_env::{Env, ModuleConfig};
 use stc_ts_file_analyzer::{
     analyzer::{Analyzer, NoopLoader},
@@ -29,7 +29,7 @@ use stc_ts_file_analyzer::{
 use stc_ts_storage::Single;
 use stc_ts_types::module_id;
 use stc_ts_utils::StcComments;
-use swc_common::{input::SourceFileInput, FileName, SyntaxContext, GLOBALS};
+use swc_common::{input::SourceFileInput, FileName, SourceMap, SyntaxContext, GLOBALS};
 use swc_ecma_ast::{EsVersion, Ident, Module, TsIntersectionType, TsKeywordTypeKind, TsLit, TsLitType, TsType, TsUnionType};
 use swc_ecma_codegen::{text_writer::JsWriter, Emitter};
 use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
@@ -47,6 +47,39 @@ fn fixture(input: PathBuf) {
     do_test(&input).unwrap();
 }
 
+#[test]
+fn declaration_map() {
+    let dir = canonicalize("tests/declaration_map").unwrap();
+    let input = dir.join("src").join("input.ts");
+
+    testing::run_test2(false, |cm, _| {
+        let (module, _) = generate_dts(&cm, &input);
+        let dts = print_dts(cm, &module.into_orig(), Some(&dir.join("types").join("input.d.ts"))).unwrap();
+
+        assert!(dts.code.ends_with("//# sourceMappingURL=input.d.ts.map\n"));
+
+        let map = sourcemap::SourceMap::from_slice(dts.map.unwrap().as_bytes()).unwrap();
+        assert_eq!(map.get_file(), Some("input.d.ts"));
+        assert_eq!(map.get_source(0), Some("../src/input.ts"));
+
+        // Identifiers should be mapped to their declarations.
+        for (name, src_line, src_col) in [("foo", 0, 16), ("bar", 4, 13)] {
+            let (line, col) = dts
+                .code
+                .lines()
+                .enumerate()
+                .find_map(|(i, l)| l.find(name).map(|col| (i as u32, col as u32)))
+                .unwrap();
+            let token = map.lookup_token(line, col).unwrap();
+
+            assert_eq!((token.get_src_line(), token.get_src_col()), (src_line, src_col), "{}", name);
+        }
+
+        Ok(())
+    })
+    .unwrap();
+}
+
 fn do_test(file_name: &Path) -> Result<(), StdErr> {
     if let Ok(test) = env::var("TEST") {
         if !file_name.to_string_lossy().replace("/", "::").contains(&test) {
@@ -61,7 +94,6 @@ fn do_test(file_name: &Path) -> Result<(), StdErr> {
         println!("---------- Input ----------\n{}", s);
     }
 
-    let fname = file_name.display().to_string();
     let (expected_code, expected_module) = get_correct_dts(&file_name);
     let expected_module = drop_span(expected_module.fold_with(&mut Normalizer));
     println!("---------- Expected ----------\n{}", expected_code);
@@ -71,59 +103,7 @@ fn do_test(file_name: &Path) -> Result<(), StdErr> {
 
     let res = testing::Tester::new().print_errors(|cm, handler| {
         let handler = Arc::new(handler);
-        let fm = cm.load_file(&file_name).unwrap();
-        let env = Env::simple(
-            Default::default(),
-            EsVersion::latest(),
-            ModuleConfig::None,
-            &Lib::load("es2019.full"),
-        );
-        let stable_env = env.shared().clone();
-        let generator = module_id::ModuleIdGenerator::default();
-        let path = Arc::new(FileName::Real(file_name.clone()));
-        let (module_id, top_level_mark) = generator.generate(&path);
-
-        let mut storage = Single {
-            parent: None,
-            id: module_id,
-            path,
-            info: Default::default(),
-            is_dts: false,
-        };
-
-        let mut node_id_gen = NodeIdGenerator::default();
-        let comments = StcComments::default();
-
-        let lexer = Lexer::new(
-            Syntax::Typescript(TsConfig {
-                tsx: fname.contains("tsx"),
-                decorators: true,
-                ..Default::default()
-            }),
-            EsVersion::latest(),
-            SourceFileInput::from(&*fm),
-            Some(&comments),
-        );
-        let mut parser = Parser::new_from(lexer);
-        let module = parser.parse_module().unwrap();
-        let module = GLOBALS.set(stable_env.swc_globals(), || {
-            module.fold_with(&mut resolver(stable_env.marks().unresolved_mark(), top_level_mark, true))
-        });
-        let mut module = RModule::from_orig(&mut node_id_gen, module);
-        let mut mutations;
-        {
-            let mut analyzer = Analyzer::root(env, cm.clone(), Default::default(), box &mut storage, &NoopLoader, None);
-            GLOBALS.set(stable_env.swc_globals(), || {
-                module.validate_with(&mut analyzer).unwrap();
-            });
-
-            mutations = analyzer.mutations.unwrap()
-        }
-
-        {
-            apply_mutations(&mut mutations, &mut module);
-            cleanup_module_for_dts(&mut module.body, &storage.info.exports);
-        }
+        let (module, storage) = generate_dts(&cm, &file_name);
 
         let expected_module = {
             let mut buf = vec![];
@@ -222,6 +202,66 @@ fn do_test(file_name: &Path) -> Result<(), StdErr> {
     Ok(())
 }
 
+/// Analyzes `file_name`, and returns the module for its `.d.ts` file with the
+/// storage used for the analysis.
+fn generate_dts(cm: &Arc<SourceMap>, file_name: &Path) -> (RModule, Single<'static>) {
+    let fm = cm.load_file(file_name).unwrap();
+    let env = Env::simple(
+        Default::default(),
+        EsVersion::latest(),
+        ModuleConfig::None,
+        &Lib::load("es2019.full"),
+    );
+    let stable_env = env.shared().clone();
+    let generator = module_id::ModuleIdGenerator::default();
+    let path = Arc::new(FileName::Real(file_name.to_path_buf()));
+    let (module_id, top_level_mark) = generator.generate(&path);
+
+    let mut storage = Single {
+        parent: None,
+        id: module_id,
+        path,
+        info: Default::default(),
+        is_dts: false,
+    };
+
+    let mut node_id_gen = NodeIdGenerator::default();
+    let comments = StcComments::default();
+
+    let lexer = Lexer::new(
+        Syntax::Typescript(TsConfig {
+            tsx: file_name.to_string_lossy().contains("tsx"),
+            decorators: true,
+            ..Default::default()
+        }),
+        EsVersion::latest(),
+        SourceFileInput::from(&*fm),
+        Some(&comments),
+    );
+    let mut parser = Parser::new_from(lexer);
+    let module = parser.parse_module().unwrap();
+    let module = GLOBALS.set(stable_env.swc_globals(), || {
+        module.fold_with(&mut resolver(stable_env.marks().unresolved_mark(), top_level_mark, true))
+    });
+    let mut module = RModule::from_orig(&mut node_id_gen, module);
+    let mut mutations;
+    {
+        let mut analyzer = Analyzer::root(env, cm.clone(), Default::default(), box &mut storage, &NoopLoader, None);
+        GLOBALS.set(stable_env.swc_globals(), || {
+            module.validate_with(&mut analyzer).unwrap();
+        });
+
+        mutations = analyzer.mutations.unwrap()
+    }
+
+    {
+        apply_mutations(&mut mutations, &mut module);
+        cleanup_module_for_dts(&mut module.body, &storage.info.exports);
+    }
+
+    (module, storage)
+}
+
 fn parse_dts(src: &str) -> Module {
     ::testing::run_test2(false, |cm, handler| {
         let fm = cm.new_source_file(FileName::Anon, src.to_string());
@@ -0,0 +1,111 @@
+use rnode::{NodeIdGenerator, RNode};
+use stc_ts_ast_rnode::RModule;
+use stc_ts_dts::{isolated_dts, print_dts};
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
+
+/// Returns the generated `.d.ts` file and codes of errors.
+fn generate(src: &str) -> (String, Vec<usize>) {
+    testing::run_test2(false, |cm, _| {
+        let fm = cm.new_source_file(FileName::Anon, src.to_string());
+
+        let lexer = Lexer::new(
+            Syntax::Typescript(TsConfig { ..Default::default() }),
+            EsVersion::latest(),
+            StringInput::from(&*fm),
+            None,
+        );
+        let module = Parser::new_from(lexer).parse_module().unwrap();
+        let mut module = RModule::from_orig(&mut NodeIdGenerator::invalid(), module);
+
+        let errors = isolated_dts(&mut module.body);
+        let dts = print_dts(cm, &module.into_orig(), None).unwrap();
+
+        Ok((dts.code, errors.iter().map(|err| err.code()).collect()))
+    })
+    .unwrap()
+}
+
+#[test]
+fn explicit_types() {
+    let (code, errors) = generate(
+        "interface Internal {
+    a: number;
+}
+export function foo(a: number, b: Internal = { a: 1 }): Internal {
+    return b;
+}
+export const bar: string = 'a';
+export const baz = 1;
+export class Foo {
+    private secret = 1;
+    x: number = 1;
+    method(): void {}
+}
+",
+    );
+
+    assert_eq!(errors, Vec::<usize>::new());
+    assert!(code.contains("interface Internal"), "{}", code);
+    assert!(
+        code.contains("export declare function foo(a: number, b?: Internal): Internal;"),
+        "{}",
+        code
+    );
+    assert!(!code.contains("return"), "{}", code);
+}
+
+#[test]
+fn missing_types() {
+    let (_, errors) = generate(
+        "export function foo(a) {
+    return a;
+}
+export let bar = 'a';
+export class Foo {
+    x = 1;
+    method() {}
+    get y() {
+        return 1;
+    }
+}
+export default foo(1);
+",
+    );
+
+    assert_eq!(errors, vec![9007, 9011, 9010, 9012, 9008, 9009, 9037]);
+}
+
+#[test]
+fn overloads() {
+    let (_, errors) = generate(
+        "export function foo(a: number): number;
+export function foo(a: string): string;
+export function foo(a) {
+    return a;
+}
+",
+    );
+
+    assert_eq!(errors, Vec::<usize>::new());
+}
+
+#[test]
+fn unexported() {
+    let (code, errors) = generate(
+        "function helper(a) {
+    return a;
+}
+function unused(a) {
+    return a;
+}
+export type T = typeof helper;
+",
+    );
+
+    // Only declarations referenced by exports are emitted.
+    assert_eq!(errors, vec![9007, 9011]);
+    assert!(code.contains("helper"), "{}", code);
+    assert!(!code.contains("unused"), "{}", code);
+}
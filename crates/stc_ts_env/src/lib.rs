@@ -4,6 +4,7 @@ use derivative::Derivative;
 use parking_lot::Mutex;
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
+use stc_ts_builtin_types::Lib;
 use stc_ts_errors::Error;
 use stc_ts_type_ops::Fix;
 use stc_ts_types::{Id, Type};
@@ -35,6 +36,9 @@ pub struct Env {
     rule: Rule,
     target: EsVersion,
     module: ModuleConfig,
+    jsx: JsxConfig,
+    /// Libraries used to create `builtin`, if known.
+    libs: Option<Vec<Lib>>,
     builtin: Arc<BuiltIn>,
     global_types: Arc<Mutex<FxHashMap<JsWord, Type>>>,
     global_vars: Arc<Mutex<FxHashMap<JsWord, Type>>>,
@@ -47,6 +51,8 @@ impl Env {
             builtin,
             target,
             module,
+            jsx: Default::default(),
+            libs: Default::default(),
             global_types: Default::default(),
             global_vars: Default::default(),
             rule,
@@ -69,6 +75,32 @@ impl Env {
         self.rule
     }
 
+    pub const fn jsx(&self) -> &JsxConfig {
+        &self.jsx
+    }
+
+    pub fn with_jsx(mut self, jsx: JsxConfig) -> Self {
+        self.jsx = jsx;
+        self
+    }
+
+    /// Returns [None] if the builtin types are not created from libraries.
+    pub fn libs(&self) -> Option<&[Lib]> {
+        self.libs.as_deref()
+    }
+
+    pub fn with_libs(mut self, libs: Vec<Lib>) -> Self {
+        self.libs = Some(libs);
+        self
+    }
+
+    /// Replaces builtin types. Global declarations of analyzed files are
+    /// preserved.
+    pub fn with_builtin(mut self, builtin: Arc<BuiltIn>) -> Self {
+        self.builtin = builtin;
+        self
+    }
+
     pub fn declare_global_var(&mut self, name: JsWord, ty: Type) {
         ty.assert_clone_cheap();
 
@@ -181,6 +213,75 @@ pub enum ModuleConfig {
     System,
     /// `esnext`
     EsNext,
+    /// `node16`
+    Node16,
+    /// `nodenext`
+    NodeNext,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, StringEnum)]
+pub enum JsxMode {
+    /// `preserve`
+    Preserve,
+    /// `react`
+    React,
+    /// `react-native`
+    ReactNative,
+    /// `react-jsx`
+    ReactJsx,
+    /// `react-jsxdev`
+    ReactJsxDev,
+}
+
+/// `jsx`, `jsxFactory`, `jsxFragmentFactory` and `jsxImportSource`.
+#[derive(Debug, Clone, Default)]
+pub struct JsxConfig {
+    /// `None` if `--jsx` is not specified, in which case JSX is an error.
+    pub mode: Option<JsxMode>,
+    /// `React.createElement` if not specified.
+    pub factory: Option<JsWord>,
+    /// `React.Fragment` if not specified.
+    pub fragment_factory: Option<JsWord>,
+    /// `react` if not specified.
+    pub import_source: Option<JsWord>,
+}
+
+impl JsxConfig {
+    /// `true` if elements are compiled to calls of functions imported from
+    /// `jsxImportSource`, instead of calls of `jsxFactory`.
+    pub fn is_automatic(&self) -> bool {
+        matches!(self.mode, Some(JsxMode::ReactJsx) | Some(JsxMode::ReactJsxDev))
+    }
+
+    /// The first identifier of `jsxFactory`, which should be in scope.
+    pub fn factory_root(&self) -> JsWord {
+        match &self.factory {
+            Some(factory) => factory.split('.').next().unwrap_or_default().into(),
+            None => "React".into(),
+        }
+    }
+
+    /// `jsxFragmentFactory`, or `React.Fragment` if not specified.
+    pub fn fragment_factory(&self) -> JsWord {
+        self.fragment_factory.clone().unwrap_or_else(|| "React.Fragment".into())
+    }
+
+    /// The first identifier of `jsxFragmentFactory`, which should be in scope.
+    pub fn fragment_factory_root(&self) -> JsWord {
+        self.fragment_factory().split('.').next().unwrap_or_default().into()
+    }
+
+    /// The module imported implicitly by files with JSX, like
+    /// `react/jsx-runtime`, if [JsxConfig::is_automatic] is true.
+    pub fn runtime_module(&self) -> Option<JsWord> {
+        let runtime = match self.mode? {
+            JsxMode::ReactJsx => "jsx-runtime",
+            JsxMode::ReactJsxDev => "jsx-dev-runtime",
+            _ => return None,
+        };
+
+        Some(format!("{}/{}", self.import_source.as_deref().unwrap_or("react"), runtime).into())
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -200,5 +301,7 @@ pub struct Rule {
     pub no_strict_generic_checks: bool,
     pub no_unused_locals: bool,
     pub no_unused_parameters: bool,
+    pub no_unchecked_indexed_access: bool,
+    pub exact_optional_property_types: bool,
     pub use_define_property_for_class_fields: bool,
 }
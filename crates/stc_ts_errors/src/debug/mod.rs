@@ -9,7 +9,7 @@ use stc_ts_types::{Id, IndexedAccessType, Ref, Type, TypeLit, TypeParam};
 use stc_utils::cache::ALLOW_DEEP_CLONE;
 use swc_common::{sync::Lrc, SourceMap, TypeEq, DUMMY_SP};
 use swc_ecma_ast::*;
-use swc_ecma_codegen::{text_writer::JsWriter, Emitter};
+use swc_ecma_codegen::{text_writer::JsWriter, Emitter, Node};
 use tracing::info;
 
 pub mod debugger;
@@ -100,6 +100,36 @@ pub fn dump_type_as_string(cm: &Lrc<SourceMap>, t: &Type) -> String {
     s.to_string()
 }
 
+/// Prints a type for users, e.g. in hover of the language server.
+///
+/// Unlike [dump_type_as_string], this works in release builds and does not
+/// include syntax contexts.
+pub fn type_to_string(t: &Type) -> String {
+    if let Type::Namespace(..) = t.normalize() {
+        return "namespace".into();
+    }
+
+    let cm = Lrc::new(SourceMap::default());
+    let ty = RTsType::from(ALLOW_DEEP_CLONE.set(&(), || t.clone())).into_orig();
+
+    let mut buf = vec![];
+    {
+        let mut emitter = Emitter {
+            cfg: swc_ecma_codegen::Config {
+                minify: false,
+                ..Default::default()
+            },
+            cm: cm.clone(),
+            comments: None,
+            wr: box JsWriter::new(cm, "\n", &mut buf, None),
+        };
+
+        ty.emit_with(&mut emitter).unwrap();
+    }
+
+    String::from_utf8_lossy(&buf).trim().to_string()
+}
+
 pub fn dbg_type(name: &str, cm: &Lrc<SourceMap>, t: &Type) {
     let s = dump_type_as_string(cm, t);
     eprintln!("===== ===== ===== Type ({}) ===== ===== =====\n{}", name, s);
@@ -4,7 +4,6 @@
 #![feature(specialization)]
 
 use std::{
-    borrow::Cow,
     fmt,
     fmt::{Debug, Display},
     ops::RangeInclusive,
@@ -18,7 +17,7 @@ use static_assertions::assert_eq_size;
 use stc_ts_ast_rnode::RTsModuleName;
 use stc_ts_types::{name::Name, Id, Key, ModuleId, Type, TypeElement, TypeParamInstantiation};
 use stc_utils::stack::StackOverflowError;
-use swc_atoms::JsWord;
+use swc_atoms::{js_word, JsWord};
 use swc_common::{
     errors::{DiagnosticId, Handler},
     Span, Spanned, DUMMY_SP,
@@ -28,6 +27,7 @@ use swc_ecma_ast::{AssignOp, BinaryOp, UnaryOp, UpdateOp};
 pub use self::result_ext::DebugExt;
 
 pub mod debug;
+mod message;
 mod result_ext;
 
 impl Errors {
@@ -132,6 +132,145 @@ pub enum Error {
         span: Span,
     },
 
+    /// TS2578
+    UnusedTsExpectError {
+        span: Span,
+    },
+
+    /// TS7030
+    NotAllCodePathsReturnValue {
+        span: Span,
+    },
+
+    /// TS7029
+    FallthroughCaseInSwitch {
+        span: Span,
+    },
+
+    /// TS17004
+    JsxNotEnabled {
+        span: Span,
+    },
+
+    /// TS7026
+    NoJsxIntrinsicElements {
+        span: Span,
+    },
+
+    /// TS2602
+    NoJsxElement {
+        span: Span,
+    },
+
+    /// TS2322
+    NoSuchJsxAttr {
+        span: Span,
+        name: JsWord,
+        props: Box<Type>,
+    },
+
+    /// TS2710
+    JsxChildrenSpecifiedTwice {
+        span: Span,
+        name: JsWord,
+    },
+
+    /// TS2604
+    JsxElementNotCallable {
+        span: Span,
+        name: JsWord,
+    },
+
+    /// TS2786
+    InvalidJsxComponent {
+        span: Span,
+        name: JsWord,
+    },
+
+    /// TS2875
+    NoJsxRuntime {
+        span: Span,
+        module: JsWord,
+    },
+
+    /// TS2879
+    NoJsxFragmentFactory {
+        span: Span,
+        name: JsWord,
+    },
+
+    /// TS17016
+    JsxFragmentFactoryRequired {
+        span: Span,
+    },
+
+    /// TS6133
+    UnusedLocal {
+        span: Span,
+        name: Id,
+    },
+
+    /// TS6196
+    UnusedType {
+        span: Span,
+        name: Id,
+    },
+
+    /// TS6192
+    AllImportsUnused {
+        span: Span,
+    },
+
+    /// TS6198
+    AllDestructuredElementsUnused {
+        span: Span,
+    },
+
+    /// TS6199
+    AllVarsUnused {
+        span: Span,
+    },
+
+    /// TS6205
+    AllTypeParamsUnused {
+        span: Span,
+    },
+
+    /// TS9007
+    IsolatedDeclarationsFnReturnType {
+        span: Span,
+    },
+
+    /// TS9008
+    IsolatedDeclarationsMethodReturnType {
+        span: Span,
+    },
+
+    /// TS9009
+    IsolatedDeclarationsAccessorType {
+        span: Span,
+    },
+
+    /// TS9010
+    IsolatedDeclarationsVarType {
+        span: Span,
+    },
+
+    /// TS9011
+    IsolatedDeclarationsParamType {
+        span: Span,
+    },
+
+    /// TS9012
+    IsolatedDeclarationsPropType {
+        span: Span,
+    },
+
+    /// TS9037
+    IsolatedDeclarationsDefaultExport {
+        span: Span,
+    },
+
     /// TS2454
     VarMayNotBeInitialized {
         span: Span,
@@ -299,6 +438,13 @@ pub enum Error {
     /// TS2307
     ModuleNotFound {
         span: Span,
+        specifier: JsWord,
+    },
+
+    /// TS2688
+    TypeDefinitionNotFound {
+        span: Span,
+        name: JsWord,
     },
 
     /// TS5061
@@ -485,6 +631,7 @@ pub enum Error {
     /// TS2300
     DuplicateProperty {
         span: Span,
+        key: Box<Key>,
     },
 
     /// TS2661
@@ -551,6 +698,11 @@ pub enum Error {
         span: Span,
     },
 
+    /// TS2683
+    ThisImplicitlyAny {
+        span: Span,
+    },
+
     SuperInClassWithoutSuper {
         span: Span,
     },
@@ -627,6 +779,7 @@ pub enum Error {
     NoSuchEnumVariant {
         span: Span,
         name: JsWord,
+        enum_name: Id,
     },
 
     ObjectIsPossiblyNull {
@@ -669,8 +822,11 @@ pub enum Error {
         inner: Box<Error>,
     },
 
+    /// TS2305, or TS1192 for default imports.
     ImportFailed {
         span: Span,
+        /// The module specifier.
+        specifier: JsWord,
         orig: Id,
         id: Id,
     },
@@ -957,6 +1113,15 @@ pub enum Error {
         msg: String,
     },
 
+    /// TS1xxx
+    ///
+    /// Reported by the parser.
+    SyntaxError {
+        span: Span,
+        code: usize,
+        msg: String,
+    },
+
     ResolvedFailed {
         span: Span,
         base: Box<PathBuf>,
@@ -1601,6 +1766,7 @@ impl Error {
     /// TypeScript error code.
     pub fn code(&self) -> usize {
         match self {
+            Error::SyntaxError { code, .. } => *code,
             Error::TS1016 { .. } => 1016,
             Error::TS1063 { .. } => 1063,
             Error::TS1094 { .. } => 1094,
@@ -1769,6 +1935,8 @@ impl Error {
 
             Error::ThisRefToModuleOrNamespace { .. } => 2331,
 
+            Error::ThisImplicitlyAny { .. } => 2683,
+
             Error::CannotReferenceThisInComputedPropName { .. } => 2465,
             Error::CannotReferenceSuperInComputedPropName { .. } => 2466,
             Error::DeclaringTypeParamReferencedByComputedPropName { .. } => 2467,
@@ -1884,6 +2052,12 @@ impl Error {
 
             Error::ModuleNotFound { .. } => 2307,
 
+            Error::ImportFailed { orig, .. } if *orig.sym() == js_word!("default") => 1192,
+
+            Error::ImportFailed { .. } => 2305,
+
+            Error::TypeDefinitionNotFound { .. } => 2688,
+
             Error::DuplicateConstructor { .. } => 2392,
 
             Error::DuplicateFnImpl { .. } => 2393,
@@ -1955,6 +2129,58 @@ impl Error {
 
             Error::UnreachableCode { .. } => 7027,
 
+            Error::UnusedTsExpectError { .. } => 2578,
+
+            Error::NotAllCodePathsReturnValue { .. } => 7030,
+
+            Error::FallthroughCaseInSwitch { .. } => 7029,
+
+            Error::JsxNotEnabled { .. } => 17004,
+
+            Error::NoJsxIntrinsicElements { .. } => 7026,
+
+            Error::NoJsxElement { .. } => 2602,
+
+            Error::NoSuchJsxAttr { .. } => 2322,
+
+            Error::JsxChildrenSpecifiedTwice { .. } => 2710,
+
+            Error::JsxElementNotCallable { .. } => 2604,
+
+            Error::InvalidJsxComponent { .. } => 2786,
+
+            Error::NoJsxRuntime { .. } => 2875,
+
+            Error::NoJsxFragmentFactory { .. } => 2879,
+
+            Error::JsxFragmentFactoryRequired { .. } => 17016,
+
+            Error::UnusedLocal { .. } => 6133,
+
+            Error::UnusedType { .. } => 6196,
+
+            Error::AllImportsUnused { .. } => 6192,
+
+            Error::AllDestructuredElementsUnused { .. } => 6198,
+
+            Error::AllVarsUnused { .. } => 6199,
+
+            Error::AllTypeParamsUnused { .. } => 6205,
+
+            Error::IsolatedDeclarationsFnReturnType { .. } => 9007,
+
+            Error::IsolatedDeclarationsMethodReturnType { .. } => 9008,
+
+            Error::IsolatedDeclarationsAccessorType { .. } => 9009,
+
+            Error::IsolatedDeclarationsVarType { .. } => 9010,
+
+            Error::IsolatedDeclarationsParamType { .. } => 9011,
+
+            Error::IsolatedDeclarationsPropType { .. } => 9012,
+
+            Error::IsolatedDeclarationsDefaultExport { .. } => 9037,
+
             Error::ConstEnumMemberHasInifinityAsInit { .. } => 2477,
 
             Error::ConstEnumMemberHasNaNAsInit { .. } => 2478,
@@ -2011,14 +2237,6 @@ impl Error {
         }
     }
 
-    fn msg(&self) -> Cow<'static, str> {
-        match self {
-            Self::Unimplemented { msg, .. } => format!("unimplemented: {}", msg).into(),
-
-            _ => format!("{:#?}", self).into(),
-        }
-    }
-
     #[cold]
     pub fn emit(self, h: &Handler) {
         let span = self.span();
@@ -2029,6 +2247,17 @@ impl Error {
             DiagnosticId::Error(format!("TS{}", Self::normalize_error_code(self.code()))),
         );
 
+        for (depth, cause) in self.cause_chain() {
+            let msg = format!("{}{}", "  ".repeat(depth), cause.msg());
+            let cause_span = cause.span();
+
+            if cause_span.is_dummy() || cause_span == span {
+                err.note(&msg);
+            } else {
+                err.span_note(cause_span, &msg);
+            }
+        }
+
         err.emit();
     }
 
@@ -0,0 +1,929 @@
+//! Human-readable messages of errors.
+//!
+//! Messages follow the wording of `tsc` as closely as possible, so that users
+//! can search for them.
+
+use std::{borrow::Cow, ops::RangeInclusive};
+
+use stc_ts_ast_rnode::RTsModuleName;
+use stc_ts_types::{name::Name, Id, Key, Type, TypeElement};
+use swc_atoms::js_word;
+
+use crate::{debug::type_to_string, Error};
+
+impl Error {
+    /// The message of the error, without causes.
+    ///
+    /// This uses the codegen of `RTsType` to print types, so this should be
+    /// called with `swc_common::GLOBALS` set.
+    pub fn msg(&self) -> Cow<'static, str> {
+        match self {
+            Self::Unimplemented { msg, .. } => format!("unimplemented: {}", msg).into(),
+
+            Self::SyntaxError { msg, .. } => msg.clone().into(),
+
+            Self::DebugContext(c) => c.inner.msg(),
+
+            Self::Errors { errors, .. } | Self::TupleAssignError { errors, .. } if !errors.is_empty() => {
+                errors.iter().map(|err| err.msg()).collect::<Vec<_>>().join("\n").into()
+            }
+            Self::Errors { .. } | Self::TupleAssignError { .. } => "Type is not assignable to the target type.".into(),
+
+            Self::InvalidInterfaceInheritance { .. } => "Interface incorrectly extends its base interface.".into(),
+
+            Self::TupleTooShort { .. } => "Tuple type is too short to have an element at this index.".into(),
+
+            Self::VarDeclNotCompatible { .. } | Self::RedeclaredVarWithDifferentType { .. } => {
+                "Subsequent variable declarations must have the same type.".into()
+            }
+
+            Self::IntrinsicIsBuiltinOnly { .. } => {
+                "The 'intrinsic' keyword can only be used to declare compiler provided intrinsic types.".into()
+            }
+
+            Self::TypeParamsProvidedButCalleeIsNotGeneric { .. } | Self::AnyTypeUsedAsCalleeWithTypeArgs { .. } => {
+                "Untyped function calls may not accept type arguments.".into()
+            }
+
+            Self::OptionalAndNonOptionalMethodPropertyMixed { .. } => "Overload signatures must all be optional or required.".into(),
+
+            Self::UpdateArgMustBeVariableOrPropertyAccess { .. } | Self::ExprInvalidForUpdateArg { .. } => {
+                "The operand of an increment or decrement operator must be a variable or a property access.".into()
+            }
+
+            Self::InterfaceNotCompatible { .. } => "Interface cannot simultaneously extend types with conflicting members.".into(),
+
+            Self::CannotUseTypeAsIndexIndex { .. } => "Type cannot be used as an index type.".into(),
+
+            Self::OnlyOneEnumCanOmitInit { .. } => {
+                "In an enum with multiple declarations, only one declaration can omit an initializer for its first enum element.".into()
+            }
+
+            Self::ConstEnumMemberHasInifinityAsInit { .. } => "'const' enum member initializer was evaluated to a non-finite value.".into(),
+
+            Self::ConstEnumMemberHasNaNAsInit { .. } => "'const' enum member initializer was evaluated to disallowed value 'NaN'.".into(),
+
+            Self::UnreachableCode { .. } => "Unreachable code detected.".into(),
+
+            Self::UnusedTsExpectError { .. } => "Unused '@ts-expect-error' directive.".into(),
+
+            Self::NotAllCodePathsReturnValue { .. } => "Not all code paths return a value.".into(),
+
+            Self::FallthroughCaseInSwitch { .. } => "Fallthrough case in switch.".into(),
+
+            Self::JsxNotEnabled { .. } => "Cannot use JSX unless the '--jsx' flag is provided.".into(),
+
+            Self::NoJsxIntrinsicElements { .. } => {
+                "JSX element implicitly has type 'any' because no interface 'JSX.IntrinsicElements' exists.".into()
+            }
+
+            Self::NoJsxElement { .. } => {
+                "JSX element implicitly has type 'any' because the global type 'JSX.Element' does not exist.".into()
+            }
+
+            Self::NoSuchJsxAttr { name, props, .. } => format!("Property '{}' does not exist on type '{}'.", name, ty(props)).into(),
+
+            Self::JsxChildrenSpecifiedTwice { name, .. } => format!(
+                "'{}' are specified twice. The attribute named '{}' will be overwritten.",
+                name, name
+            )
+            .into(),
+
+            Self::JsxElementNotCallable { name, .. } => {
+                format!("JSX element type '{}' does not have any construct or call signatures.", name).into()
+            }
+
+            Self::InvalidJsxComponent { name, .. } => format!("'{}' cannot be used as a JSX component.", name).into(),
+
+            Self::NoJsxRuntime { module, .. } => format!(
+                "This JSX tag requires the module path '{}' to exist, but none could be found. Make sure you have types for the \
+                 appropriate package installed.",
+                module
+            )
+            .into(),
+
+            Self::NoJsxFragmentFactory { name, .. } => format!(
+                "Using JSX fragments requires fragment factory '{}' to be in scope, but it could not be found.",
+                name
+            )
+            .into(),
+
+            Self::JsxFragmentFactoryRequired { .. } => "The 'jsxFragmentFactory' compiler option must be provided to use JSX fragments \
+                                                        with the 'jsxFactory' compiler option."
+                .into(),
+
+            Self::UnusedLocal { name, .. } => format!("'{}' is declared but its value is never read.", name.sym()).into(),
+
+            Self::UnusedType { name, .. } => format!("'{}' is declared but never used.", name.sym()).into(),
+
+            Self::AllImportsUnused { .. } => "All imports in import declaration are unused.".into(),
+
+            Self::AllDestructuredElementsUnused { .. } => "All destructured elements are unused.".into(),
+
+            Self::AllVarsUnused { .. } => "All variables are unused.".into(),
+
+            Self::AllTypeParamsUnused { .. } => "All type parameters are unused.".into(),
+
+            Self::IsolatedDeclarationsFnReturnType { .. } => {
+                "Function must have an explicit return type annotation with --isolatedDeclarations.".into()
+            }
+
+            Self::IsolatedDeclarationsMethodReturnType { .. } => {
+                "Method must have an explicit return type annotation with --isolatedDeclarations.".into()
+            }
+
+            Self::IsolatedDeclarationsAccessorType { .. } => {
+                "At least one accessor must have an explicit type annotation with --isolatedDeclarations.".into()
+            }
+
+            Self::IsolatedDeclarationsVarType { .. } => {
+                "Variable must have an explicit type annotation with --isolatedDeclarations.".into()
+            }
+
+            Self::IsolatedDeclarationsParamType { .. } => {
+                "Parameter must have an explicit type annotation with --isolatedDeclarations.".into()
+            }
+
+            Self::IsolatedDeclarationsPropType { .. } => {
+                "Property must have an explicit type annotation with --isolatedDeclarations.".into()
+            }
+
+            Self::IsolatedDeclarationsDefaultExport { .. } => "Default exports can't be inferred with --isolatedDeclarations.".into(),
+
+            Self::VarMayNotBeInitialized { .. } => "Variable is used before being assigned.".into(),
+
+            Self::ClassPropNotInitialized { .. } | Self::ClassPropertyInitRequired { .. } => {
+                "Property has no initializer and is not definitely assigned in the constructor.".into()
+            }
+
+            Self::DefinedWitHAccessorInSuper { .. } => {
+                "Property is defined as an accessor in the base class, but is overridden here as an instance property.".into()
+            }
+
+            Self::OptionInvalidForEs3 { .. } => "Option is not valid when 'target' is 'ES3'.".into(),
+
+            Self::ThisUsedBeforeCallingSuper { .. } => {
+                "'super' must be called before accessing 'this' in the constructor of a derived class.".into()
+            }
+
+            Self::SuperUsedBeforeCallingSuper { .. } => {
+                "'super' must be called before accessing a property of 'super' in the constructor of a derived class.".into()
+            }
+
+            Self::SuperInNestedFunction { .. } => {
+                "Super calls are not permitted outside constructors or in nested functions inside constructors.".into()
+            }
+
+            Self::SuperNotCalled { .. } => "Constructors for derived classes must contain a 'super' call.".into(),
+
+            Self::CannotAccessAbstractMember { .. } => "Abstract member cannot be accessed via 'super' expression.".into(),
+
+            Self::OperatorCannotBeAppliedToTypes { .. } | Self::TS2365 { .. } => "Operator cannot be applied to these types.".into(),
+
+            Self::AbstractClassMethodShouldBeSequntial { .. } => "All declarations of an abstract method must be consecutive.".into(),
+
+            Self::ClassMemberNotCompatibleWithStringIndexSignature { .. } => "Property is not assignable to 'string' index type.".into(),
+
+            Self::ClassMemberNotCompatibleWithNumericIndexSignature { .. } => "Property is not assignable to 'number' index type.".into(),
+
+            Self::AssignFailedBecauseTupleLengthDiffers { .. } => "Source and target tuple types have different lengths.".into(),
+
+            Self::InvalidUsageOfNewTarget { .. } => "Meta-property 'new.target' is only allowed in the body of a function declaration, \
+                                                     function expression, or constructor."
+                .into(),
+
+            Self::ReturnPropertyOfIteratorMustBeMethod { .. } => "The 'return' property of an iterator must be a method.".into(),
+
+            Self::NextOfItertorShouldReturnTypeWithPropertyValue { .. } => {
+                "The type returned by the 'next()' method of an iterator must have a 'value' property.".into()
+            }
+
+            Self::CannotAssignToNamespace { .. } => "Cannot assign to this name because it is a namespace.".into(),
+
+            Self::RestArgMustBeVarOrMemberAccess { .. } => {
+                "The target of an object rest assignment must be a variable or a property access.".into()
+            }
+
+            Self::InvalidOperandOfIncDecOptionalProp { .. } => {
+                "The operand of an increment or decrement operator may not be an optional property access.".into()
+            }
+
+            Self::InvalidRestPatternInOptionalChain { .. } => {
+                "The target of an object rest assignment may not be an optional property access.".into()
+            }
+
+            Self::InvalidLhsOfAssignOptionalProp { .. } => {
+                "The left-hand side of an assignment expression may not be an optional property access.".into()
+            }
+
+            Self::InvalidRestPatternInForIn { .. } => {
+                "The left-hand side of a 'for...in' statement may not be an optional property access.".into()
+            }
+
+            Self::InvalidRestPatternInForOf { .. } => {
+                "The left-hand side of a 'for...of' statement may not be an optional property access.".into()
+            }
+
+            Self::BindingPatNotAllowedInRestPatArg { .. } => "A rest element cannot contain a binding pattern.".into(),
+
+            Self::DeleteOperandMustBeOptional { .. } => "The operand of a 'delete' operator must be optional.".into(),
+
+            Self::SuperCannotUseTypeArgs { .. } => "'super' may not use type arguments.".into(),
+
+            Self::TargetLacksConstructSignature { .. } => {
+                "'new' expression, whose target lacks a construct signature, implicitly has an 'any' type.".into()
+            }
+
+            Self::BlockScopedVarUsedBeforeInit { .. } => "Block-scoped variable used before its declaration.".into(),
+
+            Self::DuplicateDefaultExport { .. } => "A module cannot have multiple default exports.".into(),
+
+            Self::DuplicateExport { .. } => "Cannot redeclare exported variable.".into(),
+
+            Self::DuplicateFnImpl { .. } => "Duplicate function implementation.".into(),
+
+            Self::DuplicateConstructor { .. } => "Multiple constructor implementations are not allowed.".into(),
+
+            Self::ModuleNotFound { specifier, .. } => {
+                format!("Cannot find module '{}' or its corresponding type declarations.", specifier).into()
+            }
+
+            Self::TypeDefinitionNotFound { name, .. } => format!("Cannot find type definition file for '{}'.", name).into(),
+
+            Self::TooManyAsterisk { .. } => "Pattern can have at most one '*' character.".into(),
+
+            Self::DuplicateVar { name, .. } => format!("Cannot redeclare block-scoped variable '{}'.", name.sym()).into(),
+
+            Self::ClassNameCannotBeObjectWhenTargetingEs5WithModule { .. } => {
+                "Class name cannot be 'Object' when targeting ES5 with a module system.".into()
+            }
+
+            Self::NotArrayType { .. } => "Type is not an array type.".into(),
+
+            Self::NotArrayTypeNorStringType { .. } => "Type is not an array type or a string type.".into(),
+
+            Self::NotArrayTypeNorStringTypeButDownlevelIterationWouldWork { .. } => {
+                "Type is not an array type or a string type. Use compiler option '--downlevelIteration' to allow iterating of iterators."
+                    .into()
+            }
+
+            Self::ForOfStringUsedInEs3 { .. } => {
+                "Using a string in a 'for...of' statement is only supported in ECMAScript 5 and higher.".into()
+            }
+
+            Self::LetOrConstIsNotValidIdInLetOrConstVarDecls { .. } => {
+                "'let' is not allowed to be used as a name in 'let' or 'const' declarations.".into()
+            }
+
+            Self::InvalidExprOfLhsOfForIn { .. } => {
+                "The left-hand side of a 'for...in' statement must be a variable or a property access.".into()
+            }
+
+            Self::InvalidExprOfLhsOfForOf { .. } => {
+                "The left-hand side of a 'for...of' statement must be a variable or a property access.".into()
+            }
+
+            Self::WrongTypeForLhsOfForInLoop { .. } => {
+                "The left-hand side of a 'for...in' statement must be of type 'string' or 'any'.".into()
+            }
+
+            Self::DestructuringBindingNotAllowedInLhsOfForIn { .. } => {
+                "The left-hand side of a 'for...in' statement cannot be a destructuring pattern.".into()
+            }
+
+            Self::TypeAnnOnLhsOfForInLoops { .. } => "The left-hand side of a 'for...in' statement cannot use a type annotation.".into(),
+
+            Self::TypeAnnOnLhsOfForOfLoops { .. } => "The left-hand side of a 'for...of' statement cannot use a type annotation.".into(),
+
+            Self::CannotAccessPrivatePropertyFromOutside { .. } => {
+                "Property is not accessible outside class because it has a private identifier.".into()
+            }
+
+            Self::OptionalChainCannotContainPrivateIdentifier { .. } => "An optional chain cannot contain private identifiers.".into(),
+
+            Self::CannotDeletePrivateProperty { .. } => "The operand of a 'delete' operator cannot be a private identifier.".into(),
+
+            Self::ConstructorIsKeyword { .. } => "'#constructor' is a reserved word.".into(),
+
+            Self::PrivateIdUsedAsMethodName { .. } => "A method cannot be named with a private identifier.".into(),
+
+            Self::UndefinedOrNullIsNotValidOperand { .. } => "The value 'null' or 'undefined' cannot be used here.".into(),
+
+            Self::ThisInConstructorParam { .. } => "'this' cannot be referenced in constructor arguments.".into(),
+
+            Self::ThisInStaticPropertyInitializer { .. } => "'this' cannot be referenced in a static property initializer.".into(),
+
+            Self::InvalidSuperClass { .. } | Self::NotConstructorType { .. } => "Type is not a constructor function type.".into(),
+
+            Self::WithStmtNotSupported { .. } => {
+                "The 'with' statement is not supported. All symbols in a 'with' block will have type 'any'.".into()
+            }
+
+            Self::NamspaceNotFound { name, .. } => format!("Cannot find namespace '{}'.", name_to_string(name)).into(),
+
+            Self::EnumMemberIdCannotBeNumber { .. } => "An enum member cannot have a numeric name.".into(),
+
+            Self::InvalidLhsOfAssign { .. } | Self::CannotAssingToThis { .. } => {
+                "The left-hand side of an assignment expression must be a variable or a property access.".into()
+            }
+
+            Self::ImplicitReturnType { .. } => "Function, which lacks return-type annotation, implicitly has an 'any' return type.".into(),
+
+            Self::ImcompatibleFnOverload { .. } | Self::WrongOverloadSignature { .. } => {
+                "This overload signature is not compatible with its implementation signature.".into()
+            }
+
+            Self::InitializerDisallowedInAmbientContext { .. } => {
+                "A parameter initializer is only allowed in a function or constructor implementation.".into()
+            }
+
+            Self::InvalidClassName { .. } => "Class name cannot be a reserved type name.".into(),
+
+            Self::NoSuchVarForShorthand { name, .. } => format!(
+                "No value exists in scope for the shorthand property '{}'. Either declare one or provide an initializer.",
+                name.sym()
+            )
+            .into(),
+
+            Self::NoMatchingOverload { .. } => "No overload matches this call.".into(),
+
+            Self::InvalidInterfaceName { .. } => "Interface name cannot be a reserved type name.".into(),
+
+            Self::CannotCallWithNewNonVoidFunction { .. } => "Only a void function can be called with the 'new' keyword.".into(),
+
+            Self::DuplicateProperty { key: k, .. } => format!("Duplicate identifier '{}'.", key(k)).into(),
+
+            Self::DuplicateNameWithoutName { .. } => "Duplicate identifier.".into(),
+
+            Self::CannotExportNonLocalVar { .. } => "Only local declarations can be exported from a module.".into(),
+
+            Self::StaticPropertyCannotBeNamedPrototype { .. } => {
+                "Static property 'prototype' conflicts with built-in property 'Function.prototype'.".into()
+            }
+
+            Self::SelfReferentialSuperClass { .. } => "Class is referenced directly or indirectly in its own base expression.".into(),
+
+            Self::ExportMixedWithLocal { .. } => "Individual declarations in merged declaration must be all exported or all local.".into(),
+
+            Self::ClassIncorrectlyImplementsInterface { .. } | Self::InvalidImplOfInterface { .. } => {
+                "Class incorrectly implements interface.".into()
+            }
+
+            Self::StackOverflow { .. } => "Maximum recursion depth exceeded while checking this expression.".into(),
+
+            Self::StaticMemberCannotUseTypeParamOfClass { .. } => "Static members cannot reference class type parameters.".into(),
+
+            Self::DeclaringTypeParamReferencedByComputedPropName { .. } => {
+                "A computed property name cannot reference a type parameter from its containing type.".into()
+            }
+
+            Self::CannotReferenceThisInComputedPropName { .. } => "'this' cannot be referenced in a computed property name.".into(),
+
+            Self::CannotReferenceSuperInComputedPropName { .. } => "'super' cannot be referenced in a computed property name.".into(),
+
+            Self::ThisRefToModuleOrNamespace { .. } => "'this' cannot be referenced in a module or namespace body.".into(),
+
+            Self::ThisImplicitlyAny { .. } => "'this' implicitly has type 'any' because it does not have a type annotation.".into(),
+
+            Self::SuperInClassWithoutSuper { .. } => "'super' can only be referenced in a derived class.".into(),
+
+            Self::GeneratorCannotHaveVoidAsReturnType { .. } => "A generator cannot have a 'void' type annotation.".into(),
+
+            Self::NoSuchVarButThisHasSuchProperty { name, .. } => format!(
+                "Cannot find name '{}'. Did you mean the instance member 'this.{}'?",
+                name.sym(),
+                name.sym()
+            )
+            .into(),
+
+            Self::DestructuringAssignInAmbientContext { .. } => "Destructuring declarations are not allowed in ambient contexts.".into(),
+
+            Self::OptionalBindingPatternInImplSignature { .. } => {
+                "A binding pattern parameter cannot be optional in an implementation signature.".into()
+            }
+
+            Self::NullishCoalescingMixedWithLogicalWithoutParen { .. } => {
+                "'??' operations cannot be mixed with '||' or '&&' operations without parentheses.".into()
+            }
+
+            Self::SwitchCaseTestNotCompatible { disc, test, .. } => {
+                format!("Type '{}' is not comparable to type '{}'.", ty(test), ty(disc)).into()
+            }
+
+            Self::EnumCannotBeLValue { .. } => "Cannot assign to an enum member because it is a read-only property.".into(),
+
+            Self::TypeInvalidForUpdateArg { .. } | Self::InvalidNumericOperand { .. } => {
+                "An arithmetic operand must be of type 'any', 'number', 'bigint' or an enum type.".into()
+            }
+
+            Self::PrivatePropertyIsDifferent { .. } => "Types have separate declarations of a private property.".into(),
+
+            Self::PrivateMethodIsDifferent { .. } => "Types have separate declarations of a private method.".into(),
+
+            Self::CannotCompareWithOp { op, left, right, .. } | Self::InvalidBinaryOp { op, left, right, .. } => {
+                format!("Operator '{}' cannot be applied to types '{}' and '{}'.", op, ty(left), ty(right)).into()
+            }
+
+            Self::NoSuchEnumVariant { name, enum_name, .. } => {
+                format!("Property '{}' does not exist on type 'typeof {}'.", name, enum_name.sym()).into()
+            }
+
+            Self::ObjectIsPossiblyNull { .. } | Self::TS2531 { .. } => "Object is possibly 'null'.".into(),
+
+            Self::ObjectIsPossiblyUndefined { .. } | Self::MayBeUndefined { .. } => "Object is possibly 'undefined'.".into(),
+
+            Self::ObjectIsPossiblyUndefinedWithType { ty: t, .. } => format!("Object of type '{}' is possibly 'undefined'.", ty(t)).into(),
+
+            Self::ObjectIsPossiblyNullOrUndefined { .. } => "Object is possibly 'null' or 'undefined'.".into(),
+
+            Self::CannotAssignAbstractConstructorToNonAbstractConstructor { .. } => {
+                "Cannot assign an abstract constructor type to a non-abstract constructor type.".into()
+            }
+
+            Self::InvalidUseOfConstEnum { .. } => "'const' enums can only be used in property or index access expressions or the right \
+                                                   hand side of an import declaration or export assignment or type query."
+                .into(),
+
+            Self::ComputedMemberInEnumWithStrMember { .. } => {
+                "Computed values are not permitted in an enum with string valued members.".into()
+            }
+
+            Self::CannotCreateInstanceOfAbstractClass { .. } => "Cannot create an instance of an abstract class.".into(),
+
+            Self::WrongArgType { inner, .. } => match inner.actual() {
+                Self::AssignFailed { left, right, .. } => format!(
+                    "Argument of type '{}' is not assignable to parameter of type '{}'.",
+                    ty(right),
+                    ty(left)
+                )
+                .into(),
+                _ => "Argument is not assignable to the type of the parameter.".into(),
+            },
+
+            Self::ImportFailed { specifier, orig, .. } if *orig.sym() == js_word!("default") => {
+                format!("Module '\"{}\"' has no default export.", specifier).into()
+            }
+
+            Self::ImportFailed { specifier, orig, .. } => {
+                format!("Module '\"{}\"' has no exported member '{}'.", specifier, orig.sym()).into()
+            }
+
+            Self::ExportFailed { orig, .. } => format!("Cannot export '{}' because it is not declared.", orig.sym()).into(),
+
+            Self::ExportAllFailed { .. } => "Cannot re-export members of the module.".into(),
+
+            Self::NoSuchPropertyInThis { .. } => "Property does not exist on type 'this'.".into(),
+
+            Self::NoSuchPropertyInClass { class_name, prop, .. } => match class_name {
+                Some(class_name) => format!("Property '{}' does not exist on type '{}'.", key(prop), class_name.sym()).into(),
+                None => format!("Property '{}' does not exist on the class.", key(prop)).into(),
+            },
+
+            Self::TypeParameterCountMismatch { min, max, actual, .. } => {
+                format!("Expected {} type arguments, but got {}.", count(&(*min..=*max)), actual).into()
+            }
+
+            Self::ParameterCountMismatch { min, max, actual, .. } => {
+                format!("Expected {} arguments, but got {}.", count(&(*min..=*max)), actual).into()
+            }
+
+            Self::NoSuchPropertyInModule { name, .. } => format!("Property does not exist on module '{}'.", module_name(name)).into(),
+
+            Self::ReturnRequired { .. } => {
+                "A function whose declared type is neither 'undefined', 'void', nor 'any' must return a value.".into()
+            }
+
+            Self::ConstructorRequired { .. } => "Type is not a constructor function type.".into(),
+
+            Self::CannotAssignToNonVariable { .. } | Self::NotVariable { .. } => {
+                "Cannot assign to this expression because it is not a variable.".into()
+            }
+
+            Self::CannotAssignToModule { .. } => "Cannot use namespace as a value.".into(),
+
+            Self::CannotAssignToClass { .. } => "Cannot assign to this name because it is a class.".into(),
+
+            Self::CannotAssignToEnum { .. } => "Cannot assign to this name because it is an enum.".into(),
+
+            Self::CannotAssignToFunction { .. } => "Cannot assign to this name because it is a function.".into(),
+
+            Self::AssignedWrapperToPrimitive { .. } => {
+                "A wrapper object type is not assignable to the primitive type. Prefer using the primitive type.".into()
+            }
+
+            Self::AccessibilityDiffers { .. } | Self::AssignFailedDueToAccessibility { .. } => {
+                "Property has different accessibility in the source and target types.".into()
+            }
+
+            Self::InvalidInitInConstEnum { .. } | Self::InvalidEnumInit { .. } => {
+                "const enum member initializers must be constant expressions.".into()
+            }
+
+            Self::InvalidTupleCast { .. } | Self::NonOverlappingTypeCast { .. } => {
+                "Conversion of type may be a mistake because neither type sufficiently overlaps with the other. If this was intentional, \
+                 convert the expression to 'unknown' first."
+                    .into()
+            }
+
+            Self::NoOverlap { left, right, .. } => format!(
+                "This comparison appears to be unintentional because the types '{}' and '{}' have no overlap.",
+                ty(left),
+                ty(right)
+            )
+            .into(),
+
+            Self::CannotAssignToReadonlyProperty { .. } | Self::ReadOnly { .. } | Self::InvalidLValue { .. } => {
+                "Cannot assign to this property because it is a read-only property.".into()
+            }
+
+            Self::ImplicitAny { .. } => "Variable implicitly has an 'any' type.".into(),
+
+            Self::ImplicitAnyBecauseNoIndexSignatureExists { .. } => {
+                "Element implicitly has an 'any' type because the type has no index signature.".into()
+            }
+
+            Self::ImplicitAnyBecauseIndexTypeIsWrong { .. } => {
+                "Element implicitly has an 'any' type because expression can't be used to index the type.".into()
+            }
+
+            Self::ImplicitAnyBecauseOfSelfRef { .. } => "Variable implicitly has type 'any' because it does not have a type annotation \
+                                                         and is referenced directly or indirectly in its own initializer."
+                .into(),
+
+            Self::NoSuchType { name, .. } | Self::NoSuchVar { name, .. } | Self::UndefinedSymbol { sym: name, .. } => {
+                format!("Cannot find name '{}'.", name.sym()).into()
+            }
+
+            Self::NoSuchTypeButVarExists { name, .. } => format!(
+                "'{}' refers to a value, but is being used as a type here. Did you mean 'typeof {}'?",
+                name.sym(),
+                name.sym()
+            )
+            .into(),
+
+            Self::InvalidUseOfArgumentsInEs3OrEs5 { .. } => "The 'arguments' object cannot be referenced in an arrow function in ES3 and \
+                                                             ES5. Consider using a standard function expression."
+                .into(),
+
+            Self::ArgumentsCannotBeUsedInAsyncFnInEs3OrEs5 { .. } => "The 'arguments' object cannot be referenced in an async function or \
+                                                                      method in ES3 and ES5. Consider using a standard function or method."
+                .into(),
+
+            Self::TypeUsedAsVar { name, .. } => {
+                format!("'{}' only refers to a type, but is being used as a value here.", name.sym()).into()
+            }
+
+            Self::DuplicateName { name, .. } => format!("Duplicate identifier '{}'.", name.sym()).into(),
+
+            Self::UselessSeqExpr { .. } => "Left side of comma operator is unused and has no side effects.".into(),
+
+            Self::ReferencedInInit { .. } => "Property is used before its initialization.".into(),
+
+            Self::NotGeneric { .. } => "Type is not generic.".into(),
+
+            Self::Unknown { .. } => "Object is of type 'unknown'.".into(),
+
+            Self::NoSuchPropertyWhileDeclWithBidningPat { .. } | Self::NoInitAndNoDefault { .. } => {
+                "Initializer provides no value for this binding element and the binding element has no default value.".into()
+            }
+
+            Self::NoSuchProperty { obj, prop, .. } => match (prop, obj) {
+                (Some(prop), Some(obj)) => format!("Property '{}' does not exist on type '{}'.", key(prop), ty(obj)).into(),
+                (Some(prop), None) => format!("Property '{}' does not exist.", key(prop)).into(),
+                (None, Some(obj)) => format!("Property does not exist on type '{}'.", ty(obj)).into(),
+                (None, None) => "Property does not exist.".into(),
+            },
+
+            Self::TooManyTupleElements { .. } => "Tuple type has too many elements.".into(),
+
+            Self::NotTuple { .. } => "Type is not a tuple type.".into(),
+
+            Self::TypeNotFound { name, .. } => format!("Cannot find name '{}'.", name_to_string(name)).into(),
+
+            Self::TS2378 { .. } => "A 'get' accessor must return a value.".into(),
+
+            Self::ConstEnumNonIndexAccess { .. } => "A const enum member can only be accessed using a string literal.".into(),
+
+            Self::TupleIndexError { len, index, .. } => {
+                format!("Tuple type of length '{}' has no element at index '{}'.", len, index).into()
+            }
+
+            Self::NegativeTupleIndex { .. } => "A tuple type cannot be indexed with a negative value.".into(),
+
+            Self::ResolvedFailed { base, src, .. } => format!("Cannot resolve module '{}' from '{}'.", src, base.display()).into(),
+
+            Self::MissingFields { fields, .. } => {
+                let names = fields.iter().filter_map(TypeElement::key).map(key).collect::<Vec<_>>();
+
+                match &*names {
+                    [name] => format!("Property '{}' is missing in type but required in the target type.", name).into(),
+                    _ => format!(
+                        "Type is missing the following properties from the target type: {}",
+                        names.join(", ")
+                    )
+                    .into(),
+                }
+            }
+
+            Self::AssignFailed { left, right, .. } => format!("Type '{}' is not assignable to type '{}'.", ty(right), ty(left)).into(),
+
+            Self::AssignFailedDueToOptionalityDifference { .. } => {
+                "Property is optional in the source type but required in the target type.".into()
+            }
+
+            Self::ObjectAssignFailed { .. } | Self::SimpleAssignFailed { .. } | Self::SimpleAssignFailedWithCause { .. } => {
+                "Type is not assignable to the target type.".into()
+            }
+
+            Self::InvalidAssignmentOfArray { .. } => "Array type is not assignable to the target type.".into(),
+
+            Self::UnionError { .. } => "Type is not assignable to any member of the union type.".into(),
+
+            Self::IntersectionError { .. } => "Type is not assignable to the intersection type.".into(),
+
+            Self::ModuleLoadFailed { .. } => "Failed to load the imported module.".into(),
+
+            Self::NoSuchExport { items, .. } => format!(
+                "Module has no exported member {}.",
+                items.iter().map(|id| format!("'{}'", id.sym())).collect::<Vec<_>>().join(", ")
+            )
+            .into(),
+
+            Self::NoNewSignature { callee, .. } => format!(
+                "This expression is not constructable. Type '{}' has no construct signatures.",
+                ty(callee)
+            )
+            .into(),
+
+            Self::NoConstructablePropertyWithName { obj, key: k, .. } => format!(
+                "This expression is not constructable. Property '{}' of type '{}' has no construct signatures.",
+                key(k),
+                ty(obj)
+            )
+            .into(),
+
+            Self::NoCallSignature { callee, .. } => {
+                format!("This expression is not callable. Type '{}' has no call signatures.", ty(callee)).into()
+            }
+
+            Self::NoCallablePropertyWithName { obj, key: k, .. } => format!(
+                "This expression is not callable. Property '{}' of type '{}' has no call signatures.",
+                key(k),
+                ty(obj)
+            )
+            .into(),
+
+            Self::NoSuchConstructor { key: k, .. } => format!(
+                "This expression is not constructable. Property '{}' has no construct signatures.",
+                key(k)
+            )
+            .into(),
+
+            Self::WrongTypeParams { expected, actual, .. } => {
+                format!("Expected {} type arguments, but got {}.", count(expected), actual).into()
+            }
+
+            Self::WrongParams { expected, actual, .. } => format!("Expected {} arguments, but got {}.", count(expected), actual).into(),
+
+            Self::TS1016 { .. } => "A required parameter cannot follow an optional parameter.".into(),
+
+            Self::TS1063 { .. } => "An export assignment cannot be used in a namespace.".into(),
+
+            Self::TS1094 { .. } => "An accessor cannot have type parameters.".into(),
+
+            Self::TS1095 { .. } => "A 'set' accessor cannot have a return type annotation.".into(),
+
+            Self::TS1168 { .. } => "A computed property name in a method overload must refer to an expression whose type is a literal \
+                                    type or a 'unique symbol' type."
+                .into(),
+
+            Self::TS1169 { .. } => "A computed property name in an interface must refer to an expression whose type is a literal type or \
+                                    a 'unique symbol' type."
+                .into(),
+
+            Self::TS1183 { .. } => "An implementation cannot be declared in ambient contexts.".into(),
+
+            Self::TS1318 { .. } => "Method cannot have an implementation because it is marked abstract.".into(),
+
+            Self::TS1319 { .. } => "A default export can only be used in an ECMAScript-style module.".into(),
+
+            Self::ExportEqualsMixedWithOtherExports { .. } => {
+                "An export assignment cannot be used in a module with other exported elements.".into()
+            }
+
+            Self::TS2360 { .. } => "The left-hand side of an 'in' expression must be a private identifier or of type 'any', 'string', \
+                                    'number', or 'symbol'."
+                .into(),
+
+            Self::InvalidRhsForInOperator { ty: t, .. } => format!(
+                "Type '{}' may represent a primitive value, which is not permitted as the right operand of the 'in' operator.",
+                ty(t)
+            )
+            .into(),
+
+            Self::WrongTypeForLhsOfNumericOperation { .. } => {
+                "The left-hand side of an arithmetic operation must be of type 'any', 'number', 'bigint' or an enum type.".into()
+            }
+
+            Self::WrongTypeForRhsOfNumericOperation { .. } => {
+                "The right-hand side of an arithmetic operation must be of type 'any', 'number', 'bigint' or an enum type.".into()
+            }
+
+            Self::TS2370 { .. } => "A rest parameter must be of an array type.".into(),
+
+            Self::TS1166 { .. } => {
+                "A computed property name in a class property declaration must have a simple literal type or a 'unique symbol' type.".into()
+            }
+
+            Self::TS1345 { .. } => "An expression of type 'void' cannot be tested for truthiness.".into(),
+
+            Self::TS2353 { .. } | Self::UnknownPropertyInObjectLiteralAssignment { .. } => {
+                "Object literal may only specify known properties.".into()
+            }
+
+            Self::ConstructorImplMissingOrNotFollowedByDecl { .. } => "Constructor implementation is missing.".into(),
+
+            Self::FnImplMissingOrNotFollowedByDecl { .. } => {
+                "Function implementation is missing or not immediately following the declaration.".into()
+            }
+
+            Self::InvalidTypeForComputedProperty { .. } => {
+                "A computed property name must be of type 'string', 'number', 'symbol', or 'any'.".into()
+            }
+
+            Self::ParamPropIsNotAllowedInAmbientConstructorx { .. } => {
+                "A parameter property is only allowed in a constructor implementation.".into()
+            }
+
+            Self::TS2389 { .. } => "Function implementation name must match the name of the overload signatures.".into(),
+
+            Self::TS2447 { .. } => "This operator is not allowed for boolean types. Consider using the logical operator instead.".into(),
+
+            Self::ClassDoesNotImplementMemeber { key: k, .. } => {
+                format!("Non-abstract class does not implement inherited abstract member '{}'.", key(k)).into()
+            }
+
+            Self::TS2567 { .. } => "Enum declarations can only merge with namespace or other enum declarations.".into(),
+
+            Self::TS2585 { .. } => "This name only refers to a type, but is being used as a value here. Do you need to change your target \
+                                    library? Try changing the 'lib' compiler option to es2015 or later."
+                .into(),
+
+            Self::TS2704 { .. } => "The operand of a 'delete' operator cannot be a read-only property.".into(),
+
+            Self::InvalidLhsInInstanceOf { .. } => {
+                "The left-hand side of an 'instanceof' expression must be of type 'any', an object type or a type parameter.".into()
+            }
+
+            Self::InvalidRhsInInstanceOf { .. } => "The right-hand side of an 'instanceof' expression must be of type 'any' or of a type \
+                                                    assignable to the 'Function' interface type."
+                .into(),
+
+            Self::NumericUnaryOpToSymbol { op, .. } => format!("The '{}' operator cannot be applied to type 'symbol'.", op).into(),
+
+            Self::UpdateOpToSymbol { op, .. } => format!("The '{}' operator cannot be applied to type 'symbol'.", op).into(),
+
+            Self::InvalidOperatorForLhs { op, .. } | Self::AssignOpCannotBeApplied { op, .. } => {
+                format!("Operator '{}' cannot be applied to these types.", op).into()
+            }
+
+            Self::InvalidOpAssign { op, lhs, rhs, .. } => {
+                format!("Operator '{}' cannot be applied to types '{}' and '{}'.", op, ty(lhs), ty(rhs)).into()
+            }
+
+            Self::NonSymbolComputedPropInFormOfSymbol { .. } => {
+                "A computed property name of the form 'Symbol.xxx' must be of type 'symbol'.".into()
+            }
+
+            Self::ExpectedNArgsButGotM { min, max, .. } => format!("Expected {} arguments.", count(&(*min..=max.unwrap_or(*min)))).into(),
+
+            Self::ExpectedAtLeastNArgsButGotM { min, .. } => format!("Expected at least {} arguments.", min).into(),
+
+            Self::ExpectedAtLeastNArgsButGotMOrMore { min, .. } => format!(
+                "Expected at least {} arguments, but got a spread argument which may be shorter.",
+                min
+            )
+            .into(),
+
+            Self::ExpectedNArgsButGotMOrMore { .. } | Self::SpreadMustBeTupleOrPassedToRest { .. } => {
+                "A spread argument must either have a tuple type or be passed to a rest parameter.".into()
+            }
+
+            Self::InvalidDeleteOperand { .. } => "The operand of a 'delete' operator must be a property reference.".into(),
+
+            Self::NoMethodNamedNext { .. } => "An iterator must have a 'next()' method.".into(),
+
+            Self::MustHaveSymbolIteratorThatReturnsIteratorOrMustBeArray { .. } => {
+                "Type is not an array type or does not have a '[Symbol.iterator]()' method that returns an iterator.".into()
+            }
+
+            Self::MustHaveSymbolIteratorThatReturnsIterator { .. } => {
+                "Type must have a '[Symbol.iterator]()' method that returns an iterator.".into()
+            }
+
+            Self::MustHaveSymbolAsyncIteratorThatReturnsIterator { .. } => {
+                "Type must have a '[Symbol.asyncIterator]()' method that returns an async iterator.".into()
+            }
+
+            Self::AbstractAndConcreteIsMixed { .. } => "Overload signatures must all be abstract or non-abstract.".into(),
+
+            Self::ShouldBeStaticMethod { .. } => "Function overload must be static.".into(),
+
+            Self::ShouldBeInstanceMethod { .. } => "Function overload must not be static.".into(),
+        }
+    }
+
+    /// Errors which explain why `self` is reported, like the elaboration of
+    /// `tsc`.
+    pub fn causes(&self) -> Vec<&Error> {
+        match self {
+            Self::DebugContext(c) => c.inner.causes(),
+
+            Self::InvalidInterfaceInheritance { cause, .. }
+            | Self::VarDeclNotCompatible { cause, .. }
+            | Self::ImcompatibleFnOverload { cause, .. }
+            | Self::InvalidImplOfInterface { cause, .. }
+            | Self::IntersectionError { error: cause, .. } => vec![&**cause],
+
+            Self::SimpleAssignFailed { cause: Some(cause), .. } => vec![&**cause],
+
+            Self::AssignFailed { cause, .. } | Self::SimpleAssignFailedWithCause { cause, .. } => cause.iter().collect(),
+
+            Self::ObjectAssignFailed { errors, .. } | Self::UnionError { errors, .. } => errors.iter().collect(),
+
+            Self::ModuleLoadFailed { errors, .. } => errors.0.iter().collect(),
+
+            // The message of `inner` is already used as the message of `self`.
+            Self::WrongArgType { inner, .. } => match inner.actual() {
+                Self::AssignFailed { .. } => inner.causes(),
+                _ => vec![&**inner],
+            },
+
+            _ => vec![],
+        }
+    }
+
+    /// Causes of `self` and their causes, with the depth of nesting starting
+    /// from 0.
+    pub fn cause_chain(&self) -> Vec<(usize, &Error)> {
+        let mut buf = vec![];
+        self.collect_causes(0, &mut buf);
+        buf
+    }
+
+    fn collect_causes<'a>(&'a self, depth: usize, buf: &mut Vec<(usize, &'a Error)>) {
+        for cause in self.causes() {
+            buf.push((depth, cause));
+            cause.collect_causes(depth + 1, buf);
+        }
+    }
+
+    /// The message of the error followed by indented messages of causes, like
+    /// the message chain of `tsc`.
+    pub fn msg_with_causes(&self) -> String {
+        let mut msg = self.msg().into_owned();
+
+        for (depth, cause) in self.cause_chain() {
+            msg.push('\n');
+            msg.push_str(&"  ".repeat(depth + 1));
+            msg.push_str(&cause.msg());
+        }
+
+        msg
+    }
+}
+
+fn ty(ty: &Type) -> String {
+    type_to_string(ty)
+}
+
+/// [Key] does not implement [std::fmt::Display].
+fn key(key: &Key) -> String {
+    match key {
+        Key::Computed(c) => format!("[{}]", ty(&c.ty)),
+        Key::Normal { sym, .. } => sym.to_string(),
+        Key::Num(n) => n.value.to_string(),
+        Key::BigInt(n) => format!("{}n", n.value),
+        Key::Private(p) => format!("#{}", p.id.sym()),
+    }
+}
+
+/// `Debug` of [Name] contains syntax contexts.
+fn name_to_string(name: &Name) -> String {
+    name.as_ids().iter().map(Id::sym).map(|sym| &**sym).collect::<Vec<_>>().join(".")
+}
+
+fn module_name(name: &RTsModuleName) -> String {
+    match name {
+        RTsModuleName::Ident(i) => i.sym.to_string(),
+        RTsModuleName::Str(s) => format!("\"{}\"", s.value),
+    }
+}
+
+fn count(range: &RangeInclusive<usize>) -> String {
+    if range.start() == range.end() {
+        range.start().to_string()
+    } else {
+        format!("{}-{}", range.start(), range.end())
+    }
+}
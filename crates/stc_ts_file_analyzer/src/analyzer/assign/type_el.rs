@@ -1045,6 +1045,17 @@ impl Analyzer<'_, '_> {
                                                 }
                                             }
 
+                                            // With `exactOptionalPropertyTypes`, optional properties accept
+                                            // `undefined` only if it's declared.
+                                            if lp.optional && self.rule().strict_null_checks && self.rule().exact_optional_property_types {
+                                                if let (Some(l_ty), Some(r_ty)) = (&lp.type_ann, &rp.type_ann) {
+                                                    if !l_ty.is_any() && !l_ty.contains_undefined() && r_ty.contains_undefined() {
+                                                        return Err(Error::SimpleAssignFailed { span, cause: None }
+                                                            .context("`undefined` is not assignable to an optional property"));
+                                                    }
+                                                }
+                                            }
+
                                             self.assign_inner(
                                                 data,
                                                 lp.type_ann.as_deref().unwrap_or(&Type::any(span, Default::default())),
@@ -279,6 +279,12 @@ impl Analyzer<'_, '_> {
             .with_child(ScopeKind::Constructor, Default::default(), |child: &mut Analyzer| {
                 let RConstructor { params, body, .. } = c;
 
+                // Parameter properties are properties.
+                child.declare_params_for_unused_check(params.iter().filter_map(|p| match p {
+                    RParamOrTsParamProp::Param(p) => Some(&p.pat),
+                    RParamOrTsParamProp::TsParamProp(..) => None,
+                }));
+
                 {
                     // Validate params
                     // TODO(kdy1): Move this to parser
@@ -1756,7 +1762,10 @@ impl Analyzer<'_, '_> {
                                 if let Some(key) = member.key() {
                                     // TODO(kdy1): Use better logic for testing key equality
                                     if declared_static_keys.iter().any(|prev: &Key| prev.type_eq(&*key)) {
-                                        child.storage.report(Error::DuplicateProperty { span: key.span() })
+                                        child.storage.report(Error::DuplicateProperty {
+                                            span: key.span(),
+                                            key: box key.clone().into_owned(),
+                                        })
                                     }
                                     declared_static_keys.push(key.into_owned());
                                 }
@@ -1878,7 +1887,10 @@ impl Analyzer<'_, '_> {
                                 if let Some(key) = member.key() {
                                     // TODO(kdy1): Use better logic for testing key equality
                                     if declared_instance_keys.iter().any(|prev: &Key| prev.type_eq(&*key)) {
-                                        child.storage.report(Error::DuplicateProperty { span: key.span() })
+                                        child.storage.report(Error::DuplicateProperty {
+                                            span: key.span(),
+                                            key: box key.clone().into_owned(),
+                                        })
                                     }
                                     declared_instance_keys.push(key.into_owned());
                                 }
@@ -2049,7 +2061,9 @@ impl Analyzer<'_, '_> {
             in_declare: self.ctx.in_declare || c.declare,
             ..self.ctx
         };
-        self.with_ctx(ctx).visit_class_decl_inner(c);
+        let mut a = self.with_ctx(ctx);
+        a.declare_decl_for_unused_check(&c.ident, true);
+        a.with_declaring_for_unused_check(c.ident.clone().into(), |a: &mut Analyzer| a.visit_class_decl_inner(c));
 
         Ok(())
     }
@@ -0,0 +1,68 @@
+use stc_ts_ast_rnode::RTsLit;
+use stc_ts_errors::DebugExt;
+use stc_ts_types::{IdCtx, Key};
+use swc_atoms::JsWord;
+use swc_common::Span;
+
+use crate::{
+    analyzer::{expr::TypeOfMode, Analyzer},
+    ty::Type,
+    VResult,
+};
+
+/// A property suggested by [Analyzer::properties_of].
+#[derive(Debug)]
+pub struct PropertyInfo {
+    pub name: JsWord,
+    /// `None` if the type of the property cannot be computed.
+    pub ty: Option<Type>,
+}
+
+impl Analyzer<'_, '_> {
+    /// Lists properties which can be accessed on a value of type `ty`, for
+    /// completion in IDEs.
+    ///
+    /// Names are computed using `keyof` and types of properties are computed
+    /// in the same way as member expressions.
+    pub fn properties_of(&mut self, span: Span, ty: &Type) -> VResult<Vec<PropertyInfo>> {
+        let keys = self.keyof(span, ty).context("tried to get keys for completion")?;
+
+        let mut names = vec![];
+        collect_names(&keys, &mut names);
+
+        Ok(names
+            .into_iter()
+            .map(|name| {
+                let key = Key::Normal { span, sym: name.clone() };
+                let ty = self
+                    .access_property(span, ty, &key, TypeOfMode::RValue, IdCtx::Var, Default::default())
+                    .ok();
+
+                PropertyInfo { name, ty }
+            })
+            .collect())
+    }
+}
+
+/// Index signatures are ignored because they don't have names.
+fn collect_names(keys: &Type, names: &mut Vec<JsWord>) {
+    match keys.normalize() {
+        Type::Lit(lit) => {
+            let name: JsWord = match &lit.lit {
+                RTsLit::Str(s) => s.value.clone(),
+                RTsLit::Number(n) => n.value.to_string().into(),
+                _ => return,
+            };
+
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        Type::Union(u) => {
+            for ty in &u.types {
+                collect_names(ty, names);
+            }
+        }
+        _ => {}
+    }
+}
@@ -622,11 +622,23 @@ impl Analyzer<'_, '_> {
             let mut facts_for_body = base_true_facts.clone();
             facts_for_body += true_facts_created_by_case;
 
-            self.with_child(ScopeKind::Flow, facts_for_body, |child| {
+            let ends_with_unreachable = self.with_child(ScopeKind::Flow, facts_for_body, |child| {
                 cons.visit_with(child);
-                Ok(())
+                Ok(child.ctx.in_unreachable)
             })?;
 
+            if self.rule().no_fallthrough_cases_in_switch
+                && !last
+                && !cons.is_empty()
+                && !ends_with_ret
+                && !ends_with_unreachable
+                && !self.is_switch_case_body_unconditional_termination(cons)
+            {
+                self.storage.report(Error::FallthroughCaseInSwitch {
+                    span: case.span.with_hi(cons[0].span().lo),
+                });
+            }
+
             if ends_with_ret || last {
                 false_facts += false_facts_created_by_case.clone();
                 base_true_facts += false_facts_created_by_case;
@@ -659,7 +671,7 @@ impl Analyzer<'_, '_> {
     /// `continue`.
     ///
     /// TODO(kdy1): Support break with other label.
-    fn is_switch_case_body_unconditional_termination<S>(&mut self, body: &[S]) -> bool
+    pub(super) fn is_switch_case_body_unconditional_termination<S>(&mut self, body: &[S]) -> bool
     where
         S: Borrow<RStmt>,
     {
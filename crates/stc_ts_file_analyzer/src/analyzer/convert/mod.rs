@@ -49,6 +49,7 @@ mod interface;
 impl Analyzer<'_, '_> {
     fn validate(&mut self, decl: &RTsTypeParamDecl) -> VResult<TypeParamDecl> {
         self.record(decl);
+        self.declare_type_params_for_unused_check(decl);
 
         if self.is_builtin {
             Ok(TypeParamDecl {
@@ -182,8 +183,12 @@ impl Analyzer<'_, '_> {
         self.record(d);
         let span = d.span;
 
-        let alias = {
-            self.with_child(ScopeKind::Flow, Default::default(), |child: &mut Analyzer| -> VResult<_> {
+        if !d.declare {
+            self.declare_decl_for_unused_check(&d.id, true);
+        }
+
+        let alias = self.with_declaring_for_unused_check(d.id.clone().into(), |a: &mut Analyzer| {
+            a.with_child(ScopeKind::Flow, Default::default(), |child: &mut Analyzer| -> VResult<_> {
                 let type_params = try_opt!(d.type_params.validate_with(child)).map(Box::new);
 
                 let mut ty = match &*d.type_ann {
@@ -249,8 +254,8 @@ impl Analyzer<'_, '_> {
                 })
                 .freezed();
                 Ok(alias)
-            })?
-        };
+            })
+        })?;
         self.register_type(d.id.clone().into(), alias.clone());
 
         self.store_unmergeable_type_span(d.id.clone().into(), d.id.span);
@@ -262,32 +267,38 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, d: &RTsInterfaceDecl) -> VResult<Type> {
-        let ty = self.with_child(ScopeKind::Flow, Default::default(), |child: &mut Analyzer| -> VResult<_> {
-            match &*d.id.sym {
-                "any" | "void" | "never" | "string" | "number" | "boolean" | "null" | "undefined" | "symbol" => {
-                    child.storage.report(Error::InvalidInterfaceName { span: d.id.span });
+        if !d.declare {
+            self.declare_decl_for_unused_check(&d.id, true);
+        }
+
+        let ty = self.with_declaring_for_unused_check(d.id.clone().into(), |a: &mut Analyzer| {
+            a.with_child(ScopeKind::Flow, Default::default(), |child: &mut Analyzer| -> VResult<_> {
+                match &*d.id.sym {
+                    "any" | "void" | "never" | "string" | "number" | "boolean" | "null" | "undefined" | "symbol" => {
+                        child.storage.report(Error::InvalidInterfaceName { span: d.id.span });
+                    }
+                    _ => {}
                 }
-                _ => {}
-            }
 
-            let mut ty = Interface {
-                span: d.span,
-                name: d.id.clone().into(),
-                type_params: try_opt!(d.type_params.validate_with(&mut *child).map(|v| v.map(Box::new))),
-                extends: d.extends.validate_with(child)?.freezed(),
-                body: d.body.validate_with(child)?,
-                metadata: Default::default(),
-            };
-            child.prevent_expansion(&mut ty.body);
-            ty.body.make_clone_cheap();
+                let mut ty = Interface {
+                    span: d.span,
+                    name: d.id.clone().into(),
+                    type_params: try_opt!(d.type_params.validate_with(&mut *child).map(|v| v.map(Box::new))),
+                    extends: d.extends.validate_with(child)?.freezed(),
+                    body: d.body.validate_with(child)?,
+                    metadata: Default::default(),
+                };
+                child.prevent_expansion(&mut ty.body);
+                ty.body.make_clone_cheap();
 
-            child.resolve_parent_interfaces(&d.extends);
-            child.report_error_for_conflicting_parents(d.id.span, &ty.extends);
-            child.report_error_for_wrong_interface_inheritance(d.id.span, &ty.body, &ty.extends);
+                child.resolve_parent_interfaces(&d.extends);
+                child.report_error_for_conflicting_parents(d.id.span, &ty.extends);
+                child.report_error_for_wrong_interface_inheritance(d.id.span, &ty.body, &ty.extends);
 
-            let ty = Type::Interface(ty).freezed();
+                let ty = Type::Interface(ty).freezed();
 
-            Ok(ty)
+                Ok(ty)
+            })
         })?;
 
         // TODO(kdy1): Recover
@@ -494,6 +505,8 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, e: &RTsExprWithTypeArgs) -> VResult<TsExpr> {
+        self.mark_expr_as_used(&e.expr);
+
         Ok(TsExpr {
             span: e.span,
             expr: e.expr.clone(),
@@ -698,6 +711,7 @@ impl Analyzer<'_, '_> {
 impl Analyzer<'_, '_> {
     fn validate(&mut self, t: &RTsTypeRef) -> VResult<Type> {
         self.record(t);
+        self.mark_entity_name_as_used(&t.type_name);
 
         let span = t.span;
         let type_args = try_opt!(t.type_params.validate_with(self)).map(Box::new).freezed();
@@ -821,7 +835,10 @@ impl Analyzer<'_, '_> {
         let span = t.span();
 
         Ok(match t {
-            RTsTypeQueryExpr::TsEntityName(t) => t.clone().into(),
+            RTsTypeQueryExpr::TsEntityName(t) => {
+                self.mark_entity_name_as_used(t);
+                t.clone().into()
+            }
             RTsTypeQueryExpr::Import(i) => i.validate_with(self)?.into(),
         })
     }
@@ -38,6 +38,10 @@ type EnumValues = FxHashMap<JsWord, RTsLit>;
 impl Analyzer<'_, '_> {
     #[inline(never)]
     fn validate(&mut self, e: &RTsEnumDecl) -> VResult<Enum> {
+        if !e.declare {
+            self.declare_decl_for_unused_check(&e.id, true);
+        }
+
         for m in &e.members {
             self.validate_with(|a| a.validate_enum_memeber_name(&m.id));
         }
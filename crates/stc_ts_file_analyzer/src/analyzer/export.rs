@@ -15,6 +15,7 @@ use swc_ecma_ast::*;
 
 use crate::{
     analyzer::{expr::TypeOfMode, scope::VarKind, util::ResultExt, Analyzer, Ctx},
+    index::Export,
     ty::Type,
     validator,
     validator::ValidateWith,
@@ -24,6 +25,8 @@ use crate::{
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, export: &RExportDecl) {
+        self.mark_export_decl_as_used(&export.decl);
+
         let ctx = Ctx {
             in_export_decl: true,
             ..self.ctx
@@ -64,6 +67,7 @@ impl Analyzer<'_, '_> {
                     let ty = e.validate_with(a).report(&mut a.storage).map(Type::from).map(|ty| ty.cheap());
                     let ty = ty.unwrap_or_else(|| Type::any(span, Default::default()));
                     a.register_type(e.id.clone().into(), ty);
+                    a.record_export(e.id.sym.clone(), Export::Local(e.id.clone().into()));
 
                     a.storage.export_type(span, a.ctx.module_id, e.id.clone().into());
                     a.storage
@@ -73,6 +77,7 @@ impl Analyzer<'_, '_> {
                     RTsModuleName::Ident(id) => {
                         module.visit_with(a);
 
+                        a.record_export(id.sym.clone(), Export::Local(id.clone().into()));
                         a.storage.export_type(span, a.ctx.module_id, id.clone().into());
                     }
                     RTsModuleName::Str(..) => {
@@ -112,7 +117,7 @@ impl Analyzer<'_, '_> {
         match export.decl {
             RDefaultDecl::Fn(ref f) => {
                 let i = f.ident.as_ref().map(|v| v.into()).unwrap_or_else(|| Id::word(js_word!("default")));
-                let fn_ty = match f.function.validate_with_args(self, f.ident.as_ref()) {
+                let fn_ty = match f.function.validate_with_args(self, (f.ident.as_ref(), false)) {
                     Ok(ty) => ty,
                     Err(err) => {
                         self.storage.report(err);
@@ -195,8 +200,10 @@ impl Analyzer<'_, '_> {
             self.report_errors_for_duplicated_exports_of_var(span, name.sym().clone());
         }
 
-        self.storage
-            .export_var(span, self.ctx.module_id, name.clone(), orig_name.unwrap_or(name));
+        let orig_name = orig_name.unwrap_or_else(|| name.clone());
+        self.record_export(name.sym().clone(), Export::Local(orig_name.clone()));
+
+        self.storage.export_var(span, self.ctx.module_id, name, orig_name);
     }
 
     /// Exports a type.
@@ -210,6 +217,7 @@ impl Analyzer<'_, '_> {
     #[cfg_attr(debug_assertions, tracing::instrument(skip_all))]
     fn export_type(&mut self, span: Span, name: Id, orig_name: Option<Id>) {
         let orig_name = orig_name.unwrap_or_else(|| name.clone());
+        self.record_export(name.sym().clone(), Export::Local(orig_name.clone()));
 
         let types = match self.find_type(&orig_name) {
             Ok(v) => v,
@@ -295,6 +303,10 @@ impl Analyzer<'_, '_> {
         self.with_ctx(ctx)
             .export_expr(Id::word(js_word!("default")), node.node_id, &node.expr)?;
 
+        if let RExpr::Ident(i) = &*node.expr {
+            self.record_export(js_word!("default"), Export::Local(i.into()));
+        }
+
         Ok(())
     }
 }
@@ -350,6 +362,8 @@ impl Analyzer<'_, '_> {
         let (dep, data) = self.get_imported_items(span, &node.src.value);
 
         if ctxt != dep {
+            self.record_star_export(dep);
+
             match data.normalize() {
                 Type::Module(data) => {
                     for (id, ty) in data.exports.vars.iter() {
@@ -400,13 +414,20 @@ impl Analyzer<'_, '_> {
                         Some(src) => {
                             let (dep, data) = self.get_imported_items(node.span, &src.value);
 
-                            self.reexport(
-                                span,
-                                base,
-                                dep,
-                                named.exported.as_ref().map(Id::from).unwrap_or_else(|| Id::from(&named.orig)),
-                                Id::from(&named.orig),
-                            );
+                            let exported = named.exported.as_ref().map(Id::from).unwrap_or_else(|| Id::from(&named.orig));
+                            let orig = Id::from(&named.orig);
+
+                            if base != dep {
+                                self.record_export(
+                                    exported.sym().clone(),
+                                    Export::Reexport {
+                                        module: dep,
+                                        name: orig.sym().clone(),
+                                    },
+                                );
+                            }
+
+                            self.reexport(span, base, dep, exported, orig);
                         }
                         None => {
                             self.export_named(
@@ -427,6 +448,8 @@ impl Analyzer<'_, '_> {
 
 impl Analyzer<'_, '_> {
     fn export_named(&mut self, span: Span, ctxt: ModuleId, orig: Id, id: Id) {
+        self.record_export(id.sym().clone(), Export::Local(orig.clone()));
+
         if self.storage.get_local_var(ctxt, orig.clone()).is_some() {
             self.report_errors_for_duplicated_exports_of_var(span, id.sym().clone());
 
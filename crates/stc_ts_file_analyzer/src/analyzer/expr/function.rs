@@ -25,6 +25,8 @@ impl Analyzer<'_, '_> {
         let type_ann = self.expand_type_ann(f.span, type_ann)?;
 
         self.with_child(ScopeKind::ArrowFn, Default::default(), |child: &mut Analyzer| {
+            child.declare_params_for_unused_check(&f.params);
+
             let type_params = try_opt!(f.type_params.validate_with(child));
 
             let params = {
@@ -1 +1,829 @@
+//! JSX elements and fragments.
+//!
+//! Types are resolved like `tsc`, using the `JSX` namespace in scope. See
+//! https://www.typescriptlang.org/docs/handbook/jsx.html
 
+use std::borrow::Cow;
+
+use fxhash::FxHashMap;
+use rnode::NodeId;
+use stc_ts_ast_rnode::{
+    RBindingIdent, RBool, RExpr, RIdent, RJSXAttrName, RJSXAttrOrSpread, RJSXAttrValue, RJSXElement, RJSXElementChild, RJSXElementName,
+    RJSXExpr, RJSXExprContainer, RJSXFragment, RJSXMemberExpr, RJSXObject, RJSXOpeningElement, RLit, RMemberExpr, RMemberProp, RPat,
+    RSpreadElement, RTsLit, RTsTypeParamInstantiation,
+};
+use stc_ts_env::JsxMode;
+use stc_ts_errors::Error;
+use stc_ts_type_ops::Fix;
+use stc_ts_types::{
+    Array, ClassMember, FnParam, Id, IdCtx, Key, KeywordType, LitType, PropertySignature, Type, TypeElement, TypeLit, TypeOrSpread,
+    TypeParamDecl,
+};
+use stc_utils::cache::Freeze;
+use swc_atoms::JsWord;
+use swc_common::{Span, Spanned, SyntaxContext, DUMMY_SP};
+use swc_ecma_ast::TsKeywordTypeKind;
+
+use crate::{
+    analyzer::{assign::AssignOpts, expr::TypeOfMode, util::ResultExt, Analyzer},
+    validator,
+    validator::ValidateWith,
+    VResult,
+};
+
+#[validator]
+impl Analyzer<'_, '_> {
+    fn validate(&mut self, e: &RJSXElement) -> VResult<Type> {
+        let name_span = e.opening.name.span().with_ctxt(SyntaxContext::empty());
+        self.check_jsx_preconditions(name_span);
+
+        let mut tag = self.resolve_jsx_tag(&e.opening)?;
+
+        let mut attrs = vec![];
+        for attr in &e.opening.attrs {
+            let attr = self.type_of_jsx_attr(attr, tag.props.as_ref());
+            attrs.extend(attr.report(&mut self.storage));
+        }
+
+        let children_prop = self.jsx_prop_name_of(name_span, "ElementChildrenAttribute")?.flatten();
+        let children_type_ann = match (&children_prop, &tag.props) {
+            (Some(name), Some(props)) => self
+                .access_property(
+                    name_span,
+                    props,
+                    &Key::Normal {
+                        span: name_span,
+                        sym: name.clone(),
+                    },
+                    TypeOfMode::RValue,
+                    IdCtx::Var,
+                    Default::default(),
+                )
+                .ok(),
+            _ => None,
+        };
+        let mut children = self.type_of_jsx_children(&e.children, children_type_ann.as_ref());
+
+        if let Some(name) = children_prop {
+            if !children.is_empty() {
+                for attr in &attrs {
+                    match attr {
+                        JsxAttrType::Attr {
+                            span,
+                            key: Key::Normal { sym, .. },
+                            ..
+                        } if *sym == name => self.storage.report(Error::JsxChildrenSpecifiedTwice {
+                            span: *span,
+                            name: name.clone(),
+                        }),
+                        _ => {}
+                    }
+                }
+
+                let ty = if children.len() == 1 {
+                    children.remove(0)
+                } else {
+                    Type::Array(Array {
+                        span: e.span,
+                        elem_type: box Type::union(children).fixed(),
+                        metadata: Default::default(),
+                    })
+                    .freezed()
+                };
+                attrs.push(JsxAttrType::Attr {
+                    span: name_span,
+                    key: Key::Normal {
+                        span: name_span,
+                        sym: name,
+                    },
+                    ty,
+                });
+            }
+        }
+
+        if let Some(type_params) = tag.type_params.take() {
+            self.instantiate_jsx_tag(&mut tag, &type_params, e.opening.type_args.as_deref(), &attrs)?;
+        }
+
+        if let Some(props) = &tag.props {
+            self.check_jsx_attrs(&tag, props, attrs)?;
+        }
+        self.check_jsx_component(&tag);
+
+        Ok(self.jsx_element_type(e.span))
+    }
+}
+
+#[validator]
+impl Analyzer<'_, '_> {
+    fn validate(&mut self, e: &RJSXFragment) -> VResult<Type> {
+        self.check_jsx_preconditions(e.opening.span);
+        self.check_jsx_fragment_factory(e.opening.span);
+
+        self.type_of_jsx_children(&e.children, None);
+
+        Ok(self.jsx_element_type(e.span))
+    }
+}
+
+/// The tag of an element, like `div` or `Foo`.
+struct JsxTag {
+    /// Used for error messages.
+    name: JsWord,
+    name_span: Span,
+    /// [None] if attributes are not checked.
+    props: Option<Type>,
+    /// Type parameters of a generic component.
+    type_params: Option<TypeParamDecl>,
+    component: JsxComponent,
+}
+
+#[derive(Clone)]
+enum JsxComponent {
+    /// Keys of `JSX.IntrinsicElements`, like `div`.
+    Intrinsic,
+    Function {
+        ret_ty: Option<Type>,
+    },
+    Class {
+        instance: Type,
+    },
+}
+
+enum JsxAttrType {
+    Attr { span: Span, key: Key, ty: Type },
+    Spread(Type),
+}
+
+impl Analyzer<'_, '_> {
+    /// Reports errors for options required by JSX.
+    fn check_jsx_preconditions(&mut self, span: Span) {
+        let jsx = self.env.jsx().clone();
+
+        match jsx.mode {
+            None => self.storage.report(Error::JsxNotEnabled { span }),
+            // The factory should be in scope, as elements are compiled to calls of it.
+            Some(JsxMode::React) => {
+                self.mark_jsx_factory_as_used();
+
+                let root = jsx.factory_root();
+                if self.jsx_id(&root).is_none() && self.env.get_global_var(span, &root).is_err() {
+                    self.storage.report(Error::NoSuchVar {
+                        span,
+                        name: Id::word(root),
+                    })
+                }
+            }
+            // Elements are compiled to calls of functions imported from `jsxImportSource`.
+            Some(JsxMode::ReactJsx | JsxMode::ReactJsxDev) => {
+                if let Some(module) = jsx.runtime_module() {
+                    let base = self.storage.path(self.ctx.module_id);
+                    if self.loader.module_id(&base, &module).is_none() {
+                        self.storage.report(Error::NoJsxRuntime { span, module })
+                    }
+                }
+            }
+            Some(_) => {}
+        }
+    }
+
+    /// Fragments are compiled to `jsxFragmentFactory`, which should be in
+    /// scope.
+    fn check_jsx_fragment_factory(&mut self, span: Span) {
+        let jsx = self.env.jsx().clone();
+        if jsx.mode != Some(JsxMode::React) {
+            return;
+        }
+
+        if jsx.factory.is_some() && jsx.fragment_factory.is_none() {
+            self.storage.report(Error::JsxFragmentFactoryRequired { span });
+            return;
+        }
+
+        let root = jsx.fragment_factory_root();
+        if self.jsx_id(&root).is_none() && self.env.get_global_var(span, &root).is_err() {
+            self.storage.report(Error::NoJsxFragmentFactory {
+                span,
+                name: jsx.fragment_factory(),
+            })
+        }
+    }
+
+    /// The module imported implicitly by elements, like `react/jsx-runtime`.
+    fn jsx_runtime(&mut self) -> Option<Type> {
+        let module = self.env.jsx().runtime_module()?;
+
+        let ctxt = self.ctx.module_id;
+        let base = self.storage.path(ctxt);
+        let dep = self.loader.module_id(&base, &module)?;
+        if let Some(ty) = self.imports.get(&(ctxt, dep)) {
+            return Some(ty.clone());
+        }
+        if dep == ctxt || self.loader.is_in_same_circular_group(ctxt, dep) {
+            return None;
+        }
+
+        let ty = self.loader.load_non_circular_dep(ctxt, dep).ok()?;
+        self.imports.insert((ctxt, dep), ty.clone());
+        Some(ty)
+    }
+
+    /// `JSX.Element`, or `any` if it does not exist.
+    fn jsx_element_type(&mut self, span: Span) -> Type {
+        match self.find_jsx_type("Element") {
+            Some(ty) => ty,
+            None => {
+                if self.rule().no_implicit_any {
+                    self.storage.report(Error::NoJsxElement { span })
+                }
+                Type::any(span, Default::default())
+            }
+        }
+    }
+
+    /// Resolves an identifier which is referenced implicitly, like `JSX` or
+    /// `React`.
+    fn jsx_id(&self, sym: &JsWord) -> Option<Id> {
+        self.scope
+            .find_id_by_sym(sym)
+            .or_else(|| self.imports_by_id.keys().find(|id| id.sym() == sym).cloned())
+    }
+
+    /// Finds `JSX.<name>`. Merged declarations are returned as an intersection.
+    ///
+    /// `JSX` exported from the module of `jsxImportSource` is preferred, if
+    /// elements are compiled to imports of it.
+    fn find_jsx_type(&mut self, name: &str) -> Option<Type> {
+        let mut types = vec![];
+
+        if let Some(Type::Module(runtime)) = self.jsx_runtime().as_ref().map(Type::normalize) {
+            for ns in runtime.exports.types.get(&JsWord::from("JSX")).into_iter().flatten() {
+                collect_jsx_types(ns, name, &mut types);
+            }
+        }
+
+        if types.is_empty() {
+            let id = self.jsx_id(&"JSX".into()).unwrap_or_else(|| Id::word("JSX".into()));
+
+            for ns in self.find_type(&id).ok()?? {
+                collect_jsx_types(&ns, name, &mut types);
+            }
+        }
+
+        match types.len() {
+            0 => None,
+            1 => types.pop(),
+            _ => Some(Type::new_intersection(DUMMY_SP, types).fixed().freezed()),
+        }
+    }
+
+    /// Name of the only property of `JSX.<name>`, like `props` of
+    /// `JSX.ElementAttributesProperty`.
+    ///
+    /// Returns `Some(None)` if the interface is empty.
+    fn jsx_prop_name_of(&mut self, span: Span, name: &str) -> VResult<Option<Option<JsWord>>> {
+        let ty = match self.find_jsx_type(name) {
+            Some(ty) => ty,
+            None => return Ok(None),
+        };
+
+        let lit = self.convert_type_to_type_lit(span, Cow::Owned(ty))?;
+
+        Ok(Some(lit.and_then(|lit| {
+            lit.members.iter().find_map(|m| match m.key() {
+                Some(Key::Normal { sym, .. }) => Some(sym.clone()),
+                _ => None,
+            })
+        })))
+    }
+
+    fn resolve_jsx_tag(&mut self, opening: &RJSXOpeningElement) -> VResult<JsxTag> {
+        let name_span = opening.name.span().with_ctxt(SyntaxContext::empty());
+        let name = jsx_tag_name(&opening.name);
+        let unchecked = |name: JsWord| JsxTag {
+            name,
+            name_span,
+            props: None,
+            type_params: None,
+            component: JsxComponent::Intrinsic,
+        };
+
+        let tag_ty = match &opening.name {
+            RJSXElementName::Ident(i) if !is_intrinsic_jsx_name(&i.sym) => self.type_of_var(i, TypeOfMode::RValue, None),
+            RJSXElementName::JSXMemberExpr(e) => self.type_of_member_expr(&jsx_member_expr(e), TypeOfMode::RValue),
+            _ => {
+                let props = self.jsx_intrinsic_props(name_span, &name);
+                return Ok(JsxTag { props, ..unchecked(name) });
+            }
+        };
+        let tag_ty = match tag_ty.report(&mut self.storage) {
+            Some(ty) => self
+                .normalize(Some(name_span), Cow::Owned(ty), Default::default())?
+                .into_owned()
+                .freezed(),
+            None => return Ok(unchecked(name)),
+        };
+        if tag_ty.is_any() {
+            return Ok(unchecked(name));
+        }
+
+        let (component, params, type_params) = match tag_ty.normalize() {
+            Type::ClassDef(def) => {
+                let params = def
+                    .body
+                    .iter()
+                    .find_map(|m| match m {
+                        ClassMember::Constructor(c) => Some(c.params.clone()),
+                        _ => None,
+                    })
+                    .unwrap_or_default();
+                let instance = self.make_instance(name_span, &tag_ty)?.freezed();
+
+                (JsxComponent::Class { instance }, params, def.type_params.as_deref().cloned())
+            }
+            _ => {
+                let members = self
+                    .convert_type_to_type_lit(name_span, Cow::Borrowed(&tag_ty))?
+                    .map(|lit| lit.into_owned().members)
+                    .unwrap_or_default();
+
+                let call = members.iter().find_map(|m| match m {
+                    TypeElement::Call(c) => Some(c),
+                    _ => None,
+                });
+                let constructor = members.iter().find_map(|m| match m {
+                    TypeElement::Constructor(c) => Some(c),
+                    _ => None,
+                });
+
+                match (call, constructor) {
+                    (Some(c), _) => (
+                        JsxComponent::Function {
+                            ret_ty: c.ret_ty.as_deref().cloned(),
+                        },
+                        c.params.clone(),
+                        c.type_params.clone(),
+                    ),
+                    (None, Some(c)) => (
+                        JsxComponent::Class {
+                            instance: c
+                                .ret_ty
+                                .as_deref()
+                                .cloned()
+                                .unwrap_or_else(|| Type::any(name_span, Default::default())),
+                        },
+                        c.params.clone(),
+                        c.type_params.clone(),
+                    ),
+                    (None, None) => {
+                        self.storage.report(Error::JsxElementNotCallable {
+                            span: name_span,
+                            name: name.clone(),
+                        });
+                        return Ok(unchecked(name));
+                    }
+                }
+            }
+        };
+
+        let first_param = params.into_iter().next().map(|p| *p.ty);
+        let mut props = match &component {
+            JsxComponent::Class { instance } => self.jsx_class_props(name_span, instance, first_param)?,
+            _ => first_param.unwrap_or_else(|| empty_type_lit(name_span)),
+        };
+
+        let mut extra = vec![];
+        extra.extend(self.find_jsx_type("IntrinsicAttributes"));
+        if let JsxComponent::Class { instance } = &component {
+            if let Some(ty) = self.find_jsx_type("IntrinsicClassAttributes") {
+                extra.push(self.instantiate_jsx_type(ty, instance.clone())?);
+            }
+        }
+        if !extra.is_empty() {
+            extra.insert(0, props);
+            props = Type::new_intersection(name_span, extra).fixed();
+        }
+
+        Ok(JsxTag {
+            name,
+            name_span,
+            props: Some(props.freezed()),
+            type_params,
+            component,
+        })
+    }
+
+    /// Props of an intrinsic element, which is a property of
+    /// `JSX.IntrinsicElements`.
+    fn jsx_intrinsic_props(&mut self, span: Span, name: &JsWord) -> Option<Type> {
+        let elements = match self.find_jsx_type("IntrinsicElements") {
+            Some(ty) => ty,
+            None => {
+                if self.rule().no_implicit_any {
+                    self.storage.report(Error::NoJsxIntrinsicElements { span })
+                }
+                return None;
+            }
+        };
+
+        let key = Key::Normal { span, sym: name.clone() };
+        match self.access_property(span, &elements, &key, TypeOfMode::RValue, IdCtx::Var, Default::default()) {
+            Ok(ty) => Some(ty.freezed()),
+            Err(..) => {
+                self.storage.report(Error::NoSuchProperty {
+                    span,
+                    obj: Some(box elements),
+                    prop: Some(box key),
+                });
+                None
+            }
+        }
+    }
+
+    /// Props of a class component are a property of the instance named by
+    /// `JSX.ElementAttributesProperty`. If it does not exist, the first
+    /// parameter of the constructor is used.
+    fn jsx_class_props(&mut self, span: Span, instance: &Type, first_param: Option<Type>) -> VResult<Type> {
+        Ok(match self.jsx_prop_name_of(span, "ElementAttributesProperty")? {
+            None => first_param.unwrap_or_else(|| empty_type_lit(span)),
+            Some(None) => instance.clone(),
+            Some(Some(name)) => self
+                .access_property(
+                    span,
+                    instance,
+                    &Key::Normal { span, sym: name },
+                    TypeOfMode::RValue,
+                    IdCtx::Var,
+                    Default::default(),
+                )
+                .unwrap_or_else(|_| empty_type_lit(span)),
+        })
+    }
+
+    /// Instantiates a generic interface like `JSX.IntrinsicClassAttributes<T>`.
+    fn instantiate_jsx_type(&mut self, ty: Type, arg: Type) -> VResult<Type> {
+        if let Type::Interface(i) = ty.normalize() {
+            if let Some(param) = i.type_params.as_ref().and_then(|decl| decl.params.first()) {
+                let mut params = FxHashMap::default();
+                params.insert(param.name.clone(), arg.freezed());
+
+                let mut i = i.clone();
+                i.type_params = None;
+                return Ok(self.expand_type_params(&params, Type::Interface(i), Default::default())?.freezed());
+            }
+        }
+
+        Ok(ty)
+    }
+
+    /// Instantiates a generic component using type arguments, or by inferring
+    /// them from attributes.
+    fn instantiate_jsx_tag(
+        &mut self,
+        tag: &mut JsxTag,
+        type_params: &TypeParamDecl,
+        type_args: Option<&RTsTypeParamInstantiation>,
+        attrs: &[JsxAttrType],
+    ) -> VResult<()> {
+        let props = match &tag.props {
+            Some(props) => props.clone(),
+            None => return Ok(()),
+        };
+        let span = tag.name_span;
+
+        let params = match type_args {
+            Some(type_args) => {
+                let type_args = type_args.validate_with(self)?;
+                self.instantiate_type_params_using_args(span, type_params, &type_args)?
+            }
+            None => {
+                let param = FnParam {
+                    span,
+                    required: true,
+                    pat: RPat::Ident(RBindingIdent {
+                        node_id: NodeId::invalid(),
+                        id: RIdent::new("props".into(), span),
+                        type_ann: None,
+                    }),
+                    ty: box props.clone(),
+                };
+                let arg = TypeOrSpread {
+                    span,
+                    spread: None,
+                    ty: box self.jsx_attrs_type(span, attrs)?,
+                };
+
+                self.infer_arg_types(span, None, &type_params.params, &[param], &[arg], None, Default::default())?
+                    .types
+            }
+        };
+
+        tag.props = Some(self.expand_type_params(&params, props, Default::default())?.freezed());
+        tag.component = match tag.component.clone() {
+            JsxComponent::Function { ret_ty: Some(ret_ty) } => JsxComponent::Function {
+                ret_ty: Some(self.expand_type_params(&params, ret_ty, Default::default())?.freezed()),
+            },
+            JsxComponent::Class { instance } => JsxComponent::Class {
+                instance: self.expand_type_params(&params, instance, Default::default())?.freezed(),
+            },
+            component => component,
+        };
+
+        Ok(())
+    }
+
+    fn type_of_jsx_attr(&mut self, attr: &RJSXAttrOrSpread, props: Option<&Type>) -> VResult<JsxAttrType> {
+        match attr {
+            RJSXAttrOrSpread::JSXAttr(attr) => {
+                let key = Key::Normal {
+                    span: attr.name.span().with_ctxt(SyntaxContext::empty()),
+                    sym: jsx_attr_name(&attr.name),
+                };
+                let type_ann = props.and_then(|props| {
+                    self.access_property(attr.span, props, &key, TypeOfMode::RValue, IdCtx::Var, Default::default())
+                        .ok()
+                });
+
+                let ty = match &attr.value {
+                    // `<input disabled />`
+                    None => Type::Lit(LitType {
+                        span: attr.span,
+                        lit: RTsLit::Bool(RBool {
+                            span: attr.span,
+                            value: true,
+                        }),
+                        metadata: Default::default(),
+                    }),
+                    Some(RJSXAttrValue::Lit(RLit::Str(s))) => Type::Lit(LitType {
+                        span: s.span,
+                        lit: RTsLit::Str(s.clone()),
+                        metadata: Default::default(),
+                    }),
+                    Some(RJSXAttrValue::Lit(lit)) => RExpr::Lit(lit.clone()).validate_with_default(self)?,
+                    Some(RJSXAttrValue::JSXExprContainer(c)) => self
+                        .type_of_jsx_expr_container(c, type_ann.as_ref())?
+                        .unwrap_or_else(|| Type::any(c.span, Default::default())),
+                    Some(RJSXAttrValue::JSXElement(e)) => e.validate_with(self)?,
+                    Some(RJSXAttrValue::JSXFragment(f)) => f.validate_with(self)?,
+                };
+
+                Ok(JsxAttrType::Attr {
+                    span: attr.span,
+                    key,
+                    ty: ty.freezed(),
+                })
+            }
+            RJSXAttrOrSpread::SpreadElement(RSpreadElement { expr, .. }) => {
+                let ty = expr.validate_with_args(self, (TypeOfMode::RValue, None, props))?;
+
+                Ok(JsxAttrType::Spread(ty.freezed()))
+            }
+        }
+    }
+
+    /// Returns [None] for `{}` and `{/* comment */}`.
+    fn type_of_jsx_expr_container(&mut self, c: &RJSXExprContainer, type_ann: Option<&Type>) -> VResult<Option<Type>> {
+        match &c.expr {
+            RJSXExpr::JSXEmptyExpr(..) => Ok(None),
+            RJSXExpr::Expr(e) => Ok(Some(e.validate_with_args(self, (TypeOfMode::RValue, None, type_ann))?)),
+        }
+    }
+
+    /// Types of children. Errors are reported and the erroneous child is typed
+    /// as `any`.
+    fn type_of_jsx_children(&mut self, children: &[RJSXElementChild], type_ann: Option<&Type>) -> Vec<Type> {
+        let mut types = vec![];
+
+        for child in children {
+            let ty = match child {
+                // Whitespaces containing a line break are not children.
+                RJSXElementChild::JSXText(t) if t.value.trim().is_empty() && t.value.contains('\n') => continue,
+                RJSXElementChild::JSXText(t) => Ok(Type::Keyword(KeywordType {
+                    span: t.span,
+                    kind: TsKeywordTypeKind::TsStringKeyword,
+                    metadata: Default::default(),
+                })),
+                RJSXElementChild::JSXExprContainer(c) => match self.type_of_jsx_expr_container(c, type_ann) {
+                    Ok(Some(ty)) => Ok(ty),
+                    Ok(None) => continue,
+                    Err(err) => Err(err),
+                },
+                RJSXElementChild::JSXSpreadChild(c) => c.expr.validate_with_args(self, (TypeOfMode::RValue, None, type_ann)),
+                RJSXElementChild::JSXElement(e) => e.validate_with(self),
+                RJSXElementChild::JSXFragment(f) => f.validate_with(self),
+            };
+
+            let ty = ty
+                .report(&mut self.storage)
+                .unwrap_or_else(|| Type::any(child.span(), Default::default()));
+            types.push(ty.freezed());
+        }
+
+        types
+    }
+
+    /// Type of attributes, as an object literal.
+    fn jsx_attrs_type(&mut self, span: Span, attrs: &[JsxAttrType]) -> VResult<Type> {
+        let mut ty = empty_type_lit(span);
+
+        for attr in attrs {
+            ty = match attr {
+                JsxAttrType::Attr { span, key, ty: attr_ty } => self.append_type_element(
+                    ty,
+                    TypeElement::Property(PropertySignature {
+                        span: *span,
+                        accessibility: None,
+                        readonly: false,
+                        key: key.clone(),
+                        optional: false,
+                        params: Default::default(),
+                        type_ann: Some(box attr_ty.clone()),
+                        type_params: Default::default(),
+                        metadata: Default::default(),
+                        accessor: Default::default(),
+                    }),
+                )?,
+                JsxAttrType::Spread(spread) => self.append_type(ty, spread.clone())?,
+            };
+        }
+
+        Ok(ty.freezed())
+    }
+
+    /// Checks each attribute, and then all attributes at once to find missing
+    /// properties.
+    ///
+    /// Unknown attributes with a hyphen, like `data-foo`, are allowed.
+    fn check_jsx_attrs(&mut self, tag: &JsxTag, props: &Type, attrs: Vec<JsxAttrType>) -> VResult<()> {
+        let mut has_error = false;
+        let mut checked = vec![];
+
+        for attr in attrs {
+            if let JsxAttrType::Attr { span, key, ty } = &attr {
+                match self.access_property(*span, props, key, TypeOfMode::LValue, IdCtx::Var, Default::default()) {
+                    Ok(prop_ty) => {
+                        let res = self.assign_with_opts(
+                            &mut Default::default(),
+                            &prop_ty,
+                            ty,
+                            AssignOpts {
+                                span: *span,
+                                ..Default::default()
+                            },
+                        );
+                        if res.report(&mut self.storage).is_none() {
+                            has_error = true;
+                        }
+                    }
+                    Err(..) => {
+                        if let Key::Normal { span, sym } = key {
+                            if !sym.contains('-') {
+                                self.storage.report(Error::NoSuchJsxAttr {
+                                    span: *span,
+                                    name: sym.clone(),
+                                    props: box props.clone(),
+                                });
+                                has_error = true;
+                            }
+                        }
+                        continue;
+                    }
+                }
+            }
+
+            checked.push(attr);
+        }
+
+        if !has_error {
+            let attrs = self.jsx_attrs_type(tag.name_span, &checked)?;
+            self.assign_with_opts(
+                &mut Default::default(),
+                props,
+                &attrs,
+                AssignOpts {
+                    span: tag.name_span,
+                    ..Default::default()
+                },
+            )
+            .report(&mut self.storage);
+        }
+
+        Ok(())
+    }
+
+    /// A function component should return `JSX.Element | null`, and an
+    /// instance of a class component should be `JSX.ElementClass`.
+    fn check_jsx_component(&mut self, tag: &JsxTag) {
+        let (ty, expected) = match &tag.component {
+            JsxComponent::Function { ret_ty: Some(ret_ty) } => match self.find_jsx_type("Element") {
+                Some(element) => (
+                    ret_ty,
+                    Type::union(vec![
+                        element,
+                        Type::Keyword(KeywordType {
+                            span: tag.name_span,
+                            kind: TsKeywordTypeKind::TsNullKeyword,
+                            metadata: Default::default(),
+                        }),
+                    ])
+                    .freezed(),
+                ),
+                None => return,
+            },
+            JsxComponent::Class { instance } => match self.find_jsx_type("ElementClass") {
+                Some(element_class) => (instance, element_class),
+                None => return,
+            },
+            _ => return,
+        };
+
+        let res = self.assign_with_opts(
+            &mut Default::default(),
+            &expected,
+            ty,
+            AssignOpts {
+                span: tag.name_span,
+                ..Default::default()
+            },
+        );
+        if res.is_err() {
+            self.storage.report(Error::InvalidJsxComponent {
+                span: tag.name_span,
+                name: tag.name.clone(),
+            })
+        }
+    }
+}
+
+/// Collects types named `name` from the `JSX` namespace.
+fn collect_jsx_types(ns: &Type, name: &str, types: &mut Vec<Type>) {
+    match ns.normalize() {
+        Type::Namespace(ns) => match ns.exports.types.get(&JsWord::from(name)) {
+            Some(tys) => types.extend(tys.iter().cloned()),
+            // Declarations in a `declare namespace` are exported implicitly.
+            None => {
+                for (id, tys) in &ns.exports.private_types {
+                    if &**id.sym() == name {
+                        types.extend(tys.iter().cloned());
+                    }
+                }
+            }
+        },
+        Type::Intersection(i) => {
+            for ty in &i.types {
+                collect_jsx_types(ty, name, types);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Lowercase names and names with a hyphen are intrinsic elements.
+fn is_intrinsic_jsx_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase()) || name.contains('-')
+}
+
+fn jsx_tag_name(name: &RJSXElementName) -> JsWord {
+    match name {
+        RJSXElementName::Ident(i) => i.sym.clone(),
+        RJSXElementName::JSXMemberExpr(e) => format!("{}.{}", jsx_object_name(&e.obj), e.prop.sym).into(),
+        RJSXElementName::JSXNamespacedName(n) => format!("{}:{}", n.ns.sym, n.name.sym).into(),
+    }
+}
+
+fn jsx_object_name(obj: &RJSXObject) -> String {
+    match obj {
+        RJSXObject::Ident(i) => i.sym.to_string(),
+        RJSXObject::JSXMemberExpr(e) => format!("{}.{}", jsx_object_name(&e.obj), e.prop.sym),
+    }
+}
+
+fn jsx_attr_name(name: &RJSXAttrName) -> JsWord {
+    match name {
+        RJSXAttrName::Ident(i) => i.sym.clone(),
+        RJSXAttrName::JSXNamespacedName(n) => format!("{}:{}", n.ns.sym, n.name.sym).into(),
+    }
+}
+
+/// `<Foo.Bar />` is typed like `Foo.Bar`.
+fn jsx_member_expr(e: &RJSXMemberExpr) -> RMemberExpr {
+    let obj = match &e.obj {
+        RJSXObject::Ident(i) => RExpr::Ident(i.clone()),
+        RJSXObject::JSXMemberExpr(e) => RExpr::Member(jsx_member_expr(e)),
+    };
+
+    RMemberExpr {
+        node_id: NodeId::invalid(),
+        span: e.span(),
+        obj: box obj,
+        prop: RMemberProp::Ident(e.prop.clone()),
+    }
+}
+
+fn empty_type_lit(span: Span) -> Type {
+    Type::TypeLit(TypeLit {
+        span,
+        members: vec![],
+        metadata: Default::default(),
+    })
+}
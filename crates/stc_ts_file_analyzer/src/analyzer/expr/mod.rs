@@ -167,6 +167,10 @@ impl Analyzer<'_, '_> {
                         }));
                     }
 
+                    if self.rule().no_implicit_this && self.is_this_implicitly_any() {
+                        self.storage.report(Error::ThisImplicitlyAny { span })
+                    }
+
                     let scope = if self.ctx.in_computed_prop_name {
                         self.scope.scope_of_computed_props()
                     } else {
@@ -324,6 +328,12 @@ impl Analyzer<'_, '_> {
 
                 RExpr::TsInstantiation(expr) => expr.validate_with_args(self, (mode, None, type_ann)),
 
+                RExpr::JSXElement(e) => e.validate_with(self),
+
+                RExpr::JSXFragment(e) => e.validate_with(self),
+
+                RExpr::JSXMember(..) | RExpr::JSXNamespacedName(..) | RExpr::JSXEmpty(..) => Ok(Type::any(span, Default::default())),
+
                 _ => unimplemented!("typeof ({:?})", e),
             }
         })()?;
@@ -364,6 +374,8 @@ impl Analyzer<'_, '_> {
             )
         }
 
+        self.record_type(span, &ty);
+
         // Exclude literals
         if !span.is_dummy()
             & match e {
@@ -410,6 +422,10 @@ impl Analyzer<'_, '_> {
 
                     left_i = Some(i.clone());
 
+                    if e.op != op!("=") {
+                        analyzer.mark_read_write_as_used(i);
+                    }
+
                     ty_of_left = analyzer
                         .type_of_var(i, TypeOfMode::LValue, None)
                         .context("tried to get type of lhs of an assignment")
@@ -841,6 +857,7 @@ impl Analyzer<'_, '_> {
         obj: &Type,
         prop: &Key,
         type_mode: TypeOfMode,
+        id_ctx: IdCtx,
         members: &[TypeElement],
         opts: AccessPropertyOpts,
     ) -> VResult<Option<Type>> {
@@ -855,7 +872,9 @@ impl Analyzer<'_, '_> {
                             }
 
                             if let Some(ref type_ann) = p.type_ann {
-                                if p.optional {
+                                // With `exactOptionalPropertyTypes`, `undefined` cannot be written to
+                                // an optional property unless it's declared.
+                                if p.optional && !(type_mode == TypeOfMode::LValue && self.rule().exact_optional_property_types) {
                                     let mut types = vec![Type::undefined(span, Default::default()), *type_ann.clone()];
                                     types.dedup_type();
                                     matching_elements.push(Type::union(types));
@@ -997,16 +1016,20 @@ impl Analyzer<'_, '_> {
                     if indexed {
                         if let Some(ref type_ann) = type_ann {
                             let ty = self.expand_top_ref(span, Cow::Borrowed(type_ann), Default::default())?;
-                            return Ok(Some(ty.into_owned()));
+                            return Ok(Some(self.add_undefined_for_unchecked_index(
+                                span,
+                                ty.into_owned(),
+                                type_mode,
+                                id_ctx,
+                            )));
                         }
 
                         return Ok(Some(Type::any(span, Default::default())));
                     }
 
                     if (&**index_ty).type_eq(&*prop_ty) {
-                        return Ok(Some(
-                            type_ann.clone().map(|v| *v).unwrap_or_else(|| Type::any(span, Default::default())),
-                        ));
+                        let ty = type_ann.clone().map(|v| *v).unwrap_or_else(|| Type::any(span, Default::default()));
+                        return Ok(Some(self.add_undefined_for_unchecked_index(span, ty, type_mode, id_ctx)));
                     }
 
                     match prop_ty.normalize() {
@@ -1284,7 +1307,7 @@ impl Analyzer<'_, '_> {
 
                     // TODO(kdy1): Remove clone
                     let members = self.scope.object_lit_members().to_vec();
-                    if let Some(mut v) = self.access_property_of_type_elements(span, &obj, prop, type_mode, &members, opts)? {
+                    if let Some(mut v) = self.access_property_of_type_elements(span, &obj, prop, type_mode, id_ctx, &members, opts)? {
                         v.metadata_mut().infected_by_this_in_object_literal = true;
                         return Ok(v);
                     }
@@ -1566,7 +1589,11 @@ impl Analyzer<'_, '_> {
                             RTsEnumMemberId::Str(s) => s.value == *sym,
                         });
                         if !has_such_member {
-                            return Err(Error::NoSuchEnumVariant { span, name: sym.clone() });
+                            return Err(Error::NoSuchEnumVariant {
+                                span,
+                                name: sym.clone(),
+                                enum_name: Id::from(&e.id),
+                            });
                         }
 
                         // Computed values are not permitted in an enum with string valued members.
@@ -1606,7 +1633,7 @@ impl Analyzer<'_, '_> {
                                 TypeOfMode::LValue => prop.span(),
                                 TypeOfMode::RValue => span,
                             },
-                            enum_name: e.id.clone().into(),
+                            enum_name: Id::from(&e.id),
                             name: Some(sym.clone()),
                             metadata: Default::default(),
                         }));
@@ -1770,11 +1797,12 @@ impl Analyzer<'_, '_> {
                                     || self.assign(span, &mut Default::default(), &index_ty, &prop_ty).is_ok();
 
                                 if indexed {
-                                    return Ok(index
+                                    let ty = index
                                         .type_ann
                                         .clone()
                                         .map(|v| *v)
-                                        .unwrap_or_else(|| Type::any(span, Default::default())));
+                                        .unwrap_or_else(|| Type::any(span, Default::default()));
+                                    return Ok(self.add_undefined_for_unchecked_index(span, ty, type_mode, id_ctx));
                                 }
                             }
                         }
@@ -1968,13 +1996,13 @@ impl Analyzer<'_, '_> {
                         })
                         | Type::Lit(LitType {
                             lit: RTsLit::Number(..), ..
-                        }) => return Ok(elem_type.clone()),
+                        }) => return Ok(self.add_undefined_for_unchecked_index(span, elem_type, type_mode, id_ctx)),
 
                         _ => {}
                     }
                 }
                 if let Key::Num(n) = prop {
-                    return Ok(elem_type.clone());
+                    return Ok(self.add_undefined_for_unchecked_index(span, elem_type, type_mode, id_ctx));
                 }
 
                 let array_ty = self.env.get_global_type(span, &js_word!("Array"))?;
@@ -2039,7 +2067,7 @@ impl Analyzer<'_, '_> {
             }
 
             Type::Interface(Interface { ref body, extends, .. }) => {
-                if let Ok(Some(v)) = self.access_property_of_type_elements(span, &obj, prop, type_mode, body, opts) {
+                if let Ok(Some(v)) = self.access_property_of_type_elements(span, &obj, prop, type_mode, id_ctx, body, opts) {
                     return Ok(v);
                 }
 
@@ -2089,7 +2117,7 @@ impl Analyzer<'_, '_> {
             }
 
             Type::TypeLit(TypeLit { ref members, metadata, .. }) => {
-                if let Some(v) = self.access_property_of_type_elements(span, &obj, prop, type_mode, members, opts)? {
+                if let Some(v) = self.access_property_of_type_elements(span, &obj, prop, type_mode, id_ctx, members, opts)? {
                     return Ok(v);
                 }
 
@@ -2934,6 +2962,10 @@ impl Analyzer<'_, '_> {
             }));
         }
 
+        if type_mode != TypeOfMode::LValue {
+            self.mark_as_used(&id);
+        }
+
         let mut modules = vec![];
         let mut ty = self.type_of_raw_var(i, type_mode)?;
         if type_mode == TypeOfMode::LValue && (ty.is_class_def() || ty.is_enum_type()) {
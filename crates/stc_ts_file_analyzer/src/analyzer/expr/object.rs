@@ -23,6 +23,10 @@ impl Analyzer<'_, '_> {
         let type_ann = self.expand_type_ann(node.span, type_ann)?;
         debug_assert_eq!(node.span.ctxt, SyntaxContext::empty());
 
+        if let Some(type_ann) = &type_ann {
+            self.record_contextual_type(node.span, type_ann);
+        }
+
         self.with_child(ScopeKind::ObjectLit, Default::default(), |a: &mut Analyzer| {
             let mut ret = Type::TypeLit(TypeLit {
                 span: node.span,
@@ -142,7 +146,10 @@ impl Analyzer<'_, '_> {
                                     prev_key.type_eq(&key)
                                 })
                             {
-                                self.storage.report(Error::DuplicateProperty { span })
+                                self.storage.report(Error::DuplicateProperty {
+                                    span,
+                                    key: box key.clone(),
+                                })
                             } else {
                                 known_keys.push(key);
                             }
@@ -161,7 +168,7 @@ impl Analyzer<'_, '_> {
     /// `{ a: number } + ( {b: number} | { c: number } )` => `{ a: number, b:
     /// number } | { a: number, c: number }`
     #[cfg_attr(debug_assertions, tracing::instrument(skip_all))]
-    fn append_type(&mut self, to: Type, mut rhs: Type) -> VResult<Type> {
+    pub(super) fn append_type(&mut self, to: Type, mut rhs: Type) -> VResult<Type> {
         if to.is_any() || to.is_unknown() {
             return Ok(to);
         }
@@ -262,7 +269,7 @@ impl Analyzer<'_, '_> {
     }
 
     #[cfg_attr(debug_assertions, tracing::instrument(skip_all))]
-    fn append_type_element(&mut self, to: Type, rhs: TypeElement) -> VResult<Type> {
+    pub(super) fn append_type_element(&mut self, to: Type, rhs: TypeElement) -> VResult<Type> {
         if to.is_any() || to.is_unknown() {
             return Ok(to);
         }
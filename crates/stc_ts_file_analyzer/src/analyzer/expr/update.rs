@@ -24,6 +24,10 @@ impl Analyzer<'_, '_> {
             _ => {}
         }
 
+        if let RExpr::Ident(i) = &*e.arg {
+            self.mark_read_write_as_used(i);
+        }
+
         let res = e
             .arg
             .validate_with_args(self, (TypeOfMode::LValue, None, None))
@@ -27,7 +27,7 @@ mod return_type;
 
 #[validator]
 impl Analyzer<'_, '_> {
-    fn validate(&mut self, f: &RFunction, name: Option<&RIdent>) -> VResult<ty::Function> {
+    fn validate(&mut self, f: &RFunction, name: Option<&RIdent>, has_contextual_this: bool) -> VResult<ty::Function> {
         self.record(f);
 
         let marks = self.marks();
@@ -48,10 +48,15 @@ impl Analyzer<'_, '_> {
 
         self.with_child(ScopeKind::Fn, Default::default(), |child: &mut Analyzer| {
             child.ctx.allow_new_target = true;
+            child.ctx.in_fn_with_contextual_this = has_contextual_this;
             child.ctx.in_fn_with_return_type = f.return_type.is_some();
             child.ctx.in_async = f.is_async;
             child.ctx.in_generator = f.is_generator;
 
+            if f.body.is_some() {
+                child.declare_params_for_unused_check(f.params.iter().map(|param| &param.pat));
+            }
+
             let mut errors = Errors::default();
 
             {
@@ -379,7 +384,11 @@ impl Analyzer<'_, '_> {
                 self.scope.declaring_fn = Some(name.into());
             }
 
-            let mut fn_ty: ty::Function = f.validate_with_args(self, name)?;
+            let has_contextual_this = match type_ann {
+                Some(ty) => self.has_contextual_this(f.span, ty),
+                None => false,
+            };
+            let mut fn_ty: ty::Function = f.validate_with_args(self, (name, has_contextual_this))?;
             // Handle type parameters in return type.
             fn_ty.ret_ty = fn_ty.ret_ty.fold_with(&mut TypeParamHandler {
                 params: fn_ty.type_params.as_ref().map(|v| &*v.params),
@@ -464,11 +473,13 @@ impl Analyzer<'_, '_> {
             in_generator: f.function.is_generator,
             ..self.ctx
         };
-        let fn_ty = self
-            .with_ctx(ctx)
-            .with_child(ScopeKind::Fn, Default::default(), |a: &mut Analyzer| {
+        self.with_ctx(ctx).declare_decl_for_unused_check(&f.ident, false);
+
+        let fn_ty = self.with_declaring_for_unused_check(f.ident.clone().into(), |a: &mut Analyzer| {
+            a.with_ctx(ctx).with_child(ScopeKind::Fn, Default::default(), |a: &mut Analyzer| {
                 Ok(a.visit_fn(Some(&f.ident), &f.function, None).cheap())
-            })?;
+            })
+        })?;
 
         let mut a = self.with_ctx(ctx);
         match a.declare_var(f.span(), VarKind::Fn, f.ident.clone().into(), Some(fn_ty), None, true, true, false) {
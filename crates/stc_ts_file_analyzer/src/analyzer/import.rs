@@ -7,12 +7,13 @@ use stc_ts_errors::Error;
 use stc_ts_file_analyzer_macros::extra_validator;
 use stc_ts_storage::Storage;
 use stc_ts_types::{Id, ModuleId, Type};
-use stc_ts_utils::imports::find_imports_in_comments;
+use stc_ts_utils::imports::{find_imports_in_comments, ImportRef};
 use swc_atoms::{js_word, JsWord};
 use swc_common::{comments::Comments, Span, Spanned};
 
 use crate::{
     analyzer::{scope::VarKind, util::ResultExt, Analyzer},
+    index::ImportedName,
     loader::ModuleInfo,
     validator, DepInfo, VResult,
 };
@@ -29,7 +30,10 @@ impl Analyzer<'_, '_> {
         let dep_id = match dep_id {
             Some(v) => v,
             None => {
-                self.storage.report(Error::ModuleNotFound { span });
+                self.storage.report(Error::ModuleNotFound {
+                    span,
+                    specifier: dst.clone(),
+                });
 
                 return (ctxt, Type::any(span, Default::default()));
             }
@@ -37,7 +41,10 @@ impl Analyzer<'_, '_> {
         let data = match self.imports.get(&(ctxt, dep_id)).cloned() {
             Some(v) => v,
             None => {
-                self.storage.report(Error::ModuleNotFound { span });
+                self.storage.report(Error::ModuleNotFound {
+                    span,
+                    specifier: dst.clone(),
+                });
 
                 return (ctxt, Type::any(span, Default::default()));
             }
@@ -85,11 +92,18 @@ impl Analyzer<'_, '_> {
             let span = import.span;
 
             let base = self.storage.path(ctxt);
-            let dep_id = self.loader.module_id(&base, &import.src);
+            let dep_id = if import.is_type_reference {
+                self.loader.module_id_of_type_reference(&base, &import.src, import.resolution_mode)
+            } else {
+                self.loader.module_id(&base, &import.src)
+            };
             let dep_id = match dep_id {
                 Some(v) => v,
                 None => {
-                    self.storage.report(Error::ModuleNotFound { span });
+                    self.storage.report(Error::ModuleNotFound {
+                        span,
+                        specifier: import.src.clone(),
+                    });
                     continue;
                 }
             };
@@ -127,9 +141,13 @@ impl Analyzer<'_, '_> {
 }
 
 impl Analyzer<'_, '_> {
-    fn handle_import(&mut self, span: Span, ctxt: ModuleId, target: ModuleId, orig: Id, id: Id) {
+    fn handle_import(&mut self, span: Span, ctxt: ModuleId, target: ModuleId, specifier: &JsWord, orig: Id, id: Id) {
         let mut found_entry = false;
 
+        if ctxt != target {
+            self.record_import(id.clone(), target, ImportedName::Named(orig.sym().clone()));
+        }
+
         // Check for entry only if import was successful.
         if ctxt != target {
             if let Some(data) = self.imports.get(&(ctxt, target)) {
@@ -177,7 +195,12 @@ impl Analyzer<'_, '_> {
             if ctxt != target {
                 // If import was successful but the entry is not found, the error should point
                 // the specifier.
-                self.storage.report(Error::ImportFailed { span, orig, id });
+                self.storage.report(Error::ImportFailed {
+                    span,
+                    specifier: specifier.clone(),
+                    orig,
+                    id,
+                });
             }
         }
     }
@@ -189,6 +212,8 @@ impl Analyzer<'_, '_> {
         let span = node.span;
         let base = self.ctx.module_id;
 
+        self.declare_import_for_unused_check(node);
+
         let (dep, data) = self.get_imported_items(span, &node.src.value);
 
         for specifier in &node.specifiers {
@@ -197,15 +222,29 @@ impl Analyzer<'_, '_> {
                     //
                     match &named.imported {
                         Some(imported) => {
-                            self.handle_import(named.span, base, dep, Id::from(imported), Id::from(&named.local));
+                            self.handle_import(named.span, base, dep, &node.src.value, Id::from(imported), Id::from(&named.local));
                         }
                         None => {
-                            self.handle_import(named.span, base, dep, Id::from(&named.local), Id::from(&named.local));
+                            self.handle_import(
+                                named.span,
+                                base,
+                                dep,
+                                &node.src.value,
+                                Id::from(&named.local),
+                                Id::from(&named.local),
+                            );
                         }
                     }
                 }
                 RImportSpecifier::Default(default) => {
-                    self.handle_import(default.span, base, dep, Id::word(js_word!("default")), Id::from(&default.local));
+                    self.handle_import(
+                        default.span,
+                        base,
+                        dep,
+                        &node.src.value,
+                        Id::word(js_word!("default")),
+                        Id::from(&default.local),
+                    );
                 }
                 RImportSpecifier::Namespace(ns) => {
                     if base == dep {
@@ -221,6 +260,7 @@ impl Analyzer<'_, '_> {
                             false,
                         )?;
                     } else {
+                        self.record_import(ns.local.clone().into(), dep, ImportedName::Namespace);
                         self.declare_var(
                             ns.span,
                             VarKind::Import,
@@ -262,8 +302,22 @@ where
         let ctxt = self.cur_ctxt;
         let deps = find_imports_in_comments(&self.comments, span);
 
-        self.to
-            .extend(deps.into_iter().map(|src| (ctxt, DepInfo { span, src: src.to_path() })));
+        self.to.extend(deps.into_iter().filter_map(|import| {
+            let (is_type_reference, resolution_mode) = match &import {
+                ImportRef::Types(_, mode) => (true, *mode),
+                _ => (false, None),
+            };
+
+            Some((
+                ctxt,
+                DepInfo {
+                    span,
+                    src: import.to_path()?,
+                    is_type_reference,
+                    resolution_mode,
+                },
+            ))
+        }));
     }
 
     pub fn find_imports<T>(comments: C, module_span: Vec<(ModuleId, Span)>, storage: &'a Storage<'a>, node: &T) -> Vec<(ModuleId, DepInfo)>
@@ -328,7 +382,15 @@ where
                     })
                     .next()
                     .unwrap();
-                self.to.push((self.cur_ctxt, DepInfo { span, src }));
+                self.to.push((
+                    self.cur_ctxt,
+                    DepInfo {
+                        span,
+                        src,
+                        is_type_reference: false,
+                        resolution_mode: None,
+                    },
+                ));
             }
             _ => return,
         }
@@ -347,6 +409,8 @@ where
             DepInfo {
                 span,
                 src: import.src.value.clone(),
+                is_type_reference: false,
+                resolution_mode: None,
             },
         ));
     }
@@ -366,6 +430,8 @@ where
             DepInfo {
                 span: export.span,
                 src: export.src.as_ref().unwrap().value.clone(),
+                is_type_reference: false,
+                resolution_mode: None,
             },
         ));
     }
@@ -381,6 +447,8 @@ where
             DepInfo {
                 span: export.span,
                 src: export.src.value.clone(),
+                is_type_reference: false,
+                resolution_mode: None,
             },
         ));
     }
@@ -396,6 +464,8 @@ where
             DepInfo {
                 span: r.span,
                 src: r.expr.value.clone(),
+                is_type_reference: false,
+                resolution_mode: None,
             },
         ));
     }
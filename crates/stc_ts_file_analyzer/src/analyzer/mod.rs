@@ -18,7 +18,7 @@ use stc_ts_errors::{debug::debugger::Debugger, Error};
 use stc_ts_storage::{Builtin, Info, Storage};
 use stc_ts_type_cache::TypeCache;
 use stc_ts_types::{Id, IdCtx, ModuleId, ModuleTypeData, Namespace};
-use stc_ts_utils::StcComments;
+use stc_ts_utils::{imports::ResolutionMode, StcComments};
 use stc_utils::{cache::Freeze, panic_ctx, AHashMap, AHashSet};
 use swc_atoms::{js_word, JsWord};
 use swc_common::{FileName, SourceMap, Span, Spanned, DUMMY_SP, GLOBALS};
@@ -29,10 +29,11 @@ use self::{
     control_flow::{CondFacts, Facts},
     pat::PatMode,
     props::ComputedPropMode,
-    scope::{Scope, VarKind},
+    scope::{Scope, UnusedData, VarKind},
     util::ResultExt,
 };
 use crate::{
+    index::{ImportedName, ModuleIndex},
     loader::{Load, ModuleInfo},
     ty,
     ty::Type,
@@ -41,6 +42,8 @@ use crate::{
     VResult,
 };
 
+pub use self::completion::PropertyInfo;
+
 macro_rules! try_opt {
     ($e:expr) => {{
         match $e {
@@ -52,6 +55,7 @@ macro_rules! try_opt {
 
 mod assign;
 mod class;
+mod completion;
 mod control_flow;
 mod convert;
 mod decl_merging;
@@ -150,6 +154,9 @@ pub(crate) struct Ctx {
     allow_ref_declaring: bool,
     in_argument: bool,
     in_fn_with_return_type: bool,
+    /// `true` if the contextual type of the function being validated has a
+    /// `this` parameter.
+    in_fn_with_contextual_this: bool,
     in_return_arg: bool,
     in_assign_rhs: bool,
 
@@ -224,6 +231,9 @@ pub struct Analyzer<'scope, 'b> {
     /// This is [None] only for `.d.ts` files.
     pub mutations: Option<Mutations>,
 
+    /// Set this to record information for IDE features.
+    pub index: Option<ModuleIndex>,
+
     storage: Storage<'b>,
 
     export_equals_span: Span,
@@ -293,6 +303,9 @@ struct AnalyzerData {
     cache: TypeCache,
 
     checked_for_async_iterator: bool,
+
+    /// Used to report unused declarations.
+    unused: UnusedData,
 }
 
 #[derive(Debug, Default)]
@@ -465,6 +478,7 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
             comments,
             storage,
             mutations,
+            index: None,
             export_equals_span: DUMMY_SP,
             imports: Default::default(),
             prepend_stmts: Default::default(),
@@ -510,6 +524,7 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
                 allow_ref_declaring: false,
                 in_argument: false,
                 in_fn_with_return_type: false,
+                in_fn_with_contextual_this: false,
                 in_return_arg: false,
                 in_assign_rhs: false,
                 in_export_decl: false,
@@ -578,6 +593,7 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
         let imports = take(&mut self.imports);
         let imports_by_id = take(&mut self.imports_by_id);
         let mutations = self.mutations.take();
+        let index = self.index.take();
         let cur_facts = take(&mut self.cur_facts);
         let module_data = if kind == ScopeKind::Module {
             take(&mut self.data.for_module)
@@ -587,11 +603,12 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
         let data = take(&mut self.data);
 
         let child_scope = Scope::new(&self.scope, kind, facts);
-        let (ret, errors, imports, imports_by_id, cur_facts, mut child_scope, prepend_stmts, append_stmts, mutations, data) = {
+        let (ret, errors, imports, imports_by_id, cur_facts, mut child_scope, prepend_stmts, append_stmts, mutations, index, data) = {
             let mut child = self.new(child_scope, data);
             child.imports = imports;
             child.imports_by_id = imports_by_id;
             child.mutations = mutations;
+            child.index = index;
             child.cur_facts = cur_facts;
             child.ctx = ctx;
 
@@ -613,6 +630,7 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
                 child.prepend_stmts,
                 child.append_stmts,
                 child.mutations.take(),
+                child.index.take(),
                 take(&mut child.data),
             )
         };
@@ -622,6 +640,7 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
         self.imports_by_id = imports_by_id;
         self.cur_facts = cur_facts;
         self.mutations = mutations;
+        self.index = index;
         self.data = data;
 
         hook(self);
@@ -717,6 +736,10 @@ impl Load for NoopLoader {
         unreachable!()
     }
 
+    fn module_id_of_type_reference(&self, base: &Arc<FileName>, name: &JsWord, mode: Option<ResolutionMode>) -> Option<ModuleId> {
+        unreachable!()
+    }
+
     fn is_in_same_circular_group(&self, base: ModuleId, dep: ModuleId) -> bool {
         unreachable!()
     }
@@ -750,6 +773,10 @@ impl Analyzer<'_, '_> {
 
         self.validate_stmts_with_hoisting(&items);
 
+        for m in modules {
+            self.report_unused(m);
+        }
+
         Ok(())
     }
 }
@@ -813,6 +840,8 @@ impl Analyzer<'_, '_> {
                 self.validate_stmts_and_collect(&items_ref);
             }
 
+            self.report_unused(m);
+
             Ok(())
         })
     }
@@ -848,6 +877,13 @@ impl Analyzer<'_, '_> {
 
         let ctxt = self.ctx.module_id;
 
+        if !node.is_export && !node.declare {
+            self.declare_import_equals_for_unused_check(node.span, &node.id, node.is_type_only);
+        }
+        if let RTsModuleRef::TsEntityName(e) = &node.module_ref {
+            self.mark_entity_name_as_used(e);
+        }
+
         let ctx = Ctx {
             in_declare: self.ctx.in_declare || node.declare,
             ..self.ctx
@@ -866,6 +902,7 @@ impl Analyzer<'_, '_> {
 
                     // Import successful
                     if ctxt != dep {
+                        analyzer.record_import(node.id.clone().into(), dep, ImportedName::Namespace);
                         analyzer
                             .imports
                             .get(&(ctxt, dep))
@@ -975,51 +1012,59 @@ impl Analyzer<'_, '_> {
             in_declare: self.ctx.in_declare || decl.declare,
             ..self.ctx
         };
-        let mut ty = self
-            .with_ctx(ctx)
-            .with_child(ScopeKind::Module, Default::default(), |child: &mut Analyzer| {
-                child.scope.cur_module_name = match &decl.id {
-                    RTsModuleName::Ident(i) => Some(i.into()),
-                    RTsModuleName::Str(_) => None,
-                };
+        let validate = |a: &mut Analyzer| {
+            a.with_ctx(ctx)
+                .with_child(ScopeKind::Module, Default::default(), |child: &mut Analyzer| {
+                    child.scope.cur_module_name = match &decl.id {
+                        RTsModuleName::Ident(i) => Some(i.into()),
+                        RTsModuleName::Str(_) => None,
+                    };
 
-                decl.visit_children_with(child);
+                    decl.visit_children_with(child);
 
-                let mut exports = child.storage.take_info(ctxt);
-                // Ambient module members are always exported with or without export keyword
-                if is_builtin || decl.declare {
-                    for (id, var) in take(&mut exports.private_vars) {
-                        var.assert_valid();
+                    let mut exports = child.storage.take_info(ctxt);
+                    // Ambient module members are always exported with or without export keyword
+                    if is_builtin || decl.declare {
+                        for (id, var) in take(&mut exports.private_vars) {
+                            var.assert_valid();
 
-                        if !exports.vars.contains_key(id.sym()) {
-                            exports.vars.insert(id.sym().clone(), var);
+                            if !exports.vars.contains_key(id.sym()) {
+                                exports.vars.insert(id.sym().clone(), var);
+                            }
                         }
-                    }
 
-                    for (id, ty) in take(&mut exports.private_types) {
-                        for ty in &ty {
-                            ty.assert_valid();
-                        }
+                        for (id, ty) in take(&mut exports.private_types) {
+                            for ty in &ty {
+                                ty.assert_valid();
+                            }
 
-                        if !exports.types.contains_key(id.sym()) {
-                            exports.types.insert(id.sym().clone(), ty);
+                            if !exports.types.contains_key(id.sym()) {
+                                exports.types.insert(id.sym().clone(), ty);
+                            }
                         }
                     }
-                }
 
-                if is_builtin || !global {
-                    let ty = ty::Module {
-                        name: decl.id.clone(),
-                        span,
-                        exports: box exports,
-                        metadata: Default::default(),
-                    };
-                    let ty = Type::Module(ty).cheap();
-                    return Ok(Some(ty));
-                }
+                    if is_builtin || !global {
+                        let ty = ty::Module {
+                            name: decl.id.clone(),
+                            span,
+                            exports: box exports,
+                            metadata: Default::default(),
+                        };
+                        let ty = Type::Module(ty).cheap();
+                        return Ok(Some(ty));
+                    }
 
-                Ok(None)
-            })?;
+                    Ok(None)
+                })
+        };
+        let mut ty = match &decl.id {
+            RTsModuleName::Ident(i) if !global => {
+                self.with_ctx(ctx).declare_decl_for_unused_check(i, false);
+                self.with_declaring_for_unused_check(i.into(), validate)?
+            }
+            _ => validate(self)?,
+        };
 
         if let Some(ty) = &mut ty {
             ty.make_cheap();
@@ -431,6 +431,9 @@ impl Analyzer<'_, '_> {
                 self.with_child(ScopeKind::Method { is_static: false }, Default::default(), {
                     |child: &mut Analyzer| -> VResult<_> {
                         child.ctx.pat_mode = PatMode::Decl;
+                        if p.body.is_some() {
+                            child.declare_params_for_unused_check(Some(&**param));
+                        }
                         let param = param.validate_with(child)?;
 
                         p.body.visit_with(child);
@@ -27,12 +27,12 @@ use stc_utils::{
     cache::{Freeze, ALLOW_DEEP_CLONE},
     debug_ctx, panic_ctx, stack,
 };
-use swc_atoms::js_word;
+use swc_atoms::{js_word, JsWord};
 use swc_common::{util::move_map::MoveMap, Span, Spanned, SyntaxContext, TypeEq, DUMMY_SP};
 use swc_ecma_ast::*;
 use tracing::{debug, error, info, instrument};
 
-pub(crate) use self::vars::VarKind;
+pub(crate) use self::{unused::UnusedData, vars::VarKind};
 use crate::{
     analyzer::{
         assign::AssignOpts,
@@ -53,6 +53,7 @@ use crate::{
 
 mod this;
 mod type_param;
+mod unused;
 mod vars;
 
 macro_rules! no_ref {
@@ -645,6 +646,16 @@ impl Scope<'_> {
         None
     }
 
+    /// Finds a variable or a type named `sym`, which is visible from this
+    /// scope. This is used for names referenced implicitly, like `JSX`.
+    pub fn find_id_by_sym(&self, sym: &JsWord) -> Option<Id> {
+        if let Some(id) = self.vars.keys().chain(self.types.keys()).find(|id| id.sym() == sym) {
+            return Some(id.clone());
+        }
+
+        self.parent?.find_id_by_sym(sym)
+    }
+
     pub fn mark_as_super_called(&self) {
         if self.kind == ScopeKind::Class {
             *self.class.need_super_call.borrow_mut() = false;
@@ -1343,6 +1354,12 @@ impl Analyzer<'_, '_> {
 
         let ty = ty.map(|ty| ty.cheap());
 
+        if !is_override {
+            if let Some(ty) = &ty {
+                self.record_type(span, ty);
+            }
+        }
+
         if let Some(actual_ty) = &actual_ty {
             if actual_ty.is_never() {
                 print_backtrace();
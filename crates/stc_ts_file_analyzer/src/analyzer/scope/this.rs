@@ -1,10 +1,12 @@
 use std::borrow::Cow;
 
 use rnode::{VisitMut, VisitMutWith};
+use stc_ts_ast_rnode::RPat;
 use stc_ts_type_ops::this::contains_this;
-use stc_ts_types::{ClassMember, ClassProperty, Id, Key, Method, Type};
+use stc_ts_types::{CallSignature, ClassMember, ClassProperty, FnParam, Id, Interface, Key, Method, Type, TypeElement, TypeLit};
+use swc_common::Span;
 
-use crate::analyzer::Analyzer;
+use crate::analyzer::{Analyzer, ScopeKind};
 
 impl Analyzer<'_, '_> {
     pub(crate) fn this_has_property_named(&mut self, p: &Id) -> bool {
@@ -36,6 +38,89 @@ impl Analyzer<'_, '_> {
         false
     }
 
+    /// Returns `true` if `this` implicitly has type `any`, which is the case
+    /// for functions without a `this` parameter or a contextual `this` type.
+    pub(crate) fn is_this_implicitly_any(&self) -> bool {
+        let scope = if self.ctx.in_computed_prop_name {
+            self.scope.scope_of_computed_props()
+        } else {
+            Some(&self.scope)
+        };
+        // Arrow functions capture `this` of the enclosing scope.
+        let container = match scope.and_then(|scope| {
+            scope.first_kind(|kind| match kind {
+                ScopeKind::Fn
+                | ScopeKind::Method { .. }
+                | ScopeKind::Constructor
+                | ScopeKind::Class
+                | ScopeKind::ObjectLit
+                | ScopeKind::Module => true,
+                ScopeKind::ArrowFn
+                | ScopeKind::Flow
+                | ScopeKind::Block
+                | ScopeKind::LoopBody { .. }
+                | ScopeKind::TypeParams
+                | ScopeKind::Call => false,
+            })
+        }) {
+            Some(container) => container,
+            None => return false,
+        };
+
+        if container.kind() != ScopeKind::Fn || container.is_root() {
+            return false;
+        }
+
+        // `this` parameter
+        if container.this.is_some() || self.ctx.in_fn_with_contextual_this {
+            return false;
+        }
+
+        // `this` of a function expression in an object literal is the object
+        // literal.
+        !matches!(container.parent().map(|scope| scope.kind()), Some(ScopeKind::ObjectLit))
+    }
+
+    /// Returns `true` if every call signature of the contextual type `ty` of a
+    /// function expression has a `this` parameter.
+    pub(crate) fn has_contextual_this(&mut self, span: Span, ty: &Type) -> bool {
+        let mut found = vec![];
+        self.collect_contextual_this_params(span, ty, &mut found);
+
+        !found.is_empty() && found.into_iter().all(|v| v)
+    }
+
+    fn collect_contextual_this_params(&mut self, span: Span, ty: &Type, found: &mut Vec<bool>) {
+        let ty = match self.normalize(Some(span), Cow::Borrowed(ty), Default::default()) {
+            Ok(ty) => ty,
+            Err(..) => return,
+        };
+
+        fn has_this_param(params: &[FnParam]) -> bool {
+            params.iter().any(|param| match &param.pat {
+                RPat::Ident(i) => i.id.sym == *"this",
+                _ => false,
+            })
+        }
+
+        match ty.normalize() {
+            Type::Function(f) => found.push(has_this_param(&f.params)),
+            Type::TypeLit(TypeLit { members, .. }) | Type::Interface(Interface { body: members, .. }) => {
+                for member in members {
+                    if let TypeElement::Call(CallSignature { params, .. }) = member {
+                        found.push(has_this_param(params));
+                    }
+                }
+            }
+            Type::Union(u) => {
+                for ty in &u.types {
+                    self.collect_contextual_this_params(span, ty, found);
+                }
+            }
+            _ => {}
+        }
+    }
+
     /// Expand `this` contained in `ty`.
     pub(crate) fn expand_this_in_type(&mut self, ty: &mut Type) {
         let this_ty = self.scope.this();
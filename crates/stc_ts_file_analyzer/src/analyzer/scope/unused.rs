@@ -0,0 +1,613 @@
+//! `noUnusedLocals` and `noUnusedParameters`.
+//!
+//! Validators of declarations record them using `declare_*_for_unused_check`,
+//! and references resolved by the analyzer are recorded using
+//! [Analyzer::mark_as_used]. Declarations are reported at the end of the
+//! module. The grouping of errors (e.g. `All destructured elements are
+//! unused.`) follows `checkUnusedLocalsAndParameters` of `tsc`.
+
+use std::mem::take;
+
+use fxhash::FxHashSet;
+use stc_ts_ast_rnode::{
+    RDecl, RExpr, RExprStmt, RIdent, RImportDecl, RImportSpecifier, RModule, RModuleItem, RObjectPatProp, RPat, RPatOrExpr, RTsEntityName,
+    RTsModuleName, RTsTypeParamDecl, RVarDecl,
+};
+use stc_ts_errors::Error;
+use stc_ts_types::Id;
+use stc_ts_utils::find_ids_in_pat;
+use swc_common::{Span, Spanned};
+use swc_ecma_ast::VarDeclKind;
+
+use crate::analyzer::{scope::ScopeKind, Analyzer};
+
+/// Declarations and references of a module, used to report unused
+/// declarations.
+#[derive(Debug, Default)]
+pub(crate) struct UnusedData {
+    entries: Vec<Entry>,
+    /// A node can be validated multiple times, so we record it only once.
+    recorded: FxHashSet<Span>,
+    used: FxHashSet<Id>,
+    /// Functions, classes and types which are being validated. References
+    /// from the declaration itself are not uses.
+    declaring: Vec<Id>,
+    /// Spans of `x` in `x += 1;` and `x++;`, which only write `x`.
+    write_only: FxHashSet<Span>,
+    uses_jsx: bool,
+}
+
+#[derive(Debug)]
+struct Decl {
+    id: Id,
+    span: Span,
+    /// Classes, interfaces, type aliases, enums and type-only imports.
+    is_type: bool,
+}
+
+impl Decl {
+    fn value(i: &RIdent) -> Self {
+        Decl {
+            id: i.into(),
+            span: i.span,
+            is_type: false,
+        }
+    }
+
+    fn starts_with_underscore(&self) -> bool {
+        self.id.sym().starts_with('_')
+    }
+
+    fn error(&self) -> Error {
+        if self.is_type {
+            Error::UnusedType {
+                span: self.span,
+                name: self.id.clone(),
+            }
+        } else {
+            Error::UnusedLocal {
+                span: self.span,
+                name: self.id.clone(),
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Binding {
+    Ident(Decl),
+    /// An object pattern or an array pattern.
+    Pat {
+        span: Span,
+        is_object: bool,
+        elems: Vec<Elem>,
+    },
+}
+
+#[derive(Debug)]
+struct Elem {
+    span: Span,
+    /// [None] for holes of array patterns.
+    binding: Option<Binding>,
+    /// `{ a: _a }`, `[_a]` and `a` of `{ a, ...rest }` are never reported.
+    skip: bool,
+}
+
+#[derive(Debug)]
+enum EntryKind {
+    /// Functions, classes, type declarations and namespaces.
+    Decl(Decl),
+    /// `import a, { b } from 'foo'` or `import a = require('foo')`.
+    Import {
+        span: Span,
+        decls: Vec<Decl>,
+    },
+    /// Declarators of a `var`, `let` or `const` declaration.
+    Vars {
+        span: Span,
+        in_for_in_or_of: bool,
+        decls: Vec<Binding>,
+    },
+    Param(Binding),
+    TypeParams {
+        span: Span,
+        decls: Vec<Decl>,
+    },
+}
+
+#[derive(Debug)]
+struct Entry {
+    kind: EntryKind,
+    /// Top-level declarations of a script are global, and they are not
+    /// reported.
+    is_global: bool,
+}
+
+fn expr_ident(e: &RExpr) -> Option<&RIdent> {
+    match e {
+        RExpr::Ident(i) => Some(i),
+        RExpr::Paren(e) => expr_ident(&e.expr),
+        _ => None,
+    }
+}
+
+/// Returns the binding declared by `pat`.
+fn binding_of(pat: &RPat) -> Option<Binding> {
+    match pat {
+        RPat::Ident(i) => Some(Binding::Ident(Decl::value(&i.id))),
+        RPat::Assign(p) => binding_of(&p.left),
+        RPat::Rest(p) => binding_of(&p.arg),
+        RPat::Array(p) => {
+            let elems = p
+                .elems
+                .iter()
+                .map(|elem| {
+                    let binding = elem.as_ref().and_then(binding_of);
+                    let skip = matches!(&binding, Some(Binding::Ident(d)) if d.starts_with_underscore());
+
+                    Elem {
+                        span: elem.as_ref().map_or(p.span, |pat| pat.span()),
+                        binding,
+                        skip,
+                    }
+                })
+                .collect();
+
+            Some(Binding::Pat {
+                span: p.span,
+                is_object: false,
+                elems,
+            })
+        }
+        RPat::Object(p) => {
+            // In `{ a, ...rest }`, `a` is used as it removes a property from `rest`.
+            let has_rest = matches!(p.props.last(), Some(RObjectPatProp::Rest(..)));
+
+            let elems = p
+                .props
+                .iter()
+                .enumerate()
+                .map(|(idx, prop)| {
+                    let (binding, renamed) = match prop {
+                        RObjectPatProp::KeyValue(prop) => (binding_of(&prop.value), true),
+                        RObjectPatProp::Assign(prop) => (Some(Binding::Ident(Decl::value(&prop.key))), false),
+                        RObjectPatProp::Rest(prop) => (binding_of(&prop.arg), false),
+                    };
+
+                    let skip = (renamed && matches!(&binding, Some(Binding::Ident(d)) if d.starts_with_underscore()))
+                        || (has_rest && idx + 1 != p.props.len());
+
+                    Elem {
+                        span: prop.span(),
+                        binding,
+                        skip,
+                    }
+                })
+                .collect();
+
+            Some(Binding::Pat {
+                span: p.span,
+                is_object: true,
+                elems,
+            })
+        }
+        RPat::Expr(..) | RPat::Invalid(..) => None,
+    }
+}
+
+impl Analyzer<'_, '_> {
+    fn checks_unused(&self) -> bool {
+        let rule = self.rule();
+
+        !self.ctx.is_dts && !self.ctx.in_declare && !self.is_builtin && (rule.no_unused_locals || rule.no_unused_parameters)
+    }
+
+    /// Returns `true` if a declaration in the current scope is a top-level
+    /// declaration. If `hoist` is true, block scopes are skipped like `var`.
+    fn is_top_level_for_unused_check(&self, hoist: bool) -> bool {
+        let mut scope = Some(&self.scope);
+        while let Some(s) = scope {
+            if s.is_root() {
+                return true;
+            }
+
+            match s.kind() {
+                ScopeKind::Block | ScopeKind::Flow | ScopeKind::LoopBody { .. } if hoist => {}
+                // Scopes created only to validate a declaration.
+                ScopeKind::TypeParams | ScopeKind::Call => {}
+                _ => return false,
+            }
+            scope = s.parent();
+        }
+
+        true
+    }
+
+    fn declare_for_unused_check(&mut self, span: Span, kind: EntryKind, hoist: bool) {
+        if !self.checks_unused() || !self.data.unused.recorded.insert(span) {
+            return;
+        }
+
+        let is_global = self.is_top_level_for_unused_check(hoist);
+        self.data.unused.entries.push(Entry { kind, is_global });
+    }
+
+    /// Records a reference to `id`.
+    pub(crate) fn mark_as_used(&mut self, id: &Id) {
+        if !self.data.unused.declaring.contains(id) {
+            self.data.unused.used.insert(id.clone());
+        }
+    }
+
+    /// Exported declarations are used by importers.
+    pub(crate) fn mark_export_decl_as_used(&mut self, d: &RDecl) {
+        let ids: Vec<Id> = match d {
+            RDecl::Class(c) => vec![(&c.ident).into()],
+            RDecl::Fn(f) => vec![(&f.ident).into()],
+            RDecl::Var(v) => find_ids_in_pat(&v.decls),
+            RDecl::TsInterface(d) => vec![(&d.id).into()],
+            RDecl::TsTypeAlias(d) => vec![(&d.id).into()],
+            RDecl::TsEnum(d) => vec![(&d.id).into()],
+            RDecl::TsModule(d) => match &d.id {
+                RTsModuleName::Ident(i) => vec![i.into()],
+                RTsModuleName::Str(..) => vec![],
+            },
+        };
+
+        self.data.unused.used.extend(ids);
+    }
+
+    /// Records a reference to `i` by a compound assignment or an update
+    /// expression, unless the result is discarded.
+    pub(crate) fn mark_read_write_as_used(&mut self, i: &RIdent) {
+        if !self.data.unused.write_only.contains(&i.span) {
+            self.mark_as_used(&i.into());
+        }
+    }
+
+    /// `x += 1;` and `x++;` do not read `x`.
+    pub(crate) fn record_write_only_access(&mut self, s: &RExprStmt) {
+        let target = match &*s.expr {
+            RExpr::Assign(e) => match &e.left {
+                RPatOrExpr::Pat(pat) => match &**pat {
+                    RPat::Ident(i) => Some(&i.id),
+                    RPat::Expr(e) => expr_ident(e),
+                    _ => None,
+                },
+                RPatOrExpr::Expr(e) => expr_ident(e),
+            },
+            RExpr::Update(e) => expr_ident(&e.arg),
+            _ => None,
+        };
+
+        if let Some(i) = target {
+            self.data.unused.write_only.insert(i.span);
+        }
+    }
+
+    /// Records a reference to the leftmost identifier of `n`, e.g. `a` of
+    /// `a.b.c`.
+    pub(crate) fn mark_entity_name_as_used(&mut self, n: &RTsEntityName) {
+        match n {
+            RTsEntityName::Ident(i) => self.mark_as_used(&i.into()),
+            RTsEntityName::TsQualifiedName(n) => self.mark_entity_name_as_used(&n.left),
+        }
+    }
+
+    /// Records a reference to the leftmost identifier of `e`, e.g. `a` of
+    /// `a.b` in `implements a.b`.
+    pub(crate) fn mark_expr_as_used(&mut self, e: &RExpr) {
+        match e {
+            RExpr::Ident(i) => self.mark_as_used(&i.into()),
+            RExpr::Member(e) => self.mark_expr_as_used(&e.obj),
+            RExpr::Paren(e) => self.mark_expr_as_used(&e.expr),
+            _ => {}
+        }
+    }
+
+    /// JSX elements use the roots of `jsxFactory` and `jsxFragmentFactory`,
+    /// like `React`, if they are compiled to calls of them.
+    pub(crate) fn mark_jsx_factory_as_used(&mut self) {
+        self.data.unused.uses_jsx = true;
+    }
+
+    /// References to `id` from `op` are not uses.
+    pub(crate) fn with_declaring_for_unused_check<F, Ret>(&mut self, id: Id, op: F) -> Ret
+    where
+        F: FnOnce(&mut Self) -> Ret,
+    {
+        self.data.unused.declaring.push(id);
+        let ret = op(self);
+        self.data.unused.declaring.pop();
+        ret
+    }
+
+    /// Records a function, a class, a type declaration or a namespace.
+    /// Exported declarations are marked as used by the validator of the export.
+    pub(crate) fn declare_decl_for_unused_check(&mut self, i: &RIdent, is_type: bool) {
+        let decl = Decl { is_type, ..Decl::value(i) };
+        // Functions are hoisted.
+        self.declare_for_unused_check(i.span, EntryKind::Decl(decl), !is_type);
+    }
+
+    pub(crate) fn declare_import_for_unused_check(&mut self, d: &RImportDecl) {
+        let decls = d
+            .specifiers
+            .iter()
+            .map(|s| match s {
+                RImportSpecifier::Named(s) => Decl {
+                    is_type: d.type_only || s.is_type_only,
+                    ..Decl::value(&s.local)
+                },
+                RImportSpecifier::Default(s) => Decl {
+                    is_type: d.type_only,
+                    ..Decl::value(&s.local)
+                },
+                RImportSpecifier::Namespace(s) => Decl {
+                    is_type: d.type_only,
+                    ..Decl::value(&s.local)
+                },
+            })
+            .collect::<Vec<_>>();
+
+        if !decls.is_empty() {
+            self.declare_for_unused_check(d.span, EntryKind::Import { span: d.span, decls }, false);
+        }
+    }
+
+    /// Records `import a = require('foo')` or `import a = b.c`.
+    pub(crate) fn declare_import_equals_for_unused_check(&mut self, span: Span, id: &RIdent, is_type_only: bool) {
+        let decl = Decl {
+            is_type: is_type_only,
+            ..Decl::value(id)
+        };
+        self.declare_for_unused_check(span, EntryKind::Import { span, decls: vec![decl] }, false);
+    }
+
+    pub(crate) fn declare_vars_for_unused_check(&mut self, v: &RVarDecl, in_for_in_or_of: bool) {
+        if v.declare {
+            return;
+        }
+
+        let decls = v.decls.iter().filter_map(|d| binding_of(&d.name)).collect::<Vec<_>>();
+        if decls.len() != v.decls.len() {
+            return;
+        }
+
+        self.declare_for_unused_check(
+            v.span,
+            EntryKind::Vars {
+                span: v.span,
+                in_for_in_or_of,
+                decls,
+            },
+            v.kind == VarDeclKind::Var,
+        );
+    }
+
+    /// Records parameters of a function with a body. Parameter properties
+    /// should not be passed, as they are properties.
+    pub(crate) fn declare_params_for_unused_check<'a>(&mut self, params: impl IntoIterator<Item = &'a RPat>) {
+        for param in params {
+            match binding_of(param) {
+                // `this` is not a parameter.
+                Some(Binding::Ident(d)) if &**d.id.sym() == "this" => {}
+                Some(binding) => self.declare_for_unused_check(param.span(), EntryKind::Param(binding), false),
+                None => {}
+            }
+        }
+    }
+
+    pub(crate) fn declare_type_params_for_unused_check(&mut self, decl: &RTsTypeParamDecl) {
+        let decls = decl.params.iter().map(|p| Decl::value(&p.name)).collect();
+
+        self.declare_for_unused_check(decl.span, EntryKind::TypeParams { span: decl.span, decls }, false);
+    }
+
+    /// Reports unused declarations of `m` if `noUnusedLocals` or
+    /// `noUnusedParameters` is enabled.
+    pub(crate) fn report_unused(&mut self, m: &RModule) {
+        let rule = self.rule();
+        if self.ctx.is_dts || self.is_builtin || !(rule.no_unused_locals || rule.no_unused_parameters) {
+            return;
+        }
+
+        // Top-level declarations of a script are global.
+        let is_module = m.body.iter().any(|item| matches!(item, RModuleItem::ModuleDecl(..)));
+
+        let mut data = take(&mut self.data.unused);
+        if data.uses_jsx {
+            let roots = [self.env.jsx().factory_root(), self.env.jsx().fragment_factory_root()];
+            data.used.extend(
+                data.entries
+                    .iter()
+                    .flat_map(|entry| entry.decl_ids())
+                    .filter(|id| roots.contains(id.sym()))
+                    .cloned(),
+            );
+        }
+
+        let mut reporter = Reporter {
+            locals: rule.no_unused_locals,
+            params: rule.no_unused_parameters,
+            used: &data.used,
+            errors: vec![],
+        };
+        for entry in data.entries {
+            if entry.is_global && !is_module {
+                continue;
+            }
+            reporter.report(entry.kind);
+        }
+
+        for err in reporter.errors {
+            self.storage.report(err);
+        }
+    }
+}
+
+impl Entry {
+    /// Ids of imports and declarations, which can be the factory of JSX.
+    fn decl_ids(&self) -> Vec<&Id> {
+        match &self.kind {
+            EntryKind::Decl(d) => vec![&d.id],
+            EntryKind::Import { decls, .. } => decls.iter().map(|d| &d.id).collect(),
+            EntryKind::Vars { decls, .. } => decls
+                .iter()
+                .filter_map(|b| match b {
+                    Binding::Ident(d) => Some(&d.id),
+                    _ => None,
+                })
+                .collect(),
+            EntryKind::Param(..) | EntryKind::TypeParams { .. } => vec![],
+        }
+    }
+}
+
+struct Reporter<'a> {
+    locals: bool,
+    params: bool,
+    used: &'a FxHashSet<Id>,
+    errors: Vec<Error>,
+}
+
+impl Reporter<'_> {
+    fn is_used(&self, d: &Decl) -> bool {
+        self.used.contains(&d.id)
+    }
+
+    fn report(&mut self, entry: EntryKind) {
+        match entry {
+            EntryKind::Decl(d) => {
+                if self.locals && !self.is_used(&d) {
+                    self.errors.push(d.error());
+                }
+            }
+            EntryKind::Import { span, decls } => {
+                if !self.locals {
+                    return;
+                }
+
+                let unused = decls.iter().filter(|d| !self.is_used(d)).collect::<Vec<_>>();
+                if unused.is_empty() {
+                    return;
+                }
+
+                if unused.len() == decls.len() {
+                    self.errors.push(if unused.len() == 1 {
+                        Error::UnusedLocal {
+                            span,
+                            name: unused[0].id.clone(),
+                        }
+                    } else {
+                        Error::AllImportsUnused { span }
+                    });
+                } else {
+                    let errors = unused.iter().map(|d| d.error()).collect::<Vec<_>>();
+                    self.errors.extend(errors);
+                }
+            }
+            EntryKind::Vars {
+                span,
+                in_for_in_or_of,
+                decls,
+            } => {
+                let mut unused = vec![];
+                for binding in &decls {
+                    match binding {
+                        Binding::Ident(d) => {
+                            if !self.is_used(d) && !(in_for_in_or_of && d.starts_with_underscore()) {
+                                unused.push((d.span, d.id.clone()));
+                            }
+                        }
+                        Binding::Pat { .. } => self.report_pat(binding, false, Some(&mut unused)),
+                    }
+                }
+
+                if !self.locals || unused.is_empty() {
+                    return;
+                }
+
+                if unused.len() == decls.len() && unused.len() > 1 {
+                    self.errors.push(Error::AllVarsUnused { span });
+                } else {
+                    self.errors
+                        .extend(unused.into_iter().map(|(span, name)| Error::UnusedLocal { span, name }));
+                }
+            }
+            EntryKind::Param(binding) => match &binding {
+                Binding::Ident(d) => {
+                    if self.params && !d.starts_with_underscore() && !self.is_used(d) {
+                        self.errors.push(d.error());
+                    }
+                }
+                Binding::Pat { .. } => self.report_pat(&binding, true, None),
+            },
+            EntryKind::TypeParams { span, decls } => {
+                if !self.params {
+                    return;
+                }
+
+                let unused = decls
+                    .iter()
+                    .filter(|d| !d.starts_with_underscore() && !self.is_used(d))
+                    .collect::<Vec<_>>();
+                if unused.len() == decls.len() && unused.len() > 1 {
+                    self.errors.push(Error::AllTypeParamsUnused { span });
+                } else {
+                    let errors = unused.iter().map(|d| d.error()).collect::<Vec<_>>();
+                    self.errors.extend(errors);
+                }
+            }
+        }
+    }
+
+    /// `var_group` is [Some] if `binding` is the name of a variable
+    /// declarator. A pattern with only one element is reported as a variable
+    /// in the case.
+    fn report_pat(&mut self, binding: &Binding, is_param: bool, var_group: Option<&mut Vec<(Span, Id)>>) {
+        let (span, is_object, elems) = match binding {
+            Binding::Pat { span, is_object, elems } => (*span, *is_object, elems),
+            Binding::Ident(..) => return,
+        };
+        let enabled = if is_param { self.params } else { self.locals };
+
+        let mut unused = vec![];
+        for elem in elems {
+            match &elem.binding {
+                Some(nested @ Binding::Pat { .. }) => self.report_pat(nested, is_param, None),
+                Some(Binding::Ident(d)) => {
+                    if !elem.skip && !self.is_used(d) {
+                        unused.push((elem.span, d));
+                    }
+                }
+                None => {}
+            }
+        }
+
+        if unused.is_empty() || !enabled {
+            return;
+        }
+
+        // Elements of array patterns in variable declarations are reported one by one.
+        if !is_object && !is_param {
+            self.errors.extend(unused.into_iter().map(|(_, d)| d.error()));
+            return;
+        }
+
+        if unused.len() == elems.len() {
+            if unused.len() == 1 {
+                let name = unused[0].1.id.clone();
+                match var_group {
+                    Some(vars) => vars.push((span, name)),
+                    None => self.errors.push(Error::UnusedLocal { span, name }),
+                }
+            } else {
+                self.errors.push(Error::AllDestructuredElementsUnused { span });
+            }
+        } else {
+            self.errors
+                .extend(unused.into_iter().map(|(span, d)| Error::UnusedLocal { span, name: d.id.clone() }));
+        }
+    }
+}
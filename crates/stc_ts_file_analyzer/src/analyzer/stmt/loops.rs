@@ -118,6 +118,7 @@ impl Analyzer<'_, '_> {
                 }
 
                 // Store variables
+                self.declare_vars_for_unused_check(v, true);
                 v.visit_with(self);
             }
             RVarDeclOrPat::Pat(ref pat) => {
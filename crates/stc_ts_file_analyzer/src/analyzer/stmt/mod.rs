@@ -57,6 +57,10 @@ impl Analyzer<'_, '_> {
             _ => false,
         };
 
+        if let RStmt::Expr(s) = s {
+            self.record_write_only_access(s);
+        }
+
         s.visit_children_with(self);
 
         self.scope.return_values.in_conditional = old_in_conditional;
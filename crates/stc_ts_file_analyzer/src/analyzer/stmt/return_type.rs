@@ -1,7 +1,10 @@
 use std::{borrow::Cow, mem::take, ops::AddAssign};
 
 use rnode::{Fold, FoldWith, Visit, VisitWith};
-use stc_ts_ast_rnode::{RBreakStmt, RIdent, RReturnStmt, RStmt, RStr, RThrowStmt, RTsEntityName, RTsLit, RYieldExpr};
+use stc_ts_ast_rnode::{
+    RArrowExpr, RBreakStmt, RConstructor, RFunction, RGetterProp, RIdent, RReturnStmt, RSetterProp, RStmt, RStr, RThrowStmt, RTsEntityName,
+    RTsLit, RYieldExpr,
+};
 use stc_ts_errors::{DebugExt, Error};
 use stc_ts_simple_ast_validations::yield_check::YieldValueUsageFinder;
 use stc_ts_types::{
@@ -25,6 +28,7 @@ use crate::{
         Analyzer, Ctx,
     },
     ty::{Array, Type, TypeExt},
+    util::EndsWithRet,
     validator,
     validator::ValidateWith,
     VResult,
@@ -76,6 +80,10 @@ impl Analyzer<'_, '_> {
 
         // let mut old_ret_tys = self.scope.return_types.take();
 
+        // Set by calls to functions returning `never`, for example.
+        let mut ends_with_unreachable = false;
+        let mut returns_void_or_any = true;
+
         let mut ret_ty = (|| -> VResult<_> {
             let mut values: ReturnValues = {
                 let ctx = Ctx {
@@ -86,6 +94,8 @@ impl Analyzer<'_, '_> {
                 self.with_ctx(ctx).with(|analyzer: &mut Analyzer| {
                     analyzer.validate_stmts_and_collect(&stmts.iter().collect::<Vec<_>>());
 
+                    ends_with_unreachable = analyzer.ctx.in_unreachable;
+
                     take(&mut analyzer.scope.return_values)
                 })
             };
@@ -158,6 +168,10 @@ impl Analyzer<'_, '_> {
                 actual.push(ty);
             }
 
+            // `return;` does not return a value.
+            let mut value_tys = actual.iter().filter(|ty| !ty.is_kwd(TsKeywordTypeKind::TsVoidKeyword)).peekable();
+            returns_void_or_any = value_tys.peek().is_none() || value_tys.any(|ty| ty.is_any() || ty.is_unknown() || ty.contains_void());
+
             if is_generator {
                 let mut types = Vec::with_capacity(values.yield_types.len());
 
@@ -296,8 +310,114 @@ impl Analyzer<'_, '_> {
             }
         }
 
+        if self.rule().no_implicit_returns && !is_generator {
+            self.report_implicit_returns(span, is_async, stmts, ends_with_unreachable, returns_void_or_any);
+        }
+
         Ok(ret_ty)
     }
+
+    /// Reports `Not all code paths return a value.` for `noImplicitReturns`.
+    ///
+    /// `returns_void_or_any` is about the inferred return type, and it's used
+    /// only if the return type is not declared.
+    fn report_implicit_returns(
+        &mut self,
+        span: Span,
+        is_async: bool,
+        stmts: &Vec<RStmt>,
+        ends_with_unreachable: bool,
+        returns_void_or_any: bool,
+    ) {
+        let declared = match self.scope.declared_return_type().cloned() {
+            Some(declared) if is_async => Some(
+                self.get_awaited_type(span, Cow::Owned(declared))
+                    .map(Cow::into_owned)
+                    .report(&mut self.storage)
+                    .unwrap_or_else(|| Type::any(span, Default::default())),
+            ),
+            declared => declared,
+        };
+
+        if let Some(declared) = &declared {
+            if declared.is_any() || declared.contains_void() {
+                return;
+            }
+        } else if returns_void_or_any {
+            return;
+        }
+
+        let mut v = ReturnFinder::default();
+        stmts.visit_with(&mut v);
+
+        if !declared.as_ref().map_or(false, |ty| ty.is_unknown()) {
+            for span in v.returns_without_value {
+                self.storage.report(Error::NotAllCodePathsReturnValue { span });
+            }
+        }
+
+        if ends_with_unreachable || !v.found || stmts.ends_with_ret() || self.is_switch_case_body_unconditional_termination(stmts) {
+            return;
+        }
+
+        match &declared {
+            Some(declared) => {
+                if declared.is_kwd(TsKeywordTypeKind::TsNeverKeyword) {
+                    return;
+                }
+
+                // tsc reports TS2366 instead.
+                if self.rule().strict_null_checks
+                    && self
+                        .assign(span, &mut Default::default(), declared, &Type::undefined(span, Default::default()))
+                        .is_err()
+                {
+                    return;
+                }
+
+                self.storage.report(Error::NotAllCodePathsReturnValue { span: declared.span() });
+            }
+            None => self.storage.report(Error::NotAllCodePathsReturnValue { span }),
+        }
+    }
+}
+
+/// Finds `return` statements of a function, without visiting nested
+/// functions.
+#[derive(Default)]
+struct ReturnFinder {
+    found: bool,
+    returns_without_value: Vec<Span>,
+}
+
+impl Visit<RReturnStmt> for ReturnFinder {
+    fn visit(&mut self, s: &RReturnStmt) {
+        self.found = true;
+
+        if s.arg.is_none() {
+            self.returns_without_value.push(s.span);
+        }
+    }
+}
+
+impl Visit<RFunction> for ReturnFinder {
+    fn visit(&mut self, _: &RFunction) {}
+}
+
+impl Visit<RArrowExpr> for ReturnFinder {
+    fn visit(&mut self, _: &RArrowExpr) {}
+}
+
+impl Visit<RConstructor> for ReturnFinder {
+    fn visit(&mut self, _: &RConstructor) {}
+}
+
+impl Visit<RGetterProp> for ReturnFinder {
+    fn visit(&mut self, _: &RGetterProp) {}
+}
+
+impl Visit<RSetterProp> for ReturnFinder {
+    fn visit(&mut self, _: &RSetterProp) {}
 }
 
 #[validator]
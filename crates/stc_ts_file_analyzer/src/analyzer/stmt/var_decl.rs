@@ -38,6 +38,7 @@ use crate::{
 impl Analyzer<'_, '_> {
     fn validate(&mut self, var: &RVarDecl) {
         self.record(&*var);
+        self.declare_vars_for_unused_check(var, false);
 
         let ctx = Ctx {
             pat_mode: PatMode::Decl,
@@ -1,11 +1,14 @@
 use std::borrow::Cow;
 
 use stc_ts_errors::{debug::dump_type_as_string, DebugExt};
-use stc_ts_types::{ClassDef, ClassMember, IndexSignature, Type};
-use stc_utils::ext::ValueExt;
+use stc_ts_types::{ClassDef, ClassMember, IdCtx, IndexSignature, Type};
+use stc_utils::ext::{TypeVecExt, ValueExt};
 use swc_common::Span;
 
-use crate::{analyzer::Analyzer, VResult};
+use crate::{
+    analyzer::{expr::TypeOfMode, Analyzer},
+    VResult,
+};
 
 impl Analyzer<'_, '_> {
     /// Get [IndexSignature] from `ty`, if there's one.
@@ -37,4 +40,18 @@ impl Analyzer<'_, '_> {
 
         Ok(None)
     }
+
+    /// Adds `undefined` to the type of an element read using an index
+    /// signature, if `noUncheckedIndexedAccess` is enabled.
+    ///
+    /// Writes and indexed access types (like `T[string]`) are not affected.
+    pub(crate) fn add_undefined_for_unchecked_index(&self, span: Span, ty: Type, type_mode: TypeOfMode, id_ctx: IdCtx) -> Type {
+        if !self.rule().no_unchecked_indexed_access || type_mode != TypeOfMode::RValue || id_ctx != IdCtx::Var || ty.is_any() {
+            return ty;
+        }
+
+        let mut types = vec![ty, Type::undefined(span, Default::default())];
+        types.dedup_type();
+        Type::union(types)
+    }
 }
@@ -6,16 +6,18 @@ use stc_ts_errors::Error;
 use stc_ts_storage::Storage;
 use stc_ts_type_ops::{is_str_lit_or_union, Fix};
 use stc_ts_types::{
-    Class, ClassMetadata, Enum, EnumVariant, EnumVariantMetadata, Id, IndexedAccessType, Intersection, QueryExpr, QueryType, Ref,
+    Class, ClassMetadata, Enum, EnumVariant, EnumVariantMetadata, Id, IndexedAccessType, Intersection, ModuleId, QueryExpr, QueryType, Ref,
     RefMetadata, Tuple, TypeElement, Union,
 };
-use stc_utils::cache::ALLOW_DEEP_CLONE;
+use stc_utils::cache::{Freeze, ALLOW_DEEP_CLONE};
+use swc_atoms::JsWord;
 use swc_common::{Span, Spanned, SyntaxContext};
 use swc_ecma_ast::TsKeywordTypeKind;
 use ty::TypeExt;
 
 use crate::{
     analyzer::{generic::is_literals, scope::ExpandOpts, Analyzer, Ctx},
+    index::{Export, ImportedName},
     ty,
     ty::Type,
     VResult,
@@ -35,6 +37,61 @@ impl Analyzer<'_, '_> {
         }
     }
 
+    /// Records the type of a node for IDE features.
+    pub(crate) fn record_type(&mut self, span: Span, ty: &Type) {
+        if self.is_builtin || span.is_dummy() {
+            return;
+        }
+
+        if let Some(index) = &mut self.index {
+            index.record_type(span, ALLOW_DEEP_CLONE.set(&(), || ty.clone()).freezed());
+        }
+    }
+
+    /// Records the contextual type of an object literal for completion.
+    pub(crate) fn record_contextual_type(&mut self, span: Span, ty: &Type) {
+        if self.is_builtin || span.is_dummy() {
+            return;
+        }
+
+        if let Some(index) = &mut self.index {
+            index.record_contextual_type(span, ALLOW_DEEP_CLONE.set(&(), || ty.clone()).freezed());
+        }
+    }
+
+    /// Records a binding created by an import, for IDE features.
+    pub(crate) fn record_import(&mut self, id: Id, module: ModuleId, name: ImportedName) {
+        if self.is_builtin {
+            return;
+        }
+
+        if let Some(index) = &mut self.index {
+            index.record_import(id, module, name);
+        }
+    }
+
+    /// Records an export of the current module, for IDE features.
+    pub(crate) fn record_export(&mut self, name: JsWord, export: Export) {
+        if self.is_builtin {
+            return;
+        }
+
+        if let Some(index) = &mut self.index {
+            index.record_export(name, export);
+        }
+    }
+
+    /// Records `export * from 'foo'`, for IDE features.
+    pub(crate) fn record_star_export(&mut self, module: ModuleId) {
+        if self.is_builtin {
+            return;
+        }
+
+        if let Some(index) = &mut self.index {
+            index.record_star_export(module);
+        }
+    }
+
     /// `span` and `callee` is used only for error reporting.
     #[cfg_attr(debug_assertions, tracing::instrument(skip_all))]
     fn make_instance_from_type_elements(&mut self, span: Span, callee: &Type, elements: &[TypeElement]) -> VResult<Type> {
@@ -274,28 +274,41 @@ impl BuiltInGen for BuiltIn {
     }
 }
 
+static STABLE_ENV: Lazy<StableEnv> = Lazy::new(Default::default);
+
 pub trait EnvFactory {
     fn new(env: StableEnv, rule: Rule, target: EsVersion, module: ModuleConfig, builtin: Arc<BuiltIn>) -> Env;
     fn simple(rule: Rule, target: EsVersion, module: ModuleConfig, libs: &[Lib]) -> Env {
-        static STABLE_ENV: Lazy<StableEnv> = Lazy::new(Default::default);
-        static CACHE: Lazy<DashMap<Vec<Lib>, Arc<OnceCell<Arc<BuiltIn>>>, ahash::RandomState>> = Lazy::new(Default::default);
-
-        // TODO(kdy1): Include `env` in cache
         let mut libs = libs.to_vec();
         libs.sort();
         libs.dedup();
 
-        let cell = CACHE.entry(libs.clone()).or_default().clone();
+        let builtin = Self::load_builtin(&libs);
 
-        let builtin = swc_common::GLOBALS.set(STABLE_ENV.swc_globals(), || {
+        Self::new(STABLE_ENV.clone(), rule, target, module, builtin).with_libs(libs)
+    }
+
+    /// Loads builtin types of `libs`, which should be sorted and deduplicated.
+    ///
+    /// The result is cached, and an empty [BuiltIn] is returned if `libs` is
+    /// empty.
+    fn load_builtin(libs: &[Lib]) -> Arc<BuiltIn> {
+        static CACHE: Lazy<DashMap<Vec<Lib>, Arc<OnceCell<Arc<BuiltIn>>>, ahash::RandomState>> = Lazy::new(Default::default);
+
+        if libs.is_empty() {
+            return Default::default();
+        }
+
+        // TODO(kdy1): Include `env` in cache
+        let cell = CACHE.entry(libs.to_vec()).or_default().clone();
+
+        swc_common::GLOBALS.set(STABLE_ENV.swc_globals(), || {
             let builtin = cell.get_or_init(|| {
-                let builtin = BuiltIn::from_ts_libs(&STABLE_ENV, &libs);
+                let builtin = BuiltIn::from_ts_libs(&STABLE_ENV, libs);
                 Arc::new(builtin)
             });
             (*builtin).clone()
-        });
-
-        Self::new(STABLE_ENV.clone(), rule, target, module, builtin)
+        })
     }
 }
 
@@ -0,0 +1,123 @@
+//! Information about a module recorded while analyzing it, for IDE features.
+//!
+//! Recording is disabled by default because it requires cloning types, and it
+//! can be enabled by setting [crate::analyzer::Analyzer::index].
+
+use fxhash::FxHashMap;
+use stc_ts_types::{Id, ModuleId};
+use swc_atoms::JsWord;
+use swc_common::{BytePos, Span};
+
+use crate::ty::Type;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportedName {
+    Named(JsWord),
+    /// `import * as foo` or `import foo = require()`
+    Namespace,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Export {
+    Local(Id),
+    /// `export { foo } from './bar'`
+    Reexport {
+        module: ModuleId,
+        name: JsWord,
+    },
+}
+
+#[derive(Debug, Default)]
+pub struct ModuleIndex {
+    /// Types of expressions and declared variables, in the order of
+    /// validation.
+    types: Vec<(Span, Type)>,
+
+    /// Expanded contextual types of object literals.
+    contextual_types: Vec<(Span, Type)>,
+
+    /// Bindings created by imports, with the modules resolved by the
+    /// analyzer.
+    imports: FxHashMap<Id, (ModuleId, ImportedName)>,
+
+    exports: FxHashMap<JsWord, Export>,
+
+    /// Modules re-exported by `export * from 'foo'`.
+    star_exports: Vec<ModuleId>,
+}
+
+impl ModuleIndex {
+    pub(crate) fn record_import(&mut self, id: Id, module: ModuleId, name: ImportedName) {
+        self.imports.insert(id, (module, name));
+    }
+
+    /// A class is exported both as a type and as a variable, and the first
+    /// one is used.
+    pub(crate) fn record_export(&mut self, name: JsWord, export: Export) {
+        self.exports.entry(name).or_insert(export);
+    }
+
+    pub(crate) fn record_star_export(&mut self, module: ModuleId) {
+        if !self.star_exports.contains(&module) {
+            self.star_exports.push(module);
+        }
+    }
+
+    pub fn import_of(&self, id: &Id) -> Option<&(ModuleId, ImportedName)> {
+        self.imports.get(id)
+    }
+
+    pub fn export_of(&self, name: &JsWord) -> Option<&Export> {
+        self.exports.get(name)
+    }
+
+    pub fn star_exports(&self) -> &[ModuleId] {
+        &self.star_exports
+    }
+
+    /// `ty` should be cheap to clone.
+    pub(crate) fn record_type(&mut self, span: Span, ty: Type) {
+        self.types.push((span, ty));
+    }
+
+    /// `ty` should be cheap to clone.
+    pub(crate) fn record_contextual_type(&mut self, span: Span, ty: Type) {
+        self.contextual_types.push((span, ty));
+    }
+
+    /// Returns the type of the innermost node containing `pos`.
+    ///
+    /// If a node is validated multiple times, the last result is used.
+    pub fn type_at(&self, pos: BytePos) -> Option<(Span, &Type)> {
+        self.types
+            .iter()
+            .rev()
+            .filter(|(span, _)| span.lo <= pos && pos <= span.hi)
+            .min_by_key(|(span, _)| span.hi - span.lo)
+            .map(|(span, ty)| (*span, ty))
+    }
+
+    /// Returns the type of the innermost node which ends at `pos`.
+    ///
+    /// This is used to find the object of a member expression which is being
+    /// typed.
+    pub fn type_ending_at(&self, pos: BytePos) -> Option<(Span, &Type)> {
+        self.types
+            .iter()
+            .rev()
+            .filter(|(span, _)| span.hi == pos)
+            .min_by_key(|(span, _)| span.hi - span.lo)
+            .map(|(span, ty)| (*span, ty))
+    }
+
+    /// Returns the innermost object literal containing `pos`, with its
+    /// contextual type.
+    pub fn contextual_type_at(&self, pos: BytePos) -> Option<(Span, &Type)> {
+        self.contextual_types
+            .iter()
+            .rev()
+            .filter(|(span, _)| span.lo < pos && pos < span.hi)
+            .min_by_key(|(span, _)| span.hi - span.lo)
+            .map(|(span, ty)| (*span, ty))
+    }
+}
@@ -20,11 +20,13 @@ use stc_ts_env::StableEnv;
 use stc_ts_errors::Error;
 use stc_ts_file_analyzer_macros::validator;
 pub use stc_ts_types::{Id, ModuleTypeData};
+use stc_ts_utils::imports::ResolutionMode;
 use swc_atoms::JsWord;
 use swc_common::Span;
 
 pub mod analyzer;
 pub mod env;
+pub mod index;
 pub mod loader;
 #[cfg(test)]
 mod tests;
@@ -40,6 +42,11 @@ pub type VResult<T> = Result<T, Error>;
 pub struct DepInfo {
     pub span: Span,
     pub src: JsWord,
+    /// `true` for `/// <reference types="..." />`, which is not resolved like
+    /// an import.
+    pub is_type_reference: bool,
+    /// `resolution-mode` of a type reference.
+    pub resolution_mode: Option<ResolutionMode>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
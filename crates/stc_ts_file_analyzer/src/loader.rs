@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use auto_impl::auto_impl;
 use stc_ts_types::{ModuleId, ModuleTypeData, Type};
+use stc_ts_utils::imports::ResolutionMode;
 use swc_atoms::JsWord;
 use swc_common::FileName;
 
@@ -22,6 +23,9 @@ pub struct ModuleInfo {
 pub trait Load: 'static + Send + Sync {
     fn module_id(&self, base: &Arc<FileName>, src: &JsWord) -> Option<ModuleId>;
 
+    /// Returns the module referenced by `/// <reference types="..." />`.
+    fn module_id_of_type_reference(&self, base: &Arc<FileName>, name: &JsWord, mode: Option<ResolutionMode>) -> Option<ModuleId>;
+
     /// Note: This method called within a thread
     fn is_in_same_circular_group(&self, base: ModuleId, dep: ModuleId) -> bool;
 
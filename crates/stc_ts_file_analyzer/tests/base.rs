@@ -353,6 +353,8 @@ fn run_test(file_name: PathBuf, for_error: bool) -> Option<NormalizedOutput> {
                 no_strict_generic_checks: false,
                 no_unused_locals: false,
                 no_unused_parameters: false,
+                no_unchecked_indexed_access: false,
+                exact_optional_property_types: false,
                 strict_function_types: false,
                 strict_null_checks: false,
                 suppress_excess_property_errors: false,
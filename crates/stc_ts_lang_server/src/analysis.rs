@@ -0,0 +1,363 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use fxhash::{FxHashMap, FxHashSet};
+use parking_lot::{Mutex, RwLock};
+use stc_ts_config::ParsedConfig;
+use stc_ts_errors::Error;
+use stc_ts_file_analyzer::index::ModuleIndex;
+use stc_ts_type_checker::Checker;
+use swc_common::{
+    errors::{EmitterWriter, Handler},
+    BytePos, FileName, FilePathMapping, SourceMap, Span, Spanned,
+};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Location, NumberOrString, Position, Url};
+
+use crate::{
+    convert::{file_path, to_byte_pos, to_range},
+    document::{Document, OverlayFileLoader},
+};
+
+/// Result of checking all open documents at once.
+///
+/// Documents are grouped by the nearest `tsconfig.json`, and each group is
+/// checked by a [Checker] with the options of the group.
+pub(crate) struct Analysis {
+    pub cm: Arc<SourceMap>,
+
+    pub documents: Arc<FxHashMap<PathBuf, Document>>,
+
+    pub projects: Vec<Project>,
+
+    /// Errors grouped by file.
+    pub diagnostics: FxHashMap<PathBuf, Vec<Diagnostic>>,
+
+    /// Indexes of open documents at the time of the analysis.
+    ///
+    /// These are copied because the checker drops the index of a module when
+    /// it's analyzed again.
+    pub indexes: FxHashMap<PathBuf, Arc<ModuleIndex>>,
+}
+
+pub(crate) struct Project {
+    pub checker: Arc<Mutex<Checker>>,
+
+    /// Open documents belonging to the project.
+    pub files: Vec<PathBuf>,
+
+    /// Files of the project specified by `tsconfig.json`.
+    pub config_files: Arc<Vec<Arc<FileName>>>,
+}
+
+/// Checkers of all projects in the workspace.
+///
+/// Checkers are kept across edits, so only changed files and their
+/// dependents are analyzed again.
+pub(crate) struct Workspace {
+    cm: Arc<SourceMap>,
+
+    handler: Arc<Handler>,
+
+    /// Documents visible to the module loader.
+    overlay: Arc<RwLock<Arc<FxHashMap<PathBuf, Document>>>>,
+
+    /// Documents checked last time.
+    documents: Arc<FxHashMap<PathBuf, Document>>,
+
+    /// Projects keyed by the path to `tsconfig.json`.
+    projects: FxHashMap<PathBuf, ProjectState>,
+}
+
+struct ProjectState {
+    checker: Arc<Mutex<Checker>>,
+
+    config_files: Arc<Vec<Arc<FileName>>>,
+
+    /// Open documents belonging to the project.
+    files: Vec<PathBuf>,
+
+    /// Errors grouped by file, including files which are not opened.
+    diagnostics: FxHashMap<PathBuf, Vec<Diagnostic>>,
+}
+
+impl Default for Workspace {
+    fn default() -> Self {
+        let overlay: Arc<RwLock<Arc<FxHashMap<PathBuf, Document>>>> = Default::default();
+        let cm = Arc::new(SourceMap::with_file_loader(
+            Box::new(OverlayFileLoader { files: overlay.clone() }),
+            FilePathMapping::empty(),
+        ));
+        let handler = Arc::new(Handler::with_emitter(
+            true,
+            false,
+            Box::new(EmitterWriter::new(Box::new(io::sink()), Some(cm.clone()), false, false)),
+        ));
+
+        Workspace {
+            cm,
+            handler,
+            overlay,
+            documents: Default::default(),
+            projects: Default::default(),
+        }
+    }
+}
+
+impl Workspace {
+    /// Checks `documents`, the current content of open documents.
+    ///
+    /// Files changed since the last call are invalidated, and newly opened
+    /// files are checked as entries.
+    pub fn check(&mut self, documents: Arc<FxHashMap<PathBuf, Document>>) -> Analysis {
+        *self.overlay.write() = documents.clone();
+
+        // Closed documents are read from the disk again.
+        let changed = documents
+            .keys()
+            .chain(self.documents.keys())
+            .filter(|path| match (self.documents.get(*path), documents.get(*path)) {
+                (Some(prev), Some(cur)) => !Arc::ptr_eq(&prev.text, &cur.text),
+                _ => true,
+            })
+            .cloned()
+            .collect::<FxHashSet<_>>();
+
+        let mut groups: FxHashMap<PathBuf, Vec<PathBuf>> = Default::default();
+        for path in documents.keys() {
+            let config_path = find_config(path).unwrap_or_else(|| path.with_file_name("tsconfig.json"));
+            groups.entry(config_path).or_default().push(path.clone());
+        }
+
+        self.projects.retain(|config_path, _| groups.contains_key(config_path));
+
+        for (config_path, files) in groups {
+            let (cm, handler) = (&self.cm, &self.handler);
+            let project = self
+                .projects
+                .entry(config_path)
+                .or_insert_with_key(|config_path| ProjectState::new(cm, handler, config_path));
+
+            project.check(cm, &changed, files);
+        }
+
+        self.documents = documents.clone();
+
+        let mut projects = vec![];
+        let mut diagnostics: FxHashMap<PathBuf, Vec<Diagnostic>> = Default::default();
+        let mut indexes = FxHashMap::default();
+
+        for project in self.projects.values() {
+            let checker = project.checker.lock();
+
+            for path in &project.files {
+                let id = checker.id(&Arc::new(FileName::Real(path.clone())));
+                if let Some(index) = checker.index(id) {
+                    indexes.insert(path.clone(), index);
+                }
+            }
+
+            for (path, diags) in &project.diagnostics {
+                diagnostics.entry(path.clone()).or_default().extend(diags.iter().cloned());
+            }
+
+            projects.push(Project {
+                checker: project.checker.clone(),
+                files: project.files.clone(),
+                config_files: project.config_files.clone(),
+            });
+        }
+
+        Analysis {
+            cm: self.cm.clone(),
+            documents,
+            projects,
+            diagnostics,
+            indexes,
+        }
+    }
+}
+
+impl ProjectState {
+    fn new(cm: &Arc<SourceMap>, handler: &Arc<Handler>, config_path: &Path) -> Self {
+        let config = load_config(config_path);
+
+        let mut checker = Checker::new(
+            cm.clone(),
+            handler.clone(),
+            config.env(),
+            config.parser_config(),
+            None,
+            Arc::new(config.resolver()),
+        );
+        checker.enable_index();
+        checker.load_typings(
+            config.dir(),
+            config.compiler_options.type_roots.as_deref(),
+            config.compiler_options.types.as_deref(),
+        );
+
+        ProjectState {
+            checker: Arc::new(Mutex::new(checker)),
+            config_files: Arc::new(config.files.iter().map(|file| Arc::new(FileName::Real(file.clone()))).collect()),
+            files: Default::default(),
+            diagnostics: Default::default(),
+        }
+    }
+
+    /// Invalidates `changed` files loaded by the checker, and checks `files`
+    /// which are not checked yet.
+    fn check(&mut self, cm: &SourceMap, changed: &FxHashSet<PathBuf>, files: Vec<PathBuf>) {
+        let mut checker = self.checker.lock();
+
+        let loaded = checker.files();
+        let mut analyzed = vec![];
+        for path in changed {
+            let filename = Arc::new(FileName::Real(path.clone()));
+            if loaded.contains(&filename) {
+                analyzed.extend(checker.invalidate(&filename));
+            }
+        }
+
+        let entries = files
+            .iter()
+            .filter(|file| !self.files.contains(file))
+            .map(|file| Arc::new(FileName::Real(file.clone())))
+            .collect::<Vec<_>>();
+        if !entries.is_empty() {
+            checker.check_all(&entries);
+        }
+
+        // Previous errors of modules analyzed again are outdated.
+        for id in analyzed {
+            if let FileName::Real(path) = &*checker.path(id) {
+                self.diagnostics.remove(path);
+            }
+        }
+
+        let errors = Error::flatten(checker.take_errors());
+        for (path, diags) in checker.run(|| to_diagnostics(cm, errors)) {
+            self.diagnostics.entry(path).or_default().extend(diags);
+        }
+
+        self.files = files;
+    }
+}
+
+impl Analysis {
+    /// Returns the project which contains the open document at `path`.
+    pub fn project_of(&self, path: &Path) -> Option<&Project> {
+        self.projects.iter().find(|p| p.files.iter().any(|f| f == path))
+    }
+
+    /// Converts `pos` in the file at `path` to [BytePos].
+    ///
+    /// A file can be loaded multiple times if it's used by multiple projects,
+    /// so this returns positions for all copies. Copies loaded before the
+    /// last edit of an open document are skipped, because the source map
+    /// keeps them after the file is invalidated.
+    pub fn byte_positions(&self, path: &Path, pos: Position) -> Vec<BytePos> {
+        let files = self.cm.files().clone();
+        let text = self.documents.get(path).map(|doc| &doc.text);
+
+        files
+            .iter()
+            .filter(|file| file_path(&file.name).map(|p| p == path).unwrap_or(false))
+            .filter(|file| text.map(|text| file.src.as_str() == text.as_str()).unwrap_or(true))
+            .filter_map(|file| to_byte_pos(file, pos))
+            .collect()
+    }
+
+    /// Converts `offset` in `text`, the current content of the file at
+    /// `path`, to positions in the analyzed copies of the file.
+    ///
+    /// Only copies whose content before `offset` is not changed since the
+    /// analysis are used, so that results of the analysis are still valid for
+    /// the code before `offset`.
+    pub fn unchanged_positions(&self, path: &Path, text: &str, offset: usize) -> Vec<BytePos> {
+        let files = self.cm.files().clone();
+
+        files
+            .iter()
+            .filter(|file| file_path(&file.name).map(|p| p == path).unwrap_or(false))
+            .filter(|file| file.src.get(..offset).is_some() && file.src.get(..offset) == text.get(..offset))
+            .map(|file| file.start_pos + BytePos(offset as u32))
+            .collect()
+    }
+
+    /// Returns the index of the open document at `path`, which does not exist
+    /// if the file has a syntax error.
+    pub fn index(&self, path: &Path) -> Option<Arc<ModuleIndex>> {
+        self.indexes.get(path).cloned()
+    }
+
+    pub fn to_location(&self, span: Span) -> Option<Location> {
+        let file = self.cm.lookup_char_pos(span.lo).file;
+        let uri = Url::from_file_path(file_path(&file.name)?).ok()?;
+
+        Some(Location {
+            uri,
+            range: to_range(&self.cm, span),
+        })
+    }
+
+    /// Diagnostics of open documents.
+    pub fn diagnostics(&self) -> impl '_ + Iterator<Item = (&Document, Vec<Diagnostic>)> {
+        self.documents
+            .iter()
+            .map(move |(path, doc)| (doc, self.diagnostics.get(path).cloned().unwrap_or_default()))
+    }
+}
+
+fn to_diagnostics(cm: &SourceMap, errors: Vec<Error>) -> FxHashMap<PathBuf, Vec<Diagnostic>> {
+    let mut map: FxHashMap<PathBuf, Vec<Diagnostic>> = Default::default();
+
+    for err in errors {
+        let span = err.span();
+        // We can't know where to report it.
+        if span.is_dummy() {
+            continue;
+        }
+
+        let file = cm.lookup_char_pos(span.lo).file;
+        let path = match file_path(&file.name) {
+            Some(path) => path.clone(),
+            None => continue,
+        };
+
+        map.entry(path).or_default().push(Diagnostic {
+            range: to_range(cm, span),
+            severity: Some(DiagnosticSeverity::ERROR),
+            code: Some(NumberOrString::Number(Error::normalize_error_code(err.code()) as _)),
+            source: Some("stc".into()),
+            message: err.msg_with_causes(),
+            ..Default::default()
+        });
+    }
+
+    map
+}
+
+/// Finds the nearest `tsconfig.json`.
+fn find_config(file: &Path) -> Option<PathBuf> {
+    file.ancestors().skip(1).map(|dir| dir.join("tsconfig.json")).find(|p| p.is_file())
+}
+
+/// Uses the default options if `tsconfig.json` is not usable.
+fn load_config(path: &Path) -> ParsedConfig {
+    if path.is_file() {
+        match stc_ts_config::load(path) {
+            Ok(config) => return config,
+            Err(err) => log::error!("failed to load `{}`: {:?}", path.display(), err),
+        }
+    }
+
+    ParsedConfig {
+        path: path.to_path_buf(),
+        files: Default::default(),
+        inputs: Default::default(),
+        compiler_options: Default::default(),
+    }
+}
@@ -0,0 +1,134 @@
+//! Completion of member accesses and object literals.
+//!
+//! Completion is requested while the user is typing, so the current text
+//! usually cannot be parsed. Instead of waiting for a new analysis, we use the
+//! last analysis of the file as long as the code before the cursor is not
+//! changed since then.
+
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::Path,
+    sync::Arc,
+};
+
+use stc_ts_errors::debug::type_to_string;
+use stc_ts_file_analyzer::analyzer::PropertyInfo;
+use stc_ts_types::{ModuleId, Type};
+use swc_atoms::JsWord;
+use swc_common::{FileName, Span};
+use tower_lsp::lsp_types::{CompletionItem, CompletionItemKind, Position};
+
+use crate::{
+    analysis::{Analysis, Project},
+    convert::to_offset,
+};
+
+pub(crate) fn completion(analysis: &Analysis, path: &Path, text: &str, pos: Position) -> Vec<CompletionItem> {
+    let project = match analysis.project_of(path) {
+        Some(project) => project,
+        None => return vec![],
+    };
+    let offset = match to_offset(text, pos) {
+        Some(offset) => offset,
+        None => return vec![],
+    };
+
+    // Start of the identifier being typed.
+    let word_start = text[..offset].trim_end_matches(is_ident_char).len();
+    let before = text[..word_start].trim_end();
+
+    if let Some(obj) = before.strip_suffix('.') {
+        let obj_end = obj.strip_suffix('?').unwrap_or(obj).trim_end().len();
+        return members(analysis, project, path, text, obj_end);
+    }
+
+    if before.ends_with('{') || before.ends_with(',') {
+        return object_lit_props(analysis, project, path, text, before.len());
+    }
+
+    vec![]
+}
+
+/// Lists properties of the expression which ends at `obj_end`.
+fn members(analysis: &Analysis, project: &Project, path: &Path, text: &str, obj_end: usize) -> Vec<CompletionItem> {
+    let module = project.checker.lock().id(&Arc::new(FileName::Real(path.to_path_buf())));
+    let index = match analysis.index(path) {
+        Some(index) => index,
+        None => return vec![],
+    };
+
+    let (span, ty) = match analysis
+        .unchanged_positions(path, text, obj_end)
+        .into_iter()
+        .find_map(|pos| index.type_ending_at(pos))
+    {
+        Some(v) => v,
+        None => return vec![],
+    };
+
+    to_items(project, properties_of(project, module, span, ty), &[])
+}
+
+/// Lists properties of the contextual type of the object literal containing
+/// `pos`, which are not specified yet.
+fn object_lit_props(analysis: &Analysis, project: &Project, path: &Path, text: &str, pos: usize) -> Vec<CompletionItem> {
+    let module = project.checker.lock().id(&Arc::new(FileName::Real(path.to_path_buf())));
+    let index = match analysis.index(path) {
+        Some(index) => index,
+        None => return vec![],
+    };
+
+    let (span, ty) = match analysis
+        .unchanged_positions(path, text, pos)
+        .into_iter()
+        .find_map(|pos| index.contextual_type_at(pos))
+    {
+        Some(v) => v,
+        None => return vec![],
+    };
+
+    let specified = match index.type_ending_at(span.hi) {
+        Some((obj_span, obj)) if obj_span == span => properties_of(project, module, span, obj).into_iter().map(|p| p.name).collect(),
+        _ => vec![],
+    };
+
+    to_items(project, properties_of(project, module, span, ty), &specified)
+}
+
+/// The analyzer panics for some types which are not supported yet, and we
+/// should not crash the server in the case.
+fn properties_of(project: &Project, module: ModuleId, span: Span, ty: &Type) -> Vec<PropertyInfo> {
+    match catch_unwind(AssertUnwindSafe(|| project.checker.lock().properties_of(module, span, ty))) {
+        Ok(props) => props,
+        Err(..) => {
+            log::error!("stc panicked while listing properties");
+            vec![]
+        }
+    }
+}
+
+fn to_items(project: &Project, props: Vec<PropertyInfo>, excluded: &[JsWord]) -> Vec<CompletionItem> {
+    project.checker.lock().run(|| {
+        props
+            .into_iter()
+            .filter(|p| !excluded.contains(&p.name))
+            .map(|p| {
+                let kind = match p.ty.as_ref().map(|ty| ty.normalize()) {
+                    Some(Type::Function(..)) => CompletionItemKind::METHOD,
+                    _ => CompletionItemKind::PROPERTY,
+                };
+
+                CompletionItem {
+                    label: p.name.to_string(),
+                    kind: Some(kind),
+                    detail: p.ty.as_ref().map(type_to_string),
+                    ..Default::default()
+                }
+            })
+            .collect()
+    })
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
@@ -0,0 +1,80 @@
+//! Conversion between positions of swc and the language server protocol.
+//!
+//! The protocol counts columns in UTF-16 code units, while swc uses byte
+//! offsets.
+
+use std::path::PathBuf;
+
+use swc_common::{BytePos, FileName, SourceFile, SourceMap, Span};
+use tower_lsp::lsp_types::{Position, Range};
+
+pub(crate) fn to_position(cm: &SourceMap, pos: BytePos) -> Position {
+    let loc = cm.lookup_char_pos(pos);
+    let line = loc.line - 1;
+
+    let character = loc
+        .file
+        .get_line(line)
+        .map(|text| text.chars().take(loc.col.0).map(char::len_utf16).sum::<usize>())
+        .unwrap_or(loc.col.0);
+
+    Position {
+        line: line as u32,
+        character: character as u32,
+    }
+}
+
+pub(crate) fn to_range(cm: &SourceMap, span: Span) -> Range {
+    Range {
+        start: to_position(cm, span.lo),
+        end: to_position(cm, span.hi),
+    }
+}
+
+/// Returns `None` if `pos` is out of the file.
+pub(crate) fn to_byte_pos(file: &SourceFile, pos: Position) -> Option<BytePos> {
+    let line_start = *file.lines.get(pos.line as usize)?;
+    let text = file.get_line(pos.line as usize)?;
+
+    let mut utf16 = 0;
+    let mut offset = 0;
+    for c in text.chars() {
+        if utf16 >= pos.character as usize {
+            break;
+        }
+        utf16 += c.len_utf16();
+        offset += c.len_utf8();
+    }
+
+    Some(line_start + BytePos(offset as u32))
+}
+
+/// Converts `pos` to a byte offset in `text`, which may differ from the
+/// analyzed content of the file.
+///
+/// Returns `None` if `pos` is out of `text`.
+pub(crate) fn to_offset(text: &str, pos: Position) -> Option<usize> {
+    let mut line_start = 0;
+    for _ in 0..pos.line {
+        line_start += text[line_start..].find('\n')? + 1;
+    }
+
+    let mut utf16 = 0;
+    let mut offset = line_start;
+    for c in text[line_start..].chars() {
+        if utf16 >= pos.character as usize || c == '\n' {
+            break;
+        }
+        utf16 += c.len_utf16();
+        offset += c.len_utf8();
+    }
+
+    Some(offset)
+}
+
+pub(crate) fn file_path(name: &FileName) -> Option<&PathBuf> {
+    match name {
+        FileName::Real(path) => Some(path),
+        _ => None,
+    }
+}
@@ -0,0 +1,81 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use fxhash::FxHashMap;
+use parking_lot::RwLock;
+use swc_common::source_map::{FileLoader, RealFileLoader};
+use tower_lsp::lsp_types::Url;
+
+/// A file opened by the client.
+#[derive(Debug, Clone)]
+pub(crate) struct Document {
+    pub uri: Url,
+    pub version: i32,
+    pub text: Arc<String>,
+}
+
+/// Open documents, keyed by their path.
+#[derive(Debug, Default)]
+pub(crate) struct Documents {
+    files: RwLock<FxHashMap<PathBuf, Document>>,
+}
+
+impl Documents {
+    pub fn open(&self, path: PathBuf, doc: Document) {
+        self.files.write().insert(path, doc);
+    }
+
+    /// Returns `false` if the document is not opened.
+    pub fn update(&self, path: &Path, version: i32, text: String) -> bool {
+        match self.files.write().get_mut(path) {
+            Some(doc) => {
+                doc.version = version;
+                doc.text = Arc::new(text);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn close(&self, path: &Path) -> Option<Document> {
+        self.files.write().remove(path)
+    }
+
+    pub fn get(&self, path: &Path) -> Option<Document> {
+        self.files.read().get(path).cloned()
+    }
+
+    /// Copies the current content, so that analysis does not observe edits
+    /// made while it's running.
+    pub fn snapshot(&self) -> Arc<FxHashMap<PathBuf, Document>> {
+        Arc::new(self.files.read().clone())
+    }
+}
+
+/// Reads open documents from memory, so unsaved changes are visible to the
+/// module loader.
+///
+/// `files` is replaced with a new snapshot before each check.
+pub(crate) struct OverlayFileLoader {
+    pub files: Arc<RwLock<Arc<FxHashMap<PathBuf, Document>>>>,
+}
+
+impl FileLoader for OverlayFileLoader {
+    fn file_exists(&self, path: &Path) -> bool {
+        self.files.read().contains_key(path) || RealFileLoader.file_exists(path)
+    }
+
+    fn abs_path(&self, path: &Path) -> Option<PathBuf> {
+        RealFileLoader.abs_path(path)
+    }
+
+    fn read_file(&self, path: &Path) -> io::Result<String> {
+        match self.files.read().get(path) {
+            Some(doc) => Ok((*doc.text).clone()),
+            None => fs::read_to_string(path),
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use std::path::Path;
+
+use stc_ts_errors::debug::type_to_string;
+use tower_lsp::lsp_types::{Hover, HoverContents, MarkupContent, MarkupKind, Position};
+
+use crate::{analysis::Analysis, convert::to_range};
+
+/// Shows the type computed by the analyzer for the node at `pos`.
+pub(crate) fn hover(analysis: &Analysis, path: &Path, pos: Position) -> Option<Hover> {
+    let project = analysis.project_of(path)?;
+    let index = analysis.index(path)?;
+
+    let (span, ty) = analysis.byte_positions(path, pos).into_iter().find_map(|pos| index.type_at(pos))?;
+    let ty = project.checker.lock().run(|| type_to_string(ty));
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: format!("```typescript\n{}\n```", ty),
+        }),
+        range: Some(to_range(&analysis.cm, span)),
+    })
+}
@@ -1,7 +1,33 @@
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
 use anyhow::Result;
 use clap::Args;
+use fxhash::FxHashMap;
+use parking_lot::{Mutex, RwLock};
 use tower_lsp::{async_trait, jsonrpc, lsp_types::*, Client, LanguageServer, LspService, Server};
 
+use crate::{
+    analysis::{Analysis, Workspace},
+    document::{Document, Documents},
+};
+
+mod analysis;
+mod completion;
+mod convert;
+mod document;
+mod hover;
+mod navigation;
+
+/// Time to wait for more edits before checking.
+const DEBOUNCE: Duration = Duration::from_millis(150);
+
 #[derive(Debug, Args)]
 pub struct LspCommand {}
 
@@ -10,7 +36,10 @@ impl LspCommand {
         let stdin = tokio::io::stdin();
         let stdout = tokio::io::stdout();
 
-        let (service, socket) = LspService::new(|client| StcLangServer { client });
+        let (service, socket) = LspService::new(|client| StcLangServer {
+            client,
+            state: Default::default(),
+        });
         Server::new(stdin, stdout, socket).serve(service).await;
 
         Ok(())
@@ -18,15 +47,108 @@ impl LspCommand {
 }
 
 pub struct StcLangServer {
-    #[allow(unused)]
     client: Client,
+    state: Arc<State>,
+}
+
+#[derive(Default)]
+struct State {
+    documents: Documents,
+
+    /// Incremented on each edit, and used to drop results of outdated
+    /// analysis.
+    generation: AtomicU64,
+
+    /// Checkers kept across edits.
+    workspace: Mutex<Workspace>,
+
+    /// The last finished analysis, used to answer requests.
+    analysis: RwLock<Option<Arc<Analysis>>>,
+
+    /// The last analysis which analyzed each file successfully, used for
+    /// completion because code being typed usually has syntax errors.
+    indexed: RwLock<FxHashMap<PathBuf, Arc<Analysis>>>,
+}
+
+/// Returns `None` for documents which are not files.
+fn to_file_path(uri: &Url) -> Option<PathBuf> {
+    uri.to_file_path().ok()
+}
+
+impl StcLangServer {
+    /// Returns the last analysis, which is used to answer requests about
+    /// `uri`.
+    fn analysis_for(&self, uri: &Url) -> Option<(PathBuf, Arc<Analysis>)> {
+        let path = to_file_path(uri)?;
+        let analysis = self.state.analysis.read().clone()?;
+
+        Some((path, analysis))
+    }
+
+    /// Checks all open documents after [DEBOUNCE], and publishes diagnostics
+    /// if there was no edit in the meantime.
+    fn schedule_check(&self) {
+        let generation = self.state.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let state = self.state.clone();
+        let client = self.client.clone();
+
+        tokio::spawn(async move {
+            tokio::time::sleep(DEBOUNCE).await;
+            if state.generation.load(Ordering::SeqCst) != generation {
+                return;
+            }
+
+            let documents = state.documents.snapshot();
+            let analysis = match tokio::task::spawn_blocking({
+                let state = state.clone();
+                move || state.workspace.lock().check(documents)
+            })
+            .await
+            {
+                Ok(analysis) => analysis,
+                Err(err) => {
+                    client.log_message(MessageType::ERROR, format!("stc panicked: {}", err)).await;
+                    return;
+                }
+            };
+
+            if state.generation.load(Ordering::SeqCst) != generation {
+                return;
+            }
+
+            let analysis = Arc::new(analysis);
+            *state.analysis.write() = Some(analysis.clone());
+            {
+                let mut indexed = state.indexed.write();
+                for path in analysis.documents.keys() {
+                    if analysis.index(path).is_some() {
+                        indexed.insert(path.clone(), analysis.clone());
+                    }
+                }
+            }
+
+            for (doc, diagnostics) in analysis.diagnostics() {
+                client.publish_diagnostics(doc.uri.clone(), diagnostics, Some(doc.version)).await;
+            }
+        });
+    }
 }
 
 #[async_trait]
 impl LanguageServer for StcLangServer {
     async fn initialize(&self, _params: InitializeParams) -> jsonrpc::Result<InitializeResult> {
         Ok(InitializeResult {
-            capabilities: ServerCapabilities { ..Default::default() },
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                definition_provider: Some(OneOf::Left(true)),
+                references_provider: Some(OneOf::Left(true)),
+                completion_provider: Some(CompletionOptions {
+                    trigger_characters: Some(vec![".".into()]),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
             server_info: Some(ServerInfo {
                 name: "stc-ts-lsp".to_string(),
                 version: Some(env!("CARGO_PKG_VERSION").to_string()),
@@ -37,4 +159,110 @@ impl LanguageServer for StcLangServer {
     async fn shutdown(&self) -> jsonrpc::Result<()> {
         Ok(())
     }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let doc = params.text_document;
+        let path = match to_file_path(&doc.uri) {
+            Some(path) => path,
+            None => return,
+        };
+
+        self.state.documents.open(
+            path,
+            Document {
+                uri: doc.uri,
+                version: doc.version,
+                text: Arc::new(doc.text),
+            },
+        );
+        self.schedule_check();
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        let path = match to_file_path(&params.text_document.uri) {
+            Some(path) => path,
+            None => return,
+        };
+        // We use full sync, so the last change contains the whole text.
+        let text = match params.content_changes.into_iter().last() {
+            Some(change) => change.text,
+            None => return,
+        };
+
+        if self.state.documents.update(&path, params.text_document.version, text) {
+            self.schedule_check();
+        }
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let path = match to_file_path(&params.text_document.uri) {
+            Some(path) => path,
+            None => return,
+        };
+
+        if self.state.documents.close(&path).is_some() {
+            self.state.indexed.write().remove(&path);
+            self.client.publish_diagnostics(params.text_document.uri, vec![], None).await;
+            self.schedule_check();
+        }
+    }
+
+    async fn hover(&self, params: HoverParams) -> jsonrpc::Result<Option<Hover>> {
+        let params = params.text_document_position_params;
+        let (path, analysis) = match self.analysis_for(&params.text_document.uri) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        Ok(hover::hover(&analysis, &path, params.position))
+    }
+
+    async fn goto_definition(&self, params: GotoDefinitionParams) -> jsonrpc::Result<Option<GotoDefinitionResponse>> {
+        let params = params.text_document_position_params;
+        let (path, analysis) = match self.analysis_for(&params.text_document.uri) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        let locations = navigation::definition(&analysis, &path, params.position);
+        if locations.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(GotoDefinitionResponse::Array(locations)))
+    }
+
+    async fn references(&self, params: ReferenceParams) -> jsonrpc::Result<Option<Vec<Location>>> {
+        let pos = params.text_document_position;
+        let (path, analysis) = match self.analysis_for(&pos.text_document.uri) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        Ok(Some(navigation::references(
+            &analysis,
+            &path,
+            pos.position,
+            params.context.include_declaration,
+        )))
+    }
+
+    async fn completion(&self, params: CompletionParams) -> jsonrpc::Result<Option<CompletionResponse>> {
+        let pos = params.text_document_position;
+        let path = match to_file_path(&pos.text_document.uri) {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+        let (doc, analysis) = match (self.state.documents.get(&path), self.state.indexed.read().get(&path).cloned()) {
+            (Some(doc), Some(analysis)) => (doc, analysis),
+            _ => return Ok(None),
+        };
+
+        let items = completion::completion(&analysis, &path, &doc.text, pos.position);
+        if items.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(CompletionResponse::Array(items)))
+    }
 }
@@ -0,0 +1,54 @@
+//! Go-to-definition and find-references.
+
+use std::{path::Path, sync::Arc};
+
+use stc_ts_type_checker::symbols::Symbol;
+use swc_common::FileName;
+use tower_lsp::lsp_types::{Location, Position};
+
+use crate::analysis::{Analysis, Project};
+
+fn symbol_at<'a>(analysis: &'a Analysis, path: &Path, pos: Position) -> Option<(&'a Project, Symbol)> {
+    let project = analysis.project_of(path)?;
+    let checker = project.checker.lock();
+    let module = checker.id(&Arc::new(FileName::Real(path.to_path_buf())));
+
+    let symbol = analysis
+        .byte_positions(path, pos)
+        .into_iter()
+        .find_map(|pos| checker.symbol_at(module, pos))?;
+
+    Some((project, symbol))
+}
+
+pub(crate) fn definition(analysis: &Analysis, path: &Path, pos: Position) -> Vec<Location> {
+    let (project, symbol) = match symbol_at(analysis, path, pos) {
+        Some(v) => v,
+        None => return vec![],
+    };
+
+    let definitions = project.checker.lock().definitions(&symbol);
+
+    definitions.into_iter().filter_map(|span| analysis.to_location(span)).collect()
+}
+
+pub(crate) fn references(analysis: &Analysis, path: &Path, pos: Position, include_declaration: bool) -> Vec<Location> {
+    let (project, symbol) = match symbol_at(analysis, path, pos) {
+        Some(v) => v,
+        None => return vec![],
+    };
+
+    let (decls, references) = {
+        let checker = project.checker.lock();
+        // Files which are not opened can refer to the symbol, so all files of the
+        // project should be loaded. Files checked already are not checked again.
+        checker.check_all(&project.config_files);
+        (checker.definitions(&symbol), checker.references(&symbol))
+    };
+
+    references
+        .into_iter()
+        .filter(|span| include_declaration || !decls.contains(span))
+        .filter_map(|span| analysis.to_location(span))
+        .collect()
+}
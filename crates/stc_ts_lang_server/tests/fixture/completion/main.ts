@@ -0,0 +1,9 @@
+interface Point {
+    x: number;
+    y: number;
+    move(dx: number): void;
+}
+
+const p: Point = { x: 1, y: 2, move() {} };
+const q: Point = { x: 1, };
+p.x;
@@ -0,0 +1 @@
+const a: number = 1;
@@ -0,0 +1,2 @@
+import { a } from './dep';
+export const b: number = a;
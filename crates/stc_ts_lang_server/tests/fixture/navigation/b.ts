@@ -0,0 +1,2 @@
+import { foo } from './a';
+export { foo as bar };
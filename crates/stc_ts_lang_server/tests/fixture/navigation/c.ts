@@ -0,0 +1,2 @@
+import * as a from './a';
+export const c = a.foo;
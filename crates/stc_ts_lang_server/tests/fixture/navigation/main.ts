@@ -0,0 +1,2 @@
+import { bar } from './b';
+const x = bar + bar;
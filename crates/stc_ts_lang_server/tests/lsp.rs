@@ -1,9 +1,15 @@
 use std::{
+    fs,
+    io::{BufRead, BufReader, Read, Write},
     path::{Path, PathBuf},
-    process::{Command, Stdio},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    thread,
+    time::{Duration, Instant},
 };
 
 use once_cell::sync::Lazy;
+use serde_json::{json, Value};
 
 /// Builds the example lsp command, and returns to the path to it.
 fn exec_path() -> PathBuf {
@@ -30,3 +36,357 @@ fn exec_path() -> PathBuf {
 fn test_build() {
     assert!(exec_path().exists());
 }
+
+/// How long a request or [Client::wait_for] waits for the server.
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A client speaking JSON-RPC over stdio of the server.
+struct Client {
+    child: Child,
+    stdin: ChildStdin,
+    /// Messages read by a thread, so a server which stops responding fails
+    /// the test instead of hanging it.
+    messages: Receiver<Value>,
+    /// Printed when the server times out.
+    last: Option<Value>,
+    next_id: i64,
+}
+
+impl Client {
+    fn start() -> Self {
+        let mut child = Command::new(exec_path())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("failed to start the language server");
+
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        let (tx, messages) = mpsc::channel();
+        thread::spawn(move || read_messages(stdout, tx));
+
+        let mut client = Client {
+            child,
+            stdin,
+            messages,
+            last: None,
+            next_id: 0,
+        };
+
+        client.request("initialize", json!({ "capabilities": {} }));
+        client.notify("initialized", json!({}));
+
+        client
+    }
+
+    fn send(&mut self, msg: Value) {
+        let body = msg.to_string();
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+        self.stdin.flush().unwrap();
+    }
+
+    /// Panics if no message arrives until `deadline`.
+    fn recv(&mut self, deadline: Instant, waiting_for: &str) -> Value {
+        match self.messages.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(msg) => {
+                self.last = Some(msg.clone());
+                msg
+            }
+            Err(err) => {
+                let reason = match err {
+                    RecvTimeoutError::Timeout => "timed out",
+                    RecvTimeoutError::Disconnected => "the server exited",
+                };
+                let last = self.last.as_ref().map_or_else(|| "nothing".to_string(), Value::to_string);
+
+                panic!("{} while waiting for {}; the last message was {}", reason, waiting_for, last)
+            }
+        }
+    }
+
+    /// Returns the result of the request.
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+
+        let deadline = Instant::now() + TIMEOUT;
+        let waiting_for = format!("the response to `{}`", method);
+        loop {
+            let msg = self.recv(deadline, &waiting_for);
+            if msg["id"] == id && msg.get("method").is_none() {
+                return msg["result"].clone();
+            }
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    /// Waits for a notification, skipping the others.
+    fn wait_for(&mut self, method: &str, pred: impl Fn(&Value) -> bool) -> Value {
+        let deadline = Instant::now() + TIMEOUT;
+        let waiting_for = format!("`{}`", method);
+        loop {
+            let msg = self.recv(deadline, &waiting_for);
+            if msg["method"] == method && pred(&msg["params"]) {
+                return msg["params"].clone();
+            }
+        }
+    }
+}
+
+/// Sends messages from `stdout` to `tx` until the server exits.
+fn read_messages(mut stdout: BufReader<ChildStdout>, tx: Sender<Value>) {
+    loop {
+        let mut len = None;
+        loop {
+            let mut line = String::new();
+            if stdout.read_line(&mut line).unwrap_or(0) == 0 {
+                return;
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(v) = line.strip_prefix("Content-Length: ") {
+                len = Some(v.parse::<usize>().unwrap());
+            }
+        }
+
+        let mut buf = vec![0; len.expect("Content-Length is required")];
+        if stdout.read_exact(&mut buf).is_err() {
+            return;
+        }
+        if tx.send(serde_json::from_slice(&buf).unwrap()).is_err() {
+            return;
+        }
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+    }
+}
+
+fn fixture(name: &str) -> (PathBuf, String) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixture")
+        .join(name)
+        .canonicalize()
+        .unwrap();
+    let uri = format!("file://{}", path.display());
+
+    (path, uri)
+}
+
+/// Opens the fixture and waits for the first analysis.
+fn open(client: &mut Client, name: &str) -> String {
+    let (path, uri) = fixture(name);
+
+    client.notify(
+        "textDocument/didOpen",
+        json!({
+            "textDocument": {
+                "uri": uri,
+                "languageId": "typescript",
+                "version": 1,
+                "text": fs::read_to_string(&path).unwrap(),
+            }
+        }),
+    );
+    client.wait_for("textDocument/publishDiagnostics", |p| p["uri"] == uri);
+
+    uri
+}
+
+#[test]
+fn publish_diagnostics() {
+    let (path, uri) = fixture("diagnostics/main.ts");
+    let mut client = Client::start();
+
+    client.notify(
+        "textDocument/didOpen",
+        json!({
+            "textDocument": {
+                "uri": uri,
+                "languageId": "typescript",
+                "version": 1,
+                "text": fs::read_to_string(&path).unwrap(),
+            }
+        }),
+    );
+    let params = client.wait_for("textDocument/publishDiagnostics", |p| p["uri"] == uri);
+    assert_eq!(params["diagnostics"], json!([]));
+
+    // Unsaved content should be checked.
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": uri, "version": 2 },
+            "contentChanges": [{ "text": "const a: number = 'foo';\n" }],
+        }),
+    );
+    let params = client.wait_for("textDocument/publishDiagnostics", |p| p["uri"] == uri && p["version"] == 2);
+    let diagnostics = params["diagnostics"].as_array().unwrap();
+    assert!(diagnostics.iter().any(|d| d["code"] == 2322), "{:#?}", diagnostics);
+    assert!(diagnostics.iter().all(|d| d["range"]["start"]["line"] == 0), "{:#?}", diagnostics);
+
+    client.notify("textDocument/didClose", json!({ "textDocument": { "uri": uri } }));
+    let params = client.wait_for("textDocument/publishDiagnostics", |p| p["uri"] == uri);
+    assert_eq!(params["diagnostics"], json!([]));
+}
+
+#[test]
+fn dependents_are_checked_again() {
+    let mut client = Client::start();
+    let main = open(&mut client, "invalidate/main.ts");
+    let dep = open(&mut client, "invalidate/dep.ts");
+
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": dep, "version": 2 },
+            "contentChanges": [{ "text": "export const a: string = '';\n" }],
+        }),
+    );
+    let params = client.wait_for("textDocument/publishDiagnostics", |p| {
+        p["uri"] == main && !p["diagnostics"].as_array().unwrap().is_empty()
+    });
+    let diagnostics = params["diagnostics"].as_array().unwrap();
+    assert!(diagnostics.iter().all(|d| d["code"] == 2322), "{:#?}", diagnostics);
+
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": dep, "version": 3 },
+            "contentChanges": [{ "text": "export const a: number = 2;\n" }],
+        }),
+    );
+    let params = client.wait_for("textDocument/publishDiagnostics", |p| p["uri"] == main);
+    assert_eq!(params["diagnostics"], json!([]));
+}
+
+#[test]
+fn hover() {
+    let mut client = Client::start();
+    let uri = open(&mut client, "hover/main.ts");
+
+    let result = client.request(
+        "textDocument/hover",
+        json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": 1, "character": 10 },
+        }),
+    );
+    let value = result["contents"]["value"].as_str().unwrap();
+    assert!(value.contains("number[]"), "{}", value);
+    assert_eq!(result["range"]["start"], json!({ "line": 1, "character": 10 }));
+}
+
+#[test]
+fn goto_definition() {
+    let mut client = Client::start();
+    let uri = open(&mut client, "navigation/main.ts");
+    let (_, a_uri) = fixture("navigation/a.ts");
+
+    // Through `export { foo as bar }` of `b.ts`.
+    let result = client.request(
+        "textDocument/definition",
+        json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": 1, "character": 11 },
+        }),
+    );
+    assert_eq!(
+        result,
+        json!([{
+            "uri": a_uri,
+            "range": {
+                "start": { "line": 0, "character": 13 },
+                "end": { "line": 0, "character": 16 },
+            },
+        }])
+    );
+}
+
+#[test]
+fn find_references() {
+    let mut client = Client::start();
+    let uri = open(&mut client, "navigation/main.ts");
+
+    let result = client.request(
+        "textDocument/references",
+        json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": 1, "character": 11 },
+            "context": { "includeDeclaration": true },
+        }),
+    );
+    let locations = result.as_array().unwrap();
+
+    // The declaration in `a.ts`, two in `b.ts`, three in `main.ts` and the
+    // namespace access in `c.ts`, which is not imported by the opened file.
+    assert_eq!(locations.len(), 7, "{:#?}", locations);
+    assert!(
+        locations.iter().any(|l| l["uri"].as_str().unwrap().ends_with("c.ts")),
+        "{:#?}",
+        locations
+    );
+}
+
+fn labels(result: &Value) -> Vec<&str> {
+    let mut labels: Vec<_> = result
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["label"].as_str().unwrap())
+        .collect();
+    labels.sort();
+    labels
+}
+
+#[test]
+fn member_completion() {
+    let mut client = Client::start();
+    let uri = open(&mut client, "completion/main.ts");
+    let (path, _) = fixture("completion/main.ts");
+
+    // The member access being typed is a syntax error, so the previous
+    // analysis should be used.
+    let text = fs::read_to_string(&path).unwrap().replace("p.x;", "p.");
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": uri, "version": 2 },
+            "contentChanges": [{ "text": text }],
+        }),
+    );
+
+    let result = client.request(
+        "textDocument/completion",
+        json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": 8, "character": 2 },
+        }),
+    );
+    assert_eq!(labels(&result), vec!["move", "x", "y"], "{:#?}", result);
+}
+
+#[test]
+fn object_literal_completion() {
+    let mut client = Client::start();
+    let uri = open(&mut client, "completion/main.ts");
+
+    let result = client.request(
+        "textDocument/completion",
+        json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": 7, "character": 25 },
+        }),
+    );
+    assert_eq!(labels(&result), vec!["move", "y"], "{:#?}", result);
+}
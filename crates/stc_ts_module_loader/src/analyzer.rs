@@ -1,22 +1,36 @@
-use stc_ts_utils::imports::find_imports_in_comments;
+use stc_ts_utils::imports::{find_imports_in_comments, ImportRef, ResolutionMode};
 use swc_atoms::JsWord;
 use swc_common::{comments::Comments, Span, Spanned};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{Visit, VisitWith};
 
-pub(crate) fn find_modules_and_deps<C>(comments: &C, m: &Module) -> (Vec<JsWord>, Vec<JsWord>)
+/// Modules declared or referenced by a module.
+#[derive(Default)]
+pub(crate) struct ModuleRefs {
+    pub declared_modules: Vec<JsWord>,
+    pub deps: Vec<JsWord>,
+    /// `/// <reference types="..." />`
+    pub type_refs: Vec<(JsWord, Option<ResolutionMode>)>,
+    /// `/// <reference lib="..." />`
+    pub libs: Vec<JsWord>,
+    /// `/// <reference no-default-lib="true" />`
+    pub no_default_lib: bool,
+    /// `true` if the module contains JSX elements or fragments.
+    pub uses_jsx: bool,
+}
+
+pub(crate) fn find_modules_and_deps<C>(comments: &C, m: &Module) -> ModuleRefs
 where
     C: Comments,
 {
     let mut v = DepFinder {
         comments,
-        declared_modules: Default::default(),
-        deps: Default::default(),
+        refs: Default::default(),
     };
 
     m.visit_with(&mut v);
 
-    (v.declared_modules, v.deps)
+    v.refs
 }
 
 struct DepFinder<C>
@@ -24,8 +38,7 @@ where
     C: Comments,
 {
     comments: C,
-    declared_modules: Vec<JsWord>,
-    deps: Vec<JsWord>,
+    refs: ModuleRefs,
 }
 
 impl<C> DepFinder<C>
@@ -33,9 +46,14 @@ where
     C: Comments,
 {
     fn check_comments(&mut self, span: Span) {
-        let deps = find_imports_in_comments(&self.comments, span);
-
-        self.deps.extend(deps.into_iter().map(|i| i.to_path()));
+        for import in find_imports_in_comments(&self.comments, span) {
+            match import {
+                ImportRef::Types(name, mode) => self.refs.type_refs.push((name, mode)),
+                ImportRef::Lib(name) => self.refs.libs.push(name),
+                ImportRef::NoDefaultLib => self.refs.no_default_lib = true,
+                import => self.refs.deps.extend(import.to_path()),
+            }
+        }
     }
 }
 
@@ -56,21 +74,33 @@ where
     }
 
     fn visit_export_all(&mut self, export: &ExportAll) {
-        self.deps.push(export.src.value.clone());
+        self.refs.deps.push(export.src.value.clone());
     }
 
     fn visit_import_decl(&mut self, import: &ImportDecl) {
-        self.deps.push(import.src.value.clone());
+        self.refs.deps.push(import.src.value.clone());
     }
 
     fn visit_named_export(&mut self, export: &NamedExport) {
         if let Some(src) = &export.src {
-            self.deps.push(src.value.clone());
+            self.refs.deps.push(src.value.clone());
         }
     }
 
     fn visit_ts_external_module_ref(&mut self, import: &TsExternalModuleRef) {
-        self.deps.push(import.expr.value.clone());
+        self.refs.deps.push(import.expr.value.clone());
+    }
+
+    fn visit_jsx_element(&mut self, e: &JSXElement) {
+        self.refs.uses_jsx = true;
+
+        e.visit_children_with(self);
+    }
+
+    fn visit_jsx_fragment(&mut self, e: &JSXFragment) {
+        self.refs.uses_jsx = true;
+
+        e.visit_children_with(self);
     }
 
     fn visit_ts_module_decl(&mut self, n: &TsModuleDecl) {
@@ -78,7 +108,7 @@ where
 
         match &n.id {
             TsModuleName::Str(s) => {
-                self.declared_modules.push(s.value.clone());
+                self.refs.declared_modules.push(s.value.clone());
             }
             _ => {}
         }
@@ -6,11 +6,17 @@ use anyhow::{anyhow, bail, Error};
 use dashmap::DashMap;
 use fxhash::FxBuildHasher;
 use parking_lot::{Mutex, RwLock};
+use petgraph::EdgeDirection::{Incoming, Outgoing};
 use rayon::prelude::*;
 use stc_ts_types::{module_id::ModuleIdGenerator, ModuleId};
+use stc_ts_utils::imports::ResolutionMode;
 use stc_utils::panic_ctx;
 use swc_atoms::JsWord;
-use swc_common::{collections::AHashMap, comments::Comments, FileName, Mark, SourceMap, DUMMY_SP};
+use swc_common::{
+    collections::AHashMap,
+    comments::{Comment, Comments, SingleThreadedComments},
+    BytePos, FileName, Mark, SourceMap, Spanned, DUMMY_SP,
+};
 use swc_ecma_ast::{EsVersion, Module};
 use swc_ecma_loader::resolve::Resolve;
 use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
@@ -64,10 +70,29 @@ where
     deps: RwLock<DepGraphData>,
 
     parse_cache: Mutex<AHashMap<Arc<FileName>, Arc<Module>>>,
+
+    /// Comments of each parsed file, sorted by position.
+    ///
+    /// This is stored separately from `comments` so that users can get comments
+    /// of a file without scanning comments of all files.
+    file_comments: DashMap<Arc<FileName>, Arc<Vec<Comment>>, FxBuildHasher>,
+
+    /// Positions of leading and trailing comments of each parsed file in
+    /// `comments`, used to remove them when the file is invalidated.
+    comment_positions: DashMap<Arc<FileName>, (Vec<BytePos>, Vec<BytePos>), FxBuildHasher>,
+
+    /// Names of `/// <reference lib="..." />` in each loaded module, and
+    /// whether it contains `/// <reference no-default-lib="true" />`.
+    ///
+    /// This is stored per module so it can be recomputed when a module is
+    /// invalidated.
+    lib_refs: DashMap<ModuleId, (Vec<JsWord>, bool), FxBuildHasher>,
+
+    /// Imported implicitly by modules with JSX, like `react/jsx-runtime`.
+    jsx_runtime: Option<JsWord>,
 }
 #[derive(Default)]
 struct DepGraphData {
-    pub all: Vec<ModuleId>,
     pub graph: FastDiGraphMap<ModuleId, ()>,
     pub cycles: Vec<Vec<ModuleId>>,
 }
@@ -96,13 +121,35 @@ where
             parsing_errors: Default::default(),
             deps: Default::default(),
             parse_cache: Default::default(),
+            file_comments: Default::default(),
+            comment_positions: Default::default(),
+            lib_refs: Default::default(),
+            jsx_runtime: Default::default(),
         }
     }
 
+    /// Loads `module` as a dependency of modules with JSX, like
+    /// `jsxImportSource` of `tsc`.
+    pub fn with_jsx_runtime(mut self, module: Option<JsWord>) -> Self {
+        self.jsx_runtime = module;
+        self
+    }
+
     pub fn comments(&self) -> &C {
         &self.comments
     }
 
+    /// Returns comments in the file at `path`, sorted by position.
+    pub fn comments_of(&self, path: &Arc<FileName>) -> Arc<Vec<Comment>> {
+        self.file_comments.get(path).map(|v| v.clone()).unwrap_or_default()
+    }
+
+    /// Returns the position of the first statement or declaration of the
+    /// module. Comments before it are pragmas of the file.
+    pub fn first_item_pos(&self, id: ModuleId) -> Option<BytePos> {
+        self.with_module(id, |m| m.and_then(|m| m.body.first()).map(|item| item.span().lo))
+    }
+
     /// TODO: Fix race condition of `errors`.
     pub fn load_all(&self, entry: &Arc<FileName>) -> Result<ModuleId, (ModuleId, Error)> {
         self.load_including_deps(entry, false);
@@ -119,7 +166,6 @@ where
         {
             let mut deps = self.deps.write();
 
-            deps.all.extend(res.all);
             deps.cycles.extend(res.cycles);
 
             for n in res.graph.nodes() {
@@ -142,14 +188,142 @@ where
         Ok(module_id)
     }
 
+    /// Drops the cached module at `path` and loads it again, along with new
+    /// dependencies of it.
+    ///
+    /// Returns the module, modules declared in it and their transitive
+    /// dependents, which should be analyzed again.
+    pub fn invalidate(&self, path: &Arc<FileName>) -> Vec<ModuleId> {
+        let module_id = self.id(path);
+
+        let affected = {
+            let deps = self.deps.read();
+
+            let mut affected = vec![module_id];
+            affected.extend(self.modules_declared_in(path));
+            let mut idx = 0;
+            while let Some(&id) = affected.get(idx) {
+                idx += 1;
+
+                for dependent in deps.graph.neighbors_directed(id, Incoming) {
+                    if !affected.contains(&dependent) {
+                        affected.push(dependent);
+                    }
+                }
+            }
+            affected
+        };
+
+        self.parse_cache.lock().remove(path);
+        self.file_comments.remove(path);
+        if let Some((_, (leading, trailing))) = self.comment_positions.remove(path) {
+            for pos in leading {
+                self.comments.take_leading(pos);
+            }
+            for pos in trailing {
+                self.comments.take_trailing(pos);
+            }
+        }
+        self.loaded.remove(&module_id);
+        self.started.remove(&module_id);
+        self.lib_refs.remove(&module_id);
+        self.resolver.remove_declared_modules(path);
+
+        self.load_including_deps(path, false);
+        self.load_including_deps(path, true);
+        // It's not loaded if it cannot be parsed anymore.
+        self.loaded.entry(module_id).or_insert(Err(()));
+
+        // Only dependencies of the module are changed, so the graph is analyzed again
+        // starting from it. Cycles containing it are reachable from it.
+        {
+            let res = {
+                let mut analyzer = GraphAnalyzer::new(&*self);
+                analyzer.load(module_id);
+                analyzer.into_result()
+            };
+
+            let mut deps = self.deps.write();
+
+            let prev_deps = deps.graph.neighbors_directed(module_id, Outgoing).collect::<Vec<_>>();
+            for dep in prev_deps {
+                deps.graph.remove_edge(module_id, dep);
+            }
+            for n in res.graph.nodes() {
+                deps.graph.add_node(n);
+            }
+            for (a, b, _) in res.graph.all_edges() {
+                deps.graph.add_edge(a, b, ());
+            }
+
+            deps.cycles.retain(|cycle| !cycle.contains(&module_id));
+            for cycle in res.cycles {
+                let known = deps
+                    .cycles
+                    .iter()
+                    .any(|c| c.len() == cycle.len() && cycle.iter().all(|id| c.contains(id)));
+                if !known {
+                    deps.cycles.push(cycle);
+                }
+            }
+        }
+
+        for err in take(&mut *self.errors.lock()) {
+            error!("failed to reload modules: {:?}", err);
+        }
+
+        affected
+    }
+
+    /// Takes syntax errors of the modules loaded so far.
+    pub fn take_parsing_errors(&self) -> Vec<swc_ecma_parser::error::Error> {
+        take(&mut *self.parsing_errors.lock())
+    }
+
+    /// Returns false if the module is not loaded yet or it failed to load.
+    pub fn is_loaded(&self, id: ModuleId) -> bool {
+        matches!(self.loaded.get(&id).as_deref(), Some(Ok(..)))
+    }
+
+    /// Returns direct dependencies of the module.
+    pub fn deps_of_module(&self, id: ModuleId) -> Vec<ModuleId> {
+        match self.loaded.get(&id).as_deref() {
+            Some(Ok(m)) => m.deps.clone(),
+            _ => Default::default(),
+        }
+    }
+
     pub fn id_for_declare_module(&self, module_name: &JsWord) -> ModuleId {
         self.id_generator.generate(&Arc::new(FileName::Custom(module_name.to_string()))).0
     }
 
+    /// Returns ids of modules declared by `declare module "name"` in `path`.
+    pub fn modules_declared_in(&self, path: &Arc<FileName>) -> Vec<ModuleId> {
+        self.resolver
+            .modules_declared_in(path)
+            .iter()
+            .map(|name| self.id_for_declare_module(name))
+            .collect()
+    }
+
+    /// Returns the file containing the declaration of a module created by
+    /// [Self::id_for_declare_module].
+    pub fn file_declaring_module(&self, id: ModuleId) -> Option<Arc<FileName>> {
+        match &*self.path(id) {
+            FileName::Custom(name) => self.resolver.file_declaring(name),
+            _ => None,
+        }
+    }
+
     pub fn path(&self, id: ModuleId) -> Arc<FileName> {
         self.id_generator.path(id)
     }
 
+    /// Returns paths of all loaded modules.
+    pub fn files(&self) -> Vec<Arc<FileName>> {
+        self.loaded.iter().map(|entry| self.id_generator.path(*entry.key())).collect()
+    }
+
     pub fn get_circular(&self, id: ModuleId) -> Option<Vec<ModuleId>> {
         let deps = self.deps.read();
 
@@ -167,6 +341,29 @@ where
         self.resolver.resolve(base, specifier)
     }
 
+    /// Resolves `/// <reference types="..." />`.
+    pub fn resolve_type_reference(&self, base: &FileName, name: &JsWord, mode: Option<ResolutionMode>) -> Result<Arc<FileName>, Error> {
+        self.resolver.resolve_type_reference(base, name, mode)
+    }
+
+    /// Returns names of `/// <reference lib="..." />` in loaded modules, and
+    /// whether default libraries are disabled by `/// <reference
+    /// no-default-lib="true" />`.
+    pub fn lib_references(&self) -> (Vec<JsWord>, bool) {
+        let mut names = vec![];
+        let mut no_default_lib = false;
+        for entry in self.lib_refs.iter() {
+            for name in &entry.0 {
+                if !names.contains(name) {
+                    names.push(name.clone());
+                }
+            }
+            no_default_lib |= entry.1;
+        }
+
+        (names, no_default_lib)
+    }
+
     fn with_module<F, Ret>(&self, id: ModuleId, f: F) -> Ret
     where
         F: FnOnce(Option<&Module>) -> Ret,
@@ -177,7 +374,7 @@ where
             Some(m) => match m {
                 Ok(v) => f(Some(&v.module)),
                 Err(..) => {
-                    error!("`self.loaded` did not contain `id`: {:?}", id);
+                    debug!("Module {:?} failed to load; using an empty module", id);
 
                     f(Some(&Module {
                         span: DUMMY_SP,
@@ -220,6 +417,12 @@ where
 
                     self.errors.lock().push(err);
 
+                    self.loaded.insert(id, Err(()));
+                } else if let FileName::Real(..) = &**path {
+                    // The second pass skips modules which are not started, so we mark it as
+                    // loaded here. Syntax errors are reported using `parsing_errors`.
+                    debug!("failed to load module: {:?}", err);
+
                     self.loaded.insert(id, Err(()));
                 }
 
@@ -302,23 +505,40 @@ where
 
         let _panic = panic_ctx!(format!("ModuleGraph.load({}, span = {:?})", filename, module.span));
 
-        let (declared_modules, deps) = find_modules_and_deps(&self.comments, &module);
+        let refs = find_modules_and_deps(&self.comments, &module);
 
-        for decl in declared_modules {
-            self.resolver.declare_module(decl);
+        for decl in refs.declared_modules {
+            self.resolver.declare_module(decl, filename);
+        }
+
+        if !refs.libs.is_empty() || refs.no_default_lib {
+            self.lib_refs.insert(module_id, (refs.libs, refs.no_default_lib));
+        }
+
+        let mut deps = refs.deps;
+        if refs.uses_jsx {
+            deps.extend(self.jsx_runtime.clone());
         }
 
         let resolver = &self.resolver;
 
+        let type_refs = refs
+            .type_refs
+            .into_par_iter()
+            .map(|(name, mode)| resolver.resolve_type_reference(filename, &name, mode))
+            .filter_map(|res| res.ok());
+
         let deps = if resolve_all {
             deps.into_par_iter()
                 .map(|specifier| resolver.resolve(filename, &specifier))
                 .filter_map(|res| res.ok())
+                .chain(type_refs)
                 .collect()
         } else {
             deps.into_par_iter()
                 .map(|specifier| resolver.resolve(filename, &specifier))
                 .filter_map(|res| res.ok())
+                .chain(type_refs)
                 .collect()
         };
 
@@ -340,19 +560,46 @@ where
         };
 
         let fm = self.cm.load_file(&path)?;
+        // Comments are collected per file first, to record comments of the file.
+        let comments = SingleThreadedComments::default();
         let lexer = Lexer::new(
             Syntax::Typescript(TsConfig {
-                dts: path.as_os_str().to_string_lossy().ends_with(".d.ts"),
+                dts: [".d.ts", ".d.mts", ".d.cts"]
+                    .iter()
+                    .any(|ext| path.as_os_str().to_string_lossy().ends_with(ext)),
                 tsx: path.extension().map(|v| v == "tsx").unwrap_or(false),
                 ..self.parser_config.clone()
             }),
             self.target,
             StringInput::from(&*fm),
-            Some(&self.comments),
+            Some(&comments),
         );
 
         let mut parser = Parser::new_from(lexer);
         let result = parser.parse_module();
+        let extra_errors = parser.take_errors();
+
+        {
+            let (leading, trailing) = comments.take_all();
+            let (leading, trailing) = (leading.take(), trailing.take());
+
+            let mut all = vec![];
+            let mut positions = (vec![], vec![]);
+            for (pos, cmts) in leading {
+                all.extend(cmts.iter().cloned());
+                positions.0.push(pos);
+                self.comments.add_leading_comments(pos, cmts);
+            }
+            for (pos, cmts) in trailing {
+                all.extend(cmts.iter().cloned());
+                positions.1.push(pos);
+                self.comments.add_trailing_comments(pos, cmts);
+            }
+            all.sort_by_key(|c| c.span.lo);
+            all.dedup_by_key(|c| c.span.lo);
+            self.file_comments.insert(filename.clone(), Arc::new(all));
+            self.comment_positions.insert(filename.clone(), positions);
+        }
 
         let module = match result {
             Ok(v) => v,
@@ -363,7 +610,6 @@ where
                 bail!("Failed to parse {}", path.display())
             }
         };
-        let extra_errors = parser.take_errors();
         if !extra_errors.is_empty() {
             let mut errors = self.parsing_errors.lock();
             errors.extend(extra_errors);
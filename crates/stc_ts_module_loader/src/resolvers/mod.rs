@@ -1,2 +1,48 @@
 pub mod node;
+pub mod node16;
+pub mod paths;
 pub(crate) mod typescript;
+
+/// Returns true if `target` is relative to the importing file, like `./a`,
+/// `../a`, `.` or `..`.
+pub(crate) fn is_relative(target: &str) -> bool {
+    target == "." || target == ".." || target.starts_with("./") || target.starts_with("../")
+}
+
+/// `@scope/pkg` is published as `@types/scope__pkg`.
+pub fn types_package_name(name: &str) -> String {
+    match name.strip_prefix('@') {
+        Some(name) => name.replacen('/', "__", 1),
+        None => name.to_string(),
+    }
+}
+
+/// Finds the pattern which matches `target`, and returns it with the text
+/// matched by `*`.
+///
+/// An exact match takes precedence, and then the pattern with the longest
+/// prefix before `*` is used. Patterns may contain at most one `*`.
+pub(crate) fn best_match<'a, 'b>(patterns: impl IntoIterator<Item = &'a str>, target: &'b str) -> Option<(&'a str, &'b str)> {
+    let mut best: Option<(&'a str, &'b str)> = None;
+
+    for pattern in patterns {
+        match pattern.split_once('*') {
+            None => {
+                if pattern == target {
+                    return Some((pattern, ""));
+                }
+            }
+            Some((prefix, suffix)) => {
+                if target.len() >= prefix.len() + suffix.len()
+                    && target.starts_with(prefix)
+                    && target.ends_with(suffix)
+                    && best.map_or(true, |(best, _)| best.find('*').unwrap() < prefix.len())
+                {
+                    best = Some((pattern, &target[prefix.len()..target.len() - suffix.len()]));
+                }
+            }
+        }
+    }
+
+    best
+}
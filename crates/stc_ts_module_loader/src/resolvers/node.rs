@@ -4,12 +4,14 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use anyhow::{bail, Context, Error};
+use anyhow::{anyhow, bail, Context, Error};
 use path_clean::PathClean;
 use serde::Deserialize;
 use swc_common::FileName;
 use swc_ecma_loader::resolve::Resolve;
 
+use super::is_relative;
+
 static EXTENSIONS: &[&str] = &["tsx", "ts", "d.ts"];
 
 #[derive(Deserialize)]
@@ -155,10 +157,15 @@ impl Resolve for NodeResolver {
         let cwd = &Path::new(".");
         let base_dir = base.parent().unwrap_or(&cwd);
 
-        if target.starts_with("./") || target.starts_with("../") {
+        if is_relative(target) {
             let path = base_dir.join(&*target);
-            return self
-                .resolve_as_file(&path)
+            // Like `tsc`, `.` and `..` are resolved only as directories.
+            let file = if target == "." || target == ".." {
+                Err(anyhow!("`{}` refers to a directory", target))
+            } else {
+                self.resolve_as_file(&path)
+            };
+            return file
                 .with_context(|| format!("failed to resolve `{}` as a file dependancy from `{}`", target, base.display()))
                 .or_else(|_| {
                     self.resolve_as_directory(&path)
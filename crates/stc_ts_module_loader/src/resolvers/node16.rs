@@ -0,0 +1,557 @@
+//! Resolution for `node16`, `nodenext` and `bundler` of `moduleResolution`.
+//!
+//! Unlike [NodeResolver], this respects `exports` and `imports` of
+//! `package.json`, using conditions for the module kind of the importing file.
+
+use std::{
+    fmt,
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail, Context, Error};
+use path_clean::PathClean;
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use swc_common::FileName;
+use swc_ecma_loader::resolve::Resolve;
+
+use super::{best_match, is_relative, node::NodeResolver, types_package_name};
+
+/// Used to select an entry of `typesVersions`.
+const TS_VERSION: (u32, u32) = (4, 9);
+
+/// Extensions tried for extensionless paths, if allowed.
+static EXTENSIONS: &[&str] = &[".ts", ".tsx", ".d.ts"];
+
+/// Extensions of javascript files, and typescript files for them.
+static JS_TO_TS: &[(&str, &[&str])] = &[
+    (".js", &[".ts", ".tsx", ".d.ts"]),
+    (".jsx", &[".tsx", ".d.ts"]),
+    (".mjs", &[".mts", ".d.mts"]),
+    (".cjs", &[".cts", ".d.cts"]),
+];
+
+/// `moduleResolution` of `tsconfig.json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ModuleResolution {
+    /// `node10`, or `node`.
+    #[default]
+    Node10,
+    /// `node16`
+    Node16,
+    /// `nodenext`
+    NodeNext,
+    /// `bundler`
+    Bundler,
+}
+
+impl FromStr for ModuleResolution {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match &*s.to_ascii_lowercase() {
+            "node" | "node10" => ModuleResolution::Node10,
+            "node16" => ModuleResolution::Node16,
+            "nodenext" => ModuleResolution::NodeNext,
+            "bundler" => ModuleResolution::Bundler,
+            _ => bail!("unsupported moduleResolution `{}`", s),
+        })
+    }
+}
+
+/// Kind of a module, which selects conditions of `exports`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleKind {
+    Esm,
+    CommonJs,
+}
+
+impl ModuleKind {
+    /// `.mts` and `.cts` files have fixed kinds, and other files are ES
+    /// modules only if `type` of the nearest `package.json` is `module`.
+    pub fn of(path: &Path) -> Self {
+        let name = path.file_name().map(|s| s.to_string_lossy()).unwrap_or_default();
+
+        if name.ends_with(".mts") || name.ends_with(".mjs") {
+            return ModuleKind::Esm;
+        }
+        if name.ends_with(".cts") || name.ends_with(".cjs") {
+            return ModuleKind::CommonJs;
+        }
+
+        match path.parent().and_then(package_scope) {
+            Some((_, pkg)) if pkg.ty.as_deref() == Some("module") => ModuleKind::Esm,
+            _ => ModuleKind::CommonJs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Node16Resolver {
+    pub mode: ModuleResolution,
+}
+
+impl Node16Resolver {
+    fn conditions(&self, kind: ModuleKind) -> &'static [&'static str] {
+        match (self.mode, kind) {
+            (ModuleResolution::Bundler, _) => &["types", "import", "default"],
+            (_, ModuleKind::Esm) => &["types", "import", "node", "default"],
+            (_, ModuleKind::CommonJs) => &["types", "require", "node", "default"],
+        }
+    }
+
+    /// ES modules of node require extensions for relative imports.
+    fn allows_extensionless(&self, kind: ModuleKind) -> bool {
+        self.mode == ModuleResolution::Bundler || kind == ModuleKind::CommonJs
+    }
+
+    fn resolve_relative(&self, path: &Path, kind: ModuleKind) -> Result<PathBuf, Error> {
+        if let Some(file) = ts_file_for(path) {
+            return Ok(file);
+        }
+
+        if !self.allows_extensionless(kind) {
+            bail!(
+                "relative import paths need explicit file extensions in ECMAScript imports: `{}`",
+                path.display()
+            );
+        }
+
+        probe(path)
+            .or_else(|| self.resolve_directory(path))
+            .ok_or_else(|| anyhow!("file not found: {}", path.display()))
+    }
+
+    /// `exports` is not used for directories.
+    fn resolve_directory(&self, dir: &Path) -> Option<PathBuf> {
+        if let Some(pkg) = read_package_json(dir) {
+            for entry in [&pkg.types, &pkg.typings, &pkg.main].into_iter().flatten() {
+                let path = dir.join(entry).clean();
+                if let Some(file) = ts_file_for(&path).or_else(|| probe(&path)) {
+                    return Some(file);
+                }
+            }
+        }
+
+        probe(&dir.join("index"))
+    }
+
+    /// Resolves `specifier` like `pkg` or `@scope/pkg/sub` from `node_modules`
+    /// directories.
+    fn resolve_package(&self, base_dir: &Path, specifier: &str, kind: ModuleKind) -> Result<PathBuf, Error> {
+        let (name, subpath) = split_package_name(specifier);
+        let mut last_err = None;
+
+        for dir in base_dir.ancestors() {
+            let node_modules = dir.join("node_modules");
+            if !node_modules.is_dir() {
+                continue;
+            }
+
+            for pkg_dir in [node_modules.join(name), node_modules.join("@types").join(types_package_name(name))] {
+                if !pkg_dir.is_dir() {
+                    continue;
+                }
+
+                match self.resolve_in_package(&pkg_dir, &subpath, kind) {
+                    Ok(file) => return Ok(file),
+                    Err(err) => last_err = Some(err),
+                }
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| anyhow!("package `{}` not found", name)))
+    }
+
+    /// `subpath` is `.` or starts with `./`.
+    fn resolve_in_package(&self, pkg_dir: &Path, subpath: &str, kind: ModuleKind) -> Result<PathBuf, Error> {
+        let pkg = read_package_json(pkg_dir).unwrap_or_default();
+
+        if let Some(exports) = &pkg.exports {
+            return self
+                .resolve_exports(pkg_dir, exports, subpath, kind)
+                .ok_or_else(|| anyhow!("`{}` is not exported from `{}`", subpath, pkg_dir.display()));
+        }
+
+        let rest = match subpath.strip_prefix("./") {
+            Some(rest) => rest.to_string(),
+            None => pkg.types.clone().or_else(|| pkg.typings.clone()).unwrap_or_else(|| "index".into()),
+        };
+        if let Some(file) = pkg
+            .types_versions
+            .as_ref()
+            .and_then(|v| self.resolve_types_versions(pkg_dir, v, &rest))
+        {
+            return Ok(file);
+        }
+
+        let resolved = if subpath == "." {
+            self.resolve_directory(pkg_dir)
+        } else {
+            let path = pkg_dir.join(&rest).clean();
+            ts_file_for(&path)
+                .or_else(|| probe(&path))
+                .or_else(|| self.resolve_directory(&path))
+        };
+
+        resolved.ok_or_else(|| anyhow!("`{}` not found in `{}`", subpath, pkg_dir.display()))
+    }
+
+    fn resolve_exports(&self, pkg_dir: &Path, exports: &Target, subpath: &str, kind: ModuleKind) -> Option<PathBuf> {
+        match exports {
+            Target::Object(entries) if !entries.is_empty() && entries.iter().all(|(key, _)| key.starts_with('.')) => {
+                self.resolve_pattern_map(pkg_dir, entries, subpath, kind, false)
+            }
+            // Conditions or a path for `.`
+            _ if subpath == "." => self.resolve_target(pkg_dir, exports, "", kind, false),
+            _ => None,
+        }
+    }
+
+    /// Resolves `#specifier` using `imports` of the package containing
+    /// `base_dir`.
+    fn resolve_imports(&self, base_dir: &Path, specifier: &str, kind: ModuleKind) -> Result<PathBuf, Error> {
+        let (pkg_dir, pkg) = package_scope(base_dir).ok_or_else(|| anyhow!("package.json not found for `{}`", specifier))?;
+
+        match &pkg.imports {
+            Some(Target::Object(entries)) => self
+                .resolve_pattern_map(&pkg_dir, entries, specifier, kind, true)
+                .ok_or_else(|| anyhow!("`{}` is not defined in imports of `{}`", specifier, pkg_dir.display())),
+            _ => bail!("`{}` does not have imports", pkg_dir.display()),
+        }
+    }
+
+    /// Resolves `key` using `exports` or `imports`, which are maps from
+    /// subpaths or patterns.
+    fn resolve_pattern_map(
+        &self,
+        pkg_dir: &Path,
+        entries: &[(String, Target)],
+        key: &str,
+        kind: ModuleKind,
+        is_imports: bool,
+    ) -> Option<PathBuf> {
+        let (pattern, matched) = best_match(entries.iter().map(|(pattern, _)| &**pattern), key)?;
+        let target = entries.iter().find(|(p, _)| p == pattern).map(|(_, target)| target)?;
+
+        self.resolve_target(pkg_dir, target, matched, kind, is_imports)
+    }
+
+    /// `matched` replaces `*` in targets.
+    ///
+    /// Targets of `imports` may be package names.
+    fn resolve_target(&self, pkg_dir: &Path, target: &Target, matched: &str, kind: ModuleKind, is_imports: bool) -> Option<PathBuf> {
+        match target {
+            Target::Path(path) => {
+                let path = path.replace('*', matched);
+
+                if !path.starts_with("./") {
+                    if is_imports && !path.starts_with("../") && !path.starts_with('/') {
+                        return self.resolve_package(pkg_dir, &path, kind).ok();
+                    }
+                    return None;
+                }
+
+                ts_file_for(&pkg_dir.join(path).clean())
+            }
+            Target::Array(targets) => targets
+                .iter()
+                .find_map(|target| self.resolve_target(pkg_dir, target, matched, kind, is_imports)),
+            // The first condition which can be resolved is used, in the order of the object.
+            Target::Object(conditions) => conditions.iter().find_map(|(condition, target)| {
+                if self.conditions(kind).contains(&&**condition) {
+                    self.resolve_target(pkg_dir, target, matched, kind, is_imports)
+                } else {
+                    None
+                }
+            }),
+            Target::Null => None,
+        }
+    }
+
+    /// Uses the first entry of `typesVersions` whose range matches
+    /// [TS_VERSION].
+    fn resolve_types_versions(&self, pkg_dir: &Path, types_versions: &Target, rest: &str) -> Option<PathBuf> {
+        let paths = match types_versions {
+            Target::Object(entries) => entries
+                .iter()
+                .find(|(range, _)| matches_ts_version(range))
+                .map(|(_, paths)| paths)?,
+            _ => return None,
+        };
+        let entries = match paths {
+            Target::Object(entries) => entries,
+            _ => return None,
+        };
+
+        let (pattern, matched) = best_match(entries.iter().map(|(pattern, _)| &**pattern), rest)?;
+        let substitutions = match entries.iter().find(|(p, _)| p == pattern).map(|(_, target)| target)? {
+            Target::Array(targets) => targets.iter().collect(),
+            target => vec![target],
+        };
+
+        substitutions.into_iter().find_map(|substitution| match substitution {
+            Target::Path(path) => {
+                let path = pkg_dir.join(path.replacen('*', matched, 1)).clean();
+                ts_file_for(&path)
+                    .or_else(|| probe(&path))
+                    .or_else(|| self.resolve_directory(&path))
+            }
+            _ => None,
+        })
+    }
+}
+
+impl Resolve for Node16Resolver {
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        let base = match base {
+            FileName::Real(base) => &**base,
+            _ => return NodeResolver.resolve(base, target),
+        };
+        let base_dir = base.parent().unwrap_or_else(|| Path::new("."));
+        let kind = ModuleKind::of(base);
+
+        let resolved = if is_relative(target) {
+            let path = base_dir.join(target).clean();
+            // Like `tsc`, `.` and `..` are resolved only as directories.
+            if (target == "." || target == "..") && self.allows_extensionless(kind) {
+                self.resolve_directory(&path)
+                    .ok_or_else(|| anyhow!("file not found: {}", path.display()))
+            } else {
+                self.resolve_relative(&path, kind)
+            }
+        } else if target.starts_with('/') {
+            self.resolve_relative(Path::new(target), kind)
+        } else if target.starts_with('#') {
+            self.resolve_imports(base_dir, target, kind)
+        } else {
+            self.resolve_package(base_dir, target, kind)
+        };
+
+        resolved
+            .with_context(|| format!("failed to resolve `{}` from `{}` ({:?})", target, base.display(), kind))
+            .map(FileName::Real)
+    }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackageJson {
+    #[serde(default, rename = "type")]
+    ty: Option<String>,
+
+    #[serde(default)]
+    types: Option<String>,
+
+    #[serde(default)]
+    typings: Option<String>,
+
+    #[serde(default)]
+    main: Option<String>,
+
+    #[serde(default)]
+    exports: Option<Target>,
+
+    #[serde(default)]
+    imports: Option<Target>,
+
+    #[serde(default)]
+    types_versions: Option<Target>,
+}
+
+/// A value of `exports`, `imports` or `typesVersions`.
+///
+/// This is not a [serde_json::Value] because the order of conditions is
+/// significant.
+#[derive(Debug, Clone)]
+enum Target {
+    Null,
+    Path(String),
+    Array(Vec<Target>),
+    Object(Vec<(String, Target)>),
+}
+
+impl<'de> Deserialize<'de> for Target {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TargetVisitor;
+
+        impl<'de> Visitor<'de> for TargetVisitor {
+            type Value = Target;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a string, an array, an object or null")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Target, E>
+            where
+                E: de::Error,
+            {
+                Ok(Target::Path(v.to_string()))
+            }
+
+            fn visit_unit<E>(self) -> Result<Target, E>
+            where
+                E: de::Error,
+            {
+                Ok(Target::Null)
+            }
+
+            fn visit_bool<E>(self, _: bool) -> Result<Target, E>
+            where
+                E: de::Error,
+            {
+                Ok(Target::Null)
+            }
+
+            fn visit_i64<E>(self, _: i64) -> Result<Target, E>
+            where
+                E: de::Error,
+            {
+                Ok(Target::Null)
+            }
+
+            fn visit_u64<E>(self, _: u64) -> Result<Target, E>
+            where
+                E: de::Error,
+            {
+                Ok(Target::Null)
+            }
+
+            fn visit_f64<E>(self, _: f64) -> Result<Target, E>
+            where
+                E: de::Error,
+            {
+                Ok(Target::Null)
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Target, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut items = vec![];
+                while let Some(item) = seq.next_element()? {
+                    items.push(item);
+                }
+                Ok(Target::Array(items))
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Target, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut entries = vec![];
+                while let Some(key) = map.next_key::<String>()? {
+                    entries.push((key, map.next_value()?));
+                }
+                Ok(Target::Object(entries))
+            }
+        }
+
+        deserializer.deserialize_any(TargetVisitor)
+    }
+}
+
+fn read_package_json(dir: &Path) -> Option<PackageJson> {
+    let path = dir.join("package.json");
+    let file = File::open(&path).ok()?;
+
+    match serde_json::from_reader(BufReader::new(file)) {
+        Ok(pkg) => Some(pkg),
+        Err(err) => {
+            log::warn!("failed to parse `{}`: {}", path.display(), err);
+            None
+        }
+    }
+}
+
+/// Returns the directory of the nearest `package.json`, and its content.
+fn package_scope(dir: &Path) -> Option<(PathBuf, PackageJson)> {
+    dir.ancestors()
+        .filter(|dir| dir.file_name().map_or(true, |name| name != "node_modules"))
+        .find_map(|dir| read_package_json(dir).map(|pkg| (dir.to_path_buf(), pkg)))
+}
+
+/// Returns a typescript file for `path`, which should include an extension.
+///
+/// `./a.js` refers to `./a.ts` or `./a.d.ts`, like `tsc`.
+fn ts_file_for(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?;
+
+    for (ext, ts_exts) in JS_TO_TS {
+        if let Some(stem) = name.strip_suffix(ext) {
+            return ts_exts
+                .iter()
+                .map(|ts_ext| path.with_file_name(format!("{}{}", stem, ts_ext)))
+                .find(|path| path.is_file());
+        }
+    }
+
+    if [".ts", ".tsx", ".mts", ".cts"].iter().any(|ext| name.ends_with(ext)) && path.is_file() {
+        return Some(path.to_path_buf());
+    }
+
+    None
+}
+
+/// Tries `path` with [EXTENSIONS] appended.
+fn probe(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?;
+
+    EXTENSIONS
+        .iter()
+        .map(|ext| path.with_file_name(format!("{}{}", name, ext)))
+        .find(|path| path.is_file())
+}
+
+/// Splits `@scope/pkg/sub` into `@scope/pkg` and `./sub`. The subpath is `.`
+/// if `specifier` is a package name.
+fn split_package_name(specifier: &str) -> (&str, String) {
+    let name_len = if specifier.starts_with('@') {
+        specifier.match_indices('/').nth(1).map_or(specifier.len(), |(i, _)| i)
+    } else {
+        specifier.find('/').unwrap_or(specifier.len())
+    };
+
+    let (name, rest) = specifier.split_at(name_len);
+    let subpath = if rest.is_empty() { ".".into() } else { format!(".{}", rest) };
+
+    (name, subpath)
+}
+
+/// Checks if a range like `>=4.1` or `*` of `typesVersions` matches
+/// [TS_VERSION]. Comparators separated by spaces should all match.
+fn matches_ts_version(range: &str) -> bool {
+    range.split_whitespace().all(|comparator| {
+        if comparator == "*" {
+            return true;
+        }
+
+        let (op, version) = match comparator.find(|c: char| c.is_ascii_digit()) {
+            Some(i) => comparator.split_at(i),
+            None => return false,
+        };
+        let mut parts = version.split('.').map(|s| s.parse::<u32>());
+        let version = match (parts.next(), parts.next()) {
+            (Some(Ok(major)), Some(Ok(minor))) => (major, minor),
+            (Some(Ok(major)), None) => (major, 0),
+            _ => return false,
+        };
+
+        match op {
+            ">=" => TS_VERSION >= version,
+            ">" => TS_VERSION > version,
+            "<=" => TS_VERSION <= version,
+            "<" => TS_VERSION < version,
+            "" | "=" => TS_VERSION == version,
+            _ => false,
+        }
+    })
+}
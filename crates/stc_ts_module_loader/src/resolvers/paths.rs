@@ -0,0 +1,136 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Error};
+use path_clean::PathClean;
+use swc_common::FileName;
+use swc_ecma_loader::resolve::Resolve;
+
+use super::{
+    best_match, is_relative,
+    node::NodeResolver,
+    node16::{ModuleResolution, Node16Resolver},
+};
+
+/// Applies `baseUrl`, `paths` and `rootDirs` of `tsconfig.json`, and falls
+/// back to [NodeResolver] or [Node16Resolver] depending on
+/// `moduleResolution`.
+#[derive(Debug, Default)]
+pub struct PathsResolver {
+    /// Absolute path. Non-relative specifiers are resolved relative to this.
+    pub base_url: Option<PathBuf>,
+
+    /// Absolute path. Substitutions of `paths` are relative to this.
+    pub paths_base: PathBuf,
+
+    /// Patterns, which may contain a `*`, and substitutions for them.
+    pub paths: Vec<(String, Vec<String>)>,
+
+    /// Absolute paths. Contents of these directories are resolved as if they
+    /// are merged into one directory.
+    pub root_dirs: Vec<PathBuf>,
+
+    pub module_resolution: ModuleResolution,
+}
+
+impl PathsResolver {
+    fn resolve_fallback(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        match self.module_resolution {
+            ModuleResolution::Node10 => NodeResolver.resolve(base, target),
+            mode => Node16Resolver { mode }.resolve(base, target),
+        }
+    }
+
+    fn resolve_path(&self, path: &Path) -> Result<PathBuf, Error> {
+        NodeResolver
+            .resolve_as_file(path)
+            .or_else(|_| NodeResolver.resolve_as_directory(path))
+            .map(|p| p.clean())
+    }
+
+    /// Returns [None] if no pattern matches `target`.
+    fn resolve_using_paths(&self, target: &str) -> Option<Result<PathBuf, Error>> {
+        let (pattern, matched) = best_match(self.paths.iter().map(|(pattern, _)| &**pattern), target)?;
+        let substitutions = self.paths.iter().find(|(p, _)| p == pattern).map(|(_, s)| s)?;
+
+        for substitution in substitutions {
+            let path = self.paths_base.join(substitution.replacen('*', matched, 1));
+
+            if let Ok(resolved) = self.resolve_path(&path) {
+                return Some(Ok(resolved));
+            }
+        }
+
+        Some(Err(anyhow!(
+            "none of substitutions for the pattern `{}` of `paths` exists for `{}`",
+            pattern,
+            target
+        )))
+    }
+
+    /// Returns [None] if `path` is not in any of `rootDirs`.
+    fn resolve_using_root_dirs(&self, path: &Path) -> Option<Result<PathBuf, Error>> {
+        let path = path.clean();
+        let root = self
+            .root_dirs
+            .iter()
+            .filter(|dir| path.starts_with(dir))
+            .max_by_key(|dir| dir.components().count())?;
+        let rel = path.strip_prefix(root).unwrap();
+
+        // The directory containing the base file is tried first.
+        for dir in Some(root).into_iter().chain(self.root_dirs.iter().filter(|dir| *dir != root)) {
+            if let Ok(resolved) = self.resolve_path(&dir.join(rel)) {
+                return Some(Ok(resolved));
+            }
+        }
+
+        Some(Err(anyhow!("`{}` does not exist in any of `rootDirs`", rel.display())))
+    }
+}
+
+impl Resolve for PathsResolver {
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        let base_path = match base {
+            FileName::Real(base) => &**base,
+            _ => return self.resolve_fallback(base, target),
+        };
+
+        if is_relative(target) {
+            if !self.root_dirs.is_empty() {
+                let base_dir = base_path.parent().unwrap_or_else(|| Path::new("."));
+
+                if let Some(result) = self.resolve_using_root_dirs(&base_dir.join(target)) {
+                    return result
+                        .with_context(|| format!("failed to resolve `{}` using `rootDirs` from `{}`", target, base_path.display()))
+                        .map(FileName::Real);
+                }
+            }
+
+            return self.resolve_fallback(base, target);
+        }
+
+        if target.starts_with('/') {
+            return self.resolve_fallback(base, target);
+        }
+
+        match self.resolve_using_paths(target) {
+            Some(Ok(resolved)) => return Ok(FileName::Real(resolved)),
+            // Like `tsc`, `baseUrl` is not used if a pattern matches.
+            Some(Err(err)) => {
+                return self
+                    .resolve_fallback(base, target)
+                    .map_err(|_| err)
+                    .with_context(|| format!("failed to resolve `{}` using `paths` from `{}`", target, base_path.display()))
+            }
+            None => {}
+        }
+
+        if let Some(base_url) = &self.base_url {
+            if let Ok(resolved) = self.resolve_path(&base_url.join(target)) {
+                return Ok(FileName::Real(resolved));
+            }
+        }
+
+        self.resolve_fallback(base, target)
+    }
+}
@@ -1,16 +1,21 @@
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
 use anyhow::{Context, Error};
 use parking_lot::RwLock;
+use stc_ts_utils::imports::ResolutionMode;
 use swc_atoms::JsWord;
 use swc_common::FileName;
 use swc_ecma_loader::resolve::Resolve;
 
+use super::{best_match, types_package_name};
+
 pub(crate) struct TsResolver<R>
 where
     R: Resolve,
 {
-    declared_modules: RwLock<Vec<(JsWord, Arc<FileName>)>>,
+    /// Names of declared modules, [FileName::Custom]s for them, and the files
+    /// containing declarations.
+    declared_modules: RwLock<Vec<(JsWord, Arc<FileName>, Arc<FileName>)>>,
     resolver: R,
 }
 impl<R> TsResolver<R>
@@ -25,9 +30,12 @@ where
     }
 
     /// This returns [FileName::Custom] for `declare module "http"`-s.
+    ///
+    /// Like `tsc`, wildcard patterns like `declare module "*.css"` are used
+    /// only if `module_specifier` cannot be resolved to a typescript file.
     pub(crate) fn resolve(&self, base: &FileName, module_specifier: &str) -> Result<Arc<FileName>, Error> {
-        for (pat, path) in self.declared_modules.read().iter() {
-            if matches(&pat, &module_specifier) {
+        for (pat, path, _) in self.declared_modules.read().iter() {
+            if &**pat == module_specifier {
                 return Ok(path.clone());
             }
         }
@@ -35,24 +43,87 @@ where
         let resolved = self
             .resolver
             .resolve(&base, module_specifier)
-            .with_context(|| "failed to resolve dependency")?;
+            .with_context(|| "failed to resolve dependency");
+
+        match resolved {
+            Ok(FileName::Real(path)) if !is_ts_file(&path) => match self.resolve_wildcard(module_specifier) {
+                Some(declared) => Ok(declared),
+                None => Ok(Arc::new(FileName::Real(path))),
+            },
+            Ok(resolved) => Ok(Arc::new(resolved)),
+            Err(err) => self.resolve_wildcard(module_specifier).ok_or(err),
+        }
+    }
+
+    /// Finds the declared module with the longest prefix before `*`.
+    fn resolve_wildcard(&self, module_specifier: &str) -> Option<Arc<FileName>> {
+        let declared_modules = self.declared_modules.read();
+        let (pat, _) = best_match(
+            declared_modules.iter().map(|(pat, ..)| &**pat).filter(|pat| pat.contains('*')),
+            module_specifier,
+        )?;
+
+        declared_modules.iter().find(|(p, ..)| &**p == pat).map(|(_, path, _)| path.clone())
+    }
+
+    /// Like `tsc`, `@types` packages are tried first, and then the package
+    /// itself is tried.
+    ///
+    /// If `mode` is given, the package is resolved as if `base` is a module of
+    /// the kind.
+    pub(crate) fn resolve_type_reference(&self, base: &FileName, name: &str, mode: Option<ResolutionMode>) -> Result<Arc<FileName>, Error> {
+        // Resolvers determine the kind of a module using its extension.
+        let base = match (base, mode) {
+            (FileName::Real(path), Some(ResolutionMode::Import)) => FileName::Real(path.with_extension("mts")),
+            (FileName::Real(path), Some(ResolutionMode::Require)) => FileName::Real(path.with_extension("cts")),
+            _ => base.clone(),
+        };
+
+        let resolved = self
+            .resolver
+            .resolve(&base, &format!("@types/{}", types_package_name(name)))
+            .or_else(|_| self.resolver.resolve(&base, name))
+            .with_context(|| format!("failed to resolve type reference `{}`", name))?;
 
         Ok(Arc::new(resolved))
     }
 
-    pub(crate) fn declare_module(&self, decl: JsWord) {
-        log::debug!("Declaring module '{}'", decl);
+    pub(crate) fn declare_module(&self, decl: JsWord, file: &Arc<FileName>) {
+        log::debug!("Declaring module '{}' in {}", decl, file);
 
         self.declared_modules
             .write()
-            .push((decl.clone(), Arc::new(FileName::Custom(decl.to_string()))));
+            .push((decl.clone(), Arc::new(FileName::Custom(decl.to_string())), file.clone()));
+    }
+
+    /// Returns names of modules declared in `file`.
+    pub(crate) fn modules_declared_in(&self, file: &Arc<FileName>) -> Vec<JsWord> {
+        self.declared_modules
+            .read()
+            .iter()
+            .filter(|(.., f)| f == file)
+            .map(|(pat, ..)| pat.clone())
+            .collect()
     }
-}
 
-fn matches(pat: &JsWord, module_specifier: &str) -> bool {
-    if &**pat == module_specifier {
-        return true;
+    /// Forgets modules declared in `file`, so they can be declared again when
+    /// the file is loaded again.
+    pub(crate) fn remove_declared_modules(&self, file: &Arc<FileName>) {
+        self.declared_modules.write().retain(|(.., f)| f != file);
     }
 
-    false
+    /// Returns the file containing `declare module "name"`.
+    pub(crate) fn file_declaring(&self, name: &str) -> Option<Arc<FileName>> {
+        self.declared_modules
+            .read()
+            .iter()
+            .find(|(pat, ..)| &**pat == name)
+            .map(|(.., file)| file.clone())
+    }
+}
+
+fn is_ts_file(path: &Path) -> bool {
+    let name = path.file_name().map(|s| s.to_string_lossy()).unwrap_or_default();
+
+    [".ts", ".tsx", ".mts", ".cts"].iter().any(|ext| name.ends_with(ext))
 }
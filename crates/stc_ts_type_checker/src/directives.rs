@@ -0,0 +1,162 @@
+//! Comment directives which suppress type errors, like `// @ts-ignore`.
+//!
+//! Line semantics follow `tsc`. An error is suppressed by `@ts-ignore` or
+//! `@ts-expect-error` on the nearest preceding line, as long as lines between
+//! them are empty or start with `//`.
+
+use std::sync::Arc;
+
+use fxhash::FxHashMap;
+use stc_ts_errors::Error;
+use swc_common::{
+    comments::{Comment, CommentKind},
+    BytePos, SourceFile, Span, Spanned,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DirectiveKind {
+    Ignore,
+    ExpectError,
+}
+
+#[derive(Debug)]
+struct Directive {
+    kind: DirectiveKind,
+    span: Span,
+    used: bool,
+}
+
+/// Directives of a file.
+pub(crate) struct Directives {
+    file: Arc<SourceFile>,
+    /// `// @ts-nocheck` at the top of the file.
+    no_check: bool,
+    /// Keyed by the (0-based) line where the comment ends.
+    by_line: FxHashMap<usize, Directive>,
+}
+
+impl Directives {
+    /// `comments` should be comments of `file`, sorted by position.
+    /// `first_item` is the position of the first item of the module, and
+    /// comments before it are pragmas.
+    pub fn new(file: Arc<SourceFile>, comments: &[Comment], first_item: Option<BytePos>) -> Self {
+        let mut no_check = false;
+        let mut by_line = FxHashMap::default();
+
+        for c in comments {
+            if first_item.map_or(true, |first| c.span.hi <= first) {
+                match pragma(c) {
+                    Some("ts-nocheck") => no_check = true,
+                    Some("ts-check") => no_check = false,
+                    _ => {}
+                }
+            }
+
+            if let Some(kind) = directive_kind(c) {
+                if let Some(line) = file.lookup_line(c.span.hi) {
+                    by_line.insert(
+                        line,
+                        Directive {
+                            kind,
+                            span: c.span,
+                            used: false,
+                        },
+                    );
+                }
+            }
+        }
+
+        Directives { file, no_check, by_line }
+    }
+
+    /// Removes errors suppressed by directives. Errors of other files are
+    /// preserved.
+    pub fn apply(&mut self, errors: Vec<Error>) -> Vec<Error> {
+        errors
+            .into_iter()
+            .filter(|err| {
+                let pos = err.span().lo;
+                if err.span().is_dummy() || pos < self.file.start_pos || self.file.end_pos < pos {
+                    return true;
+                }
+
+                !self.no_check && !self.is_suppressed(pos)
+            })
+            .collect()
+    }
+
+    /// TS2578 for `@ts-expect-error` which did not suppress any error.
+    pub fn unused(&self) -> Vec<Error> {
+        if self.no_check {
+            return vec![];
+        }
+
+        let mut errors = self
+            .by_line
+            .values()
+            .filter(|d| d.kind == DirectiveKind::ExpectError && !d.used)
+            .map(|d| Error::UnusedTsExpectError { span: d.span })
+            .collect::<Vec<_>>();
+        errors.sort_by_key(|err| err.span().lo);
+        errors
+    }
+
+    fn is_suppressed(&mut self, pos: BytePos) -> bool {
+        let line = match self.file.lookup_line(pos) {
+            Some(line) => line,
+            None => return false,
+        };
+
+        for line in (0..line).rev() {
+            if let Some(directive) = self.by_line.get_mut(&line) {
+                directive.used = true;
+                return true;
+            }
+
+            let text = match self.file.get_line(line) {
+                Some(text) => text,
+                None => return false,
+            };
+            let text = text.trim();
+            if !text.is_empty() && !text.starts_with("//") {
+                return false;
+            }
+        }
+
+        false
+    }
+}
+
+/// `tsc` recognizes `@ts-ignore` and `@ts-expect-error` only at the start of a
+/// comment, or at the start of the last line of a block comment.
+fn directive_kind(c: &Comment) -> Option<DirectiveKind> {
+    let text = match c.kind {
+        CommentKind::Line => c.text.strip_prefix('/').unwrap_or(&*c.text),
+        CommentKind::Block => c.text.lines().last()?.trim_start().trim_start_matches(|c| c == '/' || c == '*'),
+    };
+    let text = text.trim_start();
+
+    if text.starts_with("@ts-expect-error") {
+        Some(DirectiveKind::ExpectError)
+    } else if text.starts_with("@ts-ignore") {
+        Some(DirectiveKind::Ignore)
+    } else {
+        None
+    }
+}
+
+/// Name of a pragma like `// @ts-nocheck`, in lowercase.
+fn pragma(c: &Comment) -> Option<&'static str> {
+    if c.kind != CommentKind::Line {
+        return None;
+    }
+
+    let text = c.text.strip_prefix('/').unwrap_or(&*c.text).trim_start();
+    let name = text.strip_prefix('@')?.split_whitespace().next()?.to_ascii_lowercase();
+
+    match &*name {
+        "ts-nocheck" => Some("ts-nocheck"),
+        "ts-check" => Some("ts-check"),
+        _ => None,
+    }
+}
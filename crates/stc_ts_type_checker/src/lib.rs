@@ -4,29 +4,42 @@
 use std::{mem::take, sync::Arc, time::Instant};
 
 use dashmap::{DashMap, DashSet, SharedValue};
-use fxhash::{FxBuildHasher, FxHashMap};
+use fxhash::{FxBuildHasher, FxHashMap, FxHashSet};
 use once_cell::sync::OnceCell;
 use parking_lot::{Mutex, RwLock};
+use rayon::prelude::*;
 use rnode::{NodeIdGenerator, RNode, VisitWith};
 use stc_ts_ast_rnode::{RModule, RStr, RTsModuleName};
+use stc_ts_builtin_types::{lib_file_name, Lib};
 use stc_ts_dts::{apply_mutations, cleanup_module_for_dts};
-use stc_ts_env::Env;
-use stc_ts_errors::{debug::debugger::Debugger, Error};
-use stc_ts_file_analyzer::{analyzer::Analyzer, loader::Load, validator::ValidateWith, ModuleTypeData, VResult};
+use stc_ts_env::{Env, StableEnv};
+use stc_ts_errors::{debug::debugger::Debugger, Error, Errors};
+use stc_ts_file_analyzer::{
+    analyzer::{Analyzer, PropertyInfo},
+    env::EnvFactory,
+    index::ModuleIndex,
+    loader::Load,
+    validator::ValidateWith,
+    ModuleTypeData, VResult,
+};
 use stc_ts_module_loader::ModuleGraph;
 use stc_ts_storage::{ErrorStore, File, Group, Single};
 use stc_ts_types::{ModuleId, Type};
-use stc_ts_utils::StcComments;
+use stc_ts_utils::{imports::ResolutionMode, StcComments};
 use stc_utils::{cache::Freeze, early_error, panic_ctx};
 use swc_atoms::JsWord;
-use swc_common::{errors::Handler, FileName, SourceMap, Spanned, DUMMY_SP};
+use swc_common::{errors::Handler, FileName, SourceMap, Span, Spanned, TypeEq, DUMMY_SP};
 use swc_ecma_ast::Module;
 use swc_ecma_loader::resolve::Resolve;
-use swc_ecma_parser::TsConfig;
+use swc_ecma_parser::{error::SyntaxError, TsConfig};
 use swc_ecma_transforms::resolver;
 use swc_ecma_visit::FoldWith;
 use tracing::{info, warn};
 
+use crate::{directives::Directives, symbols::ModuleSymbols};
+
+mod directives;
+pub mod symbols;
 mod typings;
 
 /// Onc instance per swc::Compiler
@@ -48,9 +61,28 @@ pub struct Checker {
 
     errors: Mutex<Vec<Error>>,
 
-    env: Env,
+    /// Builtin types can be changed by `/// <reference lib="..." />`.
+    env: RwLock<Env>,
+
+    /// The environment given to [Checker::new], which is used to compute
+    /// `env` again if references of libraries are changed.
+    base_env: Env,
+
+    /// Typings loaded by [Checker::load_typings], which are analyzed before
+    /// entries.
+    typings: Mutex<Vec<Arc<FileName>>>,
+
+    stable_env: StableEnv,
 
     debugger: Option<Debugger>,
+
+    /// If true, [ModuleIndex] and [ModuleSymbols] are recorded for each
+    /// module.
+    record_index: bool,
+
+    indexes: DashMap<ModuleId, Arc<ModuleIndex>, FxBuildHasher>,
+
+    symbols: DashMap<ModuleId, Arc<ModuleSymbols>, FxBuildHasher>,
 }
 
 impl Checker {
@@ -65,19 +97,36 @@ impl Checker {
         cm.new_source_file(FileName::Anon, "".into());
 
         Checker {
-            env: env.clone(),
+            env: RwLock::new(env.clone()),
+            base_env: env.clone(),
+            typings: Default::default(),
+            stable_env: env.shared().clone(),
             cm: cm.clone(),
             handler,
             module_types: Default::default(),
             dts_modules: Default::default(),
-            module_graph: Arc::new(ModuleGraph::new(cm, Default::default(), resolver, parser_config, env.target())),
+            module_graph: Arc::new(
+                ModuleGraph::new(cm, Default::default(), resolver, parser_config, env.target())
+                    .with_jsx_runtime(env.jsx().runtime_module()),
+            ),
             started: Default::default(),
             errors: Default::default(),
             debugger,
             declared_modules: Default::default(),
+            record_index: false,
+            indexes: Default::default(),
+            symbols: Default::default(),
         }
     }
 
+    /// Records [ModuleIndex] and [ModuleSymbols] of modules analyzed after this
+    /// call.
+    ///
+    /// This is used by the language server.
+    pub fn enable_index(&mut self) {
+        self.record_index = true;
+    }
+
     pub fn run<F, R>(&self, op: F) -> R
     where
         F: FnOnce() -> R,
@@ -86,7 +135,7 @@ impl Checker {
     }
 
     pub fn globals(&self) -> &swc_common::Globals {
-        &self.env.shared().swc_globals()
+        self.stable_env.swc_globals()
     }
 }
 
@@ -97,6 +146,45 @@ impl Checker {
         lock.get(&id).and_then(|v| v.get().cloned())
     }
 
+    /// Get information recorded for IDE features, if [Checker::enable_index]
+    /// was called.
+    pub fn index(&self, id: ModuleId) -> Option<Arc<ModuleIndex>> {
+        self.indexes.get(&id).map(|v| v.clone())
+    }
+
+    /// Lists properties of `ty` for completion. `ty` should be a type recorded
+    /// in the [ModuleIndex] of `module`, and `span` should be the span of the
+    /// node.
+    ///
+    /// Returns an empty list if properties cannot be computed.
+    pub fn properties_of(&self, module: ModuleId, span: Span, ty: &Type) -> Vec<PropertyInfo> {
+        self.run(|| {
+            let mut storage = Single {
+                parent: None,
+                id: module,
+                path: self.module_graph.path(module),
+                info: Default::default(),
+                is_dts: false,
+            };
+            let mut a = Analyzer::root(
+                self.env.read().clone(),
+                self.cm.clone(),
+                self.module_graph.comments().clone(),
+                box &mut storage,
+                self,
+                self.debugger.clone(),
+            );
+
+            match a.properties_of(span, ty) {
+                Ok(props) => props,
+                Err(err) => {
+                    warn!("failed to list properties: {:?}", err);
+                    vec![]
+                }
+            }
+        })
+    }
+
     /// Removes dts module from `self` and return it.
     pub fn take_dts(&self, id: ModuleId) -> Option<Module> {
         self.dts_modules.remove(&id).map(|v| v.1.into_orig())
@@ -106,29 +194,203 @@ impl Checker {
         self.module_graph.id(path)
     }
 
+    pub fn path(&self, id: ModuleId) -> Arc<FileName> {
+        self.module_graph.path(id)
+    }
+
+    /// Returns paths of all modules loaded by [Checker::check].
+    pub fn files(&self) -> Vec<Arc<FileName>> {
+        self.module_graph.files()
+    }
+
     /// After calling this method, you can get errors using `.take_errors()`
+    ///
+    /// To check multiple files, use [Checker::check_all] instead, so that
+    /// `/// <reference lib="..." />` of all files are applied before analysis.
     pub fn check(&self, entry: Arc<FileName>) -> ModuleId {
+        self.check_all(&[entry])[0]
+    }
+
+    /// Loads all entries and their dependencies, and analyzes them.
+    ///
+    /// Builtin types are determined by `/// <reference lib="..." />` of all
+    /// loaded modules, before any module is analyzed.
+    pub fn check_all(&self, entries: &[Arc<FileName>]) -> Vec<ModuleId> {
         self.run(|| {
             let start = Instant::now();
 
-            let id = self.module_graph.load_all(&entry);
+            let ids = entries
+                .par_iter()
+                .map(|entry| self.module_graph.load_all(entry).unwrap_or_else(|(id, _)| id))
+                .collect::<Vec<_>>();
 
             let end = Instant::now();
-            log::debug!("Loading of `{}` and dependencies took {:?}", entry, end - start);
+            log::debug!("Loading of {} entries and dependencies took {:?}", entries.len(), end - start);
+
+            self.apply_lib_references();
 
             let start = Instant::now();
 
-            self.analyze_module(None, entry.clone());
+            let typings = take(&mut *self.typings.lock());
+            typings.into_par_iter().for_each(|typing| {
+                self.analyze_module(None, typing);
+            });
+
+            entries.par_iter().for_each(|entry| {
+                self.analyze_module(None, entry.clone());
+            });
 
             let end = Instant::now();
-            log::debug!("Analysis of `{}` and dependencies took {:?}", entry, end - start);
+            log::debug!("Analysis of {} entries and dependencies took {:?}", entries.len(), end - start);
 
-            id.unwrap_or_else(|(id, _)| id)
+            ids
         })
     }
 
+    /// Computes builtin types using libraries referenced by `/// <reference
+    /// lib="..." />` in loaded modules. If a file contains `/// <reference
+    /// no-default-lib="true" />`, only referenced libraries are used.
+    ///
+    /// Returns `true` if builtin types are changed.
+    fn apply_lib_references(&self) -> bool {
+        let (names, no_default_lib) = self.module_graph.lib_references();
+
+        let env = if names.is_empty() && !no_default_lib {
+            self.base_env.clone()
+        } else {
+            let mut libs = match (no_default_lib, self.base_env.libs()) {
+                (true, _) => vec![],
+                (false, Some(libs)) => libs.to_vec(),
+                // Builtin types cannot be extended.
+                (false, None) => return false,
+            };
+            libs.extend(names.iter().flat_map(|name| Lib::load(&lib_file_name(name))));
+            libs.sort();
+            libs.dedup();
+
+            info!("Using libraries referenced by files: {:?}", libs);
+            let builtin = Env::load_builtin(&libs);
+            self.base_env.clone().with_libs(libs).with_builtin(builtin)
+        };
+
+        let mut cur = self.env.write();
+        if cur.libs() == env.libs() {
+            return false;
+        }
+        *cur = env;
+        true
+    }
+
+    /// Returns syntax errors and type errors found so far.
     pub fn take_errors(&mut self) -> Vec<Error> {
-        take(self.errors.get_mut())
+        let mut errors = vec![];
+
+        // A module which fails to parse is loaded multiple times.
+        let mut seen = FxHashSet::default();
+        for err in self.module_graph.take_parsing_errors() {
+            if !seen.insert((err.span(), syntax_error_code(err.kind()))) {
+                continue;
+            }
+
+            errors.push(syntax_error(&err));
+        }
+
+        errors.extend(take(self.errors.get_mut()));
+        errors
+    }
+
+    /// Checks the module at `path` again after it's changed.
+    ///
+    /// Dependents of the module are checked again only if exports of a
+    /// dependency are changed.
+    ///
+    /// Returns the modules which are analyzed again. Errors of them can be
+    /// taken using [Checker::take_errors], and previous errors of them should
+    /// be discarded by the caller.
+    pub fn invalidate(&self, path: &Arc<FileName>) -> Vec<ModuleId> {
+        self.run(|| {
+            let id = self.module_graph.id(path);
+
+            // Modules declared in the file are declared again while analyzing it.
+            let declared = self.module_graph.modules_declared_in(path);
+            self.declared_modules.write().retain(|(id, _)| !declared.contains(id));
+
+            let mut affected = self.module_graph.invalidate(path);
+
+            // All modules depend on builtin types.
+            let force = self.apply_lib_references();
+            if force {
+                affected = self.module_graph.files().iter().map(|path| self.module_graph.id(path)).collect();
+            }
+
+            // Declared modules are not analyzed by themselves, and their dependents should
+            // be analyzed again.
+            let mut changed = declared.iter().map(|&id| (id, true)).collect();
+            let mut analyzed = vec![];
+            self.reanalyze(id, &affected, true, &mut changed, &mut analyzed);
+
+            for &dependent in &affected {
+                self.reanalyze(dependent, &affected, force, &mut changed, &mut analyzed);
+            }
+
+            analyzed
+        })
+    }
+
+    /// Analyzes `id` again if `force` is true or exports of a dependency of it
+    /// are changed.
+    ///
+    /// Returns `true` if exports of `id` are changed.
+    fn reanalyze(
+        &self,
+        id: ModuleId,
+        affected: &[ModuleId],
+        force: bool,
+        changed: &mut FxHashMap<ModuleId, bool>,
+        analyzed: &mut Vec<ModuleId>,
+    ) -> bool {
+        if let Some(&v) = changed.get(&id) {
+            return v;
+        }
+        // Modules in a cycle are analyzed at once.
+        changed.insert(id, false);
+
+        let mut needs_work = force;
+        for dep in self.module_graph.deps_of_module(id) {
+            if affected.contains(&dep) {
+                needs_work |= self.reanalyze(dep, affected, false, changed, analyzed);
+            }
+        }
+        if !needs_work {
+            return false;
+        }
+
+        let group = self.module_graph.get_circular(id).unwrap_or_else(|| vec![id]);
+        let prev = group.iter().map(|&id| self.get_types(id)).collect::<Vec<_>>();
+
+        for &id in &group {
+            self.module_types.write().remove(&id);
+            self.dts_modules.remove(&id);
+            self.started.remove(&id);
+            self.indexes.remove(&id);
+            self.symbols.remove(&id);
+        }
+
+        self.analyze_module(None, self.module_graph.path(id));
+
+        for (&id, prev) in group.iter().zip(prev) {
+            let is_changed = match (prev, self.get_types(id)) {
+                (Some(prev), Some(cur)) => !exports_eq(&prev, &cur),
+                _ => true,
+            };
+
+            changed.insert(id, is_changed);
+            if !analyzed.contains(&id) {
+                analyzed.push(id);
+            }
+        }
+
+        changed[&id]
     }
 
     /// Analyzes one module.
@@ -185,28 +447,40 @@ impl Checker {
                             .map(|&id| (id, self.module_graph.clone_module(id)))
                             .filter_map(|m| m.1.map(|v| (m.0, v)))
                             .map(|(module_id, module)| {
-                                RModule::from_orig(
-                                    &mut node_id_gen,
-                                    module.fold_with(&mut resolver(
-                                        self.env.shared().marks().unresolved_mark(),
-                                        self.module_graph.top_level_mark(module_id),
-                                        true,
-                                    )),
-                                )
+                                let module = module.fold_with(&mut resolver(
+                                    self.stable_env.marks().unresolved_mark(),
+                                    self.module_graph.top_level_mark(module_id),
+                                    true,
+                                ));
+                                if self.record_index {
+                                    self.symbols.insert(module_id, Arc::new(ModuleSymbols::new(&module)));
+                                }
+
+                                RModule::from_orig(&mut node_id_gen, module)
                             })
                             .collect::<Vec<_>>();
                         let mut mutations;
                         {
                             let mut a = Analyzer::root(
-                                self.env.clone(),
+                                self.env.read().clone(),
                                 self.cm.clone(),
                                 self.module_graph.comments().clone(),
                                 box &mut storage,
                                 self,
                                 self.debugger.clone(),
                             );
+                            if self.record_index {
+                                a.index = Some(Default::default());
+                            }
                             let _ = modules.validate_with(&mut a);
                             mutations = a.mutations.unwrap();
+
+                            if let Some(index) = a.index.take() {
+                                let index = Arc::new(index);
+                                for &id in &ids {
+                                    self.indexes.insert(id, index.clone());
+                                }
+                            }
                         }
 
                         for (id, mut dts_module) in ids.iter().zip(modules) {
@@ -227,8 +501,8 @@ impl Checker {
                         }
 
                         {
-                            let mut lock = self.errors.lock();
-                            lock.extend(storage.take_errors());
+                            let errors = self.apply_directives(&ids, storage.take_errors());
+                            self.errors.lock().extend(errors);
                         }
                         {
                             let mut lock = self.module_types.write();
@@ -298,7 +572,9 @@ impl Checker {
             let start = Instant::now();
 
             let is_dts = match &*path {
-                FileName::Real(path) => path.to_string_lossy().ends_with(".d.ts"),
+                FileName::Real(path) => [".d.ts", ".d.mts", ".d.cts"]
+                    .iter()
+                    .any(|ext| path.to_string_lossy().ends_with(ext)),
                 _ => false,
             };
 
@@ -308,13 +584,17 @@ impl Checker {
                 .clone_module(module_id)
                 .unwrap_or_else(|| unreachable!("Module graph does not contains {:?}: {}", module_id, path));
             module = module.fold_with(&mut resolver(
-                self.env.shared().marks().unresolved_mark(),
+                self.stable_env.marks().unresolved_mark(),
                 self.module_graph.top_level_mark(module_id),
                 true,
             ));
 
             let _panic = panic_ctx!(format!("Span of module = ({:?})", module.span));
 
+            if self.record_index {
+                self.symbols.insert(module_id, Arc::new(ModuleSymbols::new(&module)));
+            }
+
             let mut module = RModule::from_orig(&mut node_id_gen, module);
 
             let mut storage = Single {
@@ -328,13 +608,16 @@ impl Checker {
             {
                 let start = Instant::now();
                 let mut a = Analyzer::root(
-                    self.env.clone(),
+                    self.env.read().clone(),
                     self.cm.clone(),
                     self.module_graph.comments().clone(),
                     box &mut storage,
                     self,
                     self.debugger.clone(),
                 );
+                if self.record_index {
+                    a.index = Some(Default::default());
+                }
 
                 module.visit_with(&mut a);
 
@@ -343,6 +626,10 @@ impl Checker {
                 log::debug!("[Timing] Analysis of {} took {:?}", path, dur);
 
                 mutations = a.mutations.unwrap();
+
+                if let Some(index) = a.index.take() {
+                    self.indexes.insert(module_id, Arc::new(index));
+                }
             }
 
             {
@@ -351,13 +638,13 @@ impl Checker {
                 cleanup_module_for_dts(&mut module.body, &storage.info.exports);
             }
 
+            let errors = self.apply_directives(&[module_id], take(&mut storage.info.errors));
             if early_error() {
-                for err in storage.info.errors {
+                for err in errors {
                     self.handler.struct_span_err(err.span(), &format!("{:?}", err)).emit();
                 }
             } else {
-                let mut errors = self.errors.lock();
-                errors.extend(storage.info.errors);
+                self.errors.lock().extend(errors);
             }
 
             let type_info = Type::Module(stc_ts_types::Module {
@@ -380,6 +667,26 @@ impl Checker {
             type_info
         })
     }
+
+    /// Applies comment directives like `// @ts-ignore` in `modules` to
+    /// `errors`, and reports unused `// @ts-expect-error`.
+    fn apply_directives(&self, modules: &[ModuleId], errors: Errors) -> Vec<Error> {
+        let mut errors = Error::flatten(errors.into());
+
+        for &id in modules {
+            let path = self.module_graph.path(id);
+            let file = match self.cm.get_source_file(&path) {
+                Some(file) => file,
+                None => continue,
+            };
+
+            let mut directives = Directives::new(file, &self.module_graph.comments_of(&path), self.module_graph.first_item_pos(id));
+            errors = directives.apply(errors);
+            errors.extend(directives.unused());
+        }
+
+        errors
+    }
 }
 
 impl Load for Checker {
@@ -389,6 +696,11 @@ impl Load for Checker {
         Some(id)
     }
 
+    fn module_id_of_type_reference(&self, base: &Arc<FileName>, name: &JsWord, mode: Option<ResolutionMode>) -> Option<ModuleId> {
+        let path = self.module_graph.resolve_type_reference(&base, name, mode).ok()?;
+        Some(self.module_graph.id(&path))
+    }
+
     fn is_in_same_circular_group(&self, base: ModuleId, dep: ModuleId) -> bool {
         let circular_set = self.module_graph.get_circular(base);
 
@@ -412,15 +724,29 @@ impl Load for Checker {
         let dep_path = self.module_graph.path(dep);
 
         if matches!(&*dep_path, FileName::Custom(..)) {
-            let ty = self
-                .declared_modules
-                .read()
-                .iter()
-                .find_map(|(v, ty)| if *v == dep { Some(ty.clone()) } else { None });
+            let declared = || {
+                self.declared_modules
+                    .read()
+                    .iter()
+                    .find_map(|(v, ty)| if *v == dep { Some(ty.clone()) } else { None })
+            };
 
-            if let Some(ty) = ty {
+            if let Some(ty) = declared() {
                 return Ok(ty);
             }
+
+            // The file containing `declare module` is not analyzed yet. This is common for
+            // wildcard patterns like `declare module "*.svg"` in a global declaration file.
+            if let Some(file) = self.module_graph.file_declaring_module(dep) {
+                let file_id = self.module_graph.id(&file);
+                if file_id != base && !self.started.contains(&file_id) {
+                    self.analyze_module(Some(base_path.clone()), file);
+
+                    if let Some(ty) = declared() {
+                        return Ok(ty);
+                    }
+                }
+            }
         }
 
         info!("({}): Loading {}", base_path, dep_path);
@@ -439,3 +765,104 @@ impl Load for Checker {
         self.declared_modules.write().push((module_id, module));
     }
 }
+
+/// Converts an error of the parser to [Error::SyntaxError].
+pub fn syntax_error(err: &swc_ecma_parser::error::Error) -> Error {
+    Error::SyntaxError {
+        span: err.span(),
+        code: syntax_error_code(err.kind()),
+        msg: err.kind().msg().into_owned(),
+    }
+}
+
+/// Returns the code of tsc for a syntax error.
+///
+/// Variants of [SyntaxError] with a dedicated code in tsc are named after the
+/// code. Other errors are reported as TS1005 (`'{0}' expected.`), which is
+/// the code used by tsc for most unexpected tokens.
+fn syntax_error_code(kind: &SyntaxError) -> usize {
+    match kind {
+        SyntaxError::TS1003 => 1003,
+        SyntaxError::TS1005 => 1005,
+        SyntaxError::TS1009 => 1009,
+        SyntaxError::TS1014 => 1014,
+        SyntaxError::TS1015 => 1015,
+        SyntaxError::TS1029(..) => 1029,
+        SyntaxError::TS1030(..) => 1030,
+        SyntaxError::TS1031 => 1031,
+        SyntaxError::TS1038 => 1038,
+        SyntaxError::TS1042 => 1042,
+        SyntaxError::TS1047 => 1047,
+        SyntaxError::TS1048 => 1048,
+        SyntaxError::TS1056 => 1056,
+        SyntaxError::TS1085 => 1085,
+        SyntaxError::TS1089(..) => 1089,
+        SyntaxError::TS1092 => 1092,
+        SyntaxError::TS1093 => 1093,
+        SyntaxError::TS1096 => 1096,
+        SyntaxError::TS1098 => 1098,
+        SyntaxError::TS1100 => 1100,
+        SyntaxError::TS1102 => 1102,
+        SyntaxError::TS1105 => 1105,
+        SyntaxError::TS1106 => 1106,
+        SyntaxError::TS1107 => 1107,
+        SyntaxError::TS1109 => 1109,
+        SyntaxError::TS1110 => 1110,
+        SyntaxError::TS1114 => 1114,
+        SyntaxError::TS1115 => 1115,
+        SyntaxError::TS1116 => 1116,
+        SyntaxError::TS1123 => 1123,
+        SyntaxError::TS1141 => 1141,
+        SyntaxError::TS1162 => 1162,
+        SyntaxError::TS1164 => 1164,
+        SyntaxError::TS1171 => 1171,
+        SyntaxError::TS1172 => 1172,
+        SyntaxError::TS1173 => 1173,
+        SyntaxError::TS1174 => 1174,
+        SyntaxError::TS1175 => 1175,
+        SyntaxError::TS1183 => 1183,
+        SyntaxError::TS1184 => 1184,
+        SyntaxError::TS1196 => 1196,
+        SyntaxError::TS1242 => 1242,
+        SyntaxError::TS1243(..) => 1243,
+        SyntaxError::TS1244 => 1244,
+        SyntaxError::TS1245 => 1245,
+        SyntaxError::TS1267 => 1267,
+        SyntaxError::TS1273(..) => 1273,
+        SyntaxError::TS1274(..) => 1274,
+        SyntaxError::TS1277(..) => 1277,
+        SyntaxError::TS2206 => 2206,
+        SyntaxError::TS2207 => 2207,
+        SyntaxError::TS2369 => 2369,
+        SyntaxError::TS2371 => 2371,
+        SyntaxError::TS2406 => 2406,
+        SyntaxError::TS2410 => 2410,
+        SyntaxError::TS2414 => 2414,
+        SyntaxError::TS2427 => 2427,
+        SyntaxError::TS2452 => 2452,
+        SyntaxError::TS2483 => 2483,
+        SyntaxError::TS2491 => 2491,
+        SyntaxError::TS2499 => 2499,
+        SyntaxError::TS2703 => 2703,
+        SyntaxError::TS4112 => 4112,
+        SyntaxError::TS8038 => 8038,
+        _ => 1005,
+    }
+}
+
+/// Compares exports of module types, ignoring spans.
+fn exports_eq(l: &Type, r: &Type) -> bool {
+    fn map_eq<T>(l: &FxHashMap<JsWord, T>, r: &FxHashMap<JsWord, T>, eq: impl Fn(&T, &T) -> bool) -> bool {
+        l.len() == r.len() && l.iter().all(|(k, l)| r.get(k).map(|r| eq(l, r)).unwrap_or(false))
+    }
+
+    match (l.normalize(), r.normalize()) {
+        (Type::Module(l), Type::Module(r)) => {
+            map_eq(&l.exports.vars, &r.exports.vars, |l, r| l.type_eq(r))
+                && map_eq(&l.exports.types, &r.exports.types, |l, r| {
+                    l.len() == r.len() && l.iter().zip(r).all(|(l, r)| l.type_eq(r))
+                })
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,443 @@
+//! Symbol tables used for go-to-definition and find-references.
+//!
+//! The tables are built from modules processed by the resolver, so
+//! identifiers can be compared using [Id]. Imports and exports are resolved
+//! using [ModuleIndex](stc_ts_file_analyzer::index::ModuleIndex)es recorded by
+//! the analyzer.
+
+use std::sync::Arc;
+
+use fxhash::{FxHashMap, FxHashSet};
+use stc_ts_file_analyzer::index::{Export, ImportedName};
+use stc_ts_types::ModuleId;
+use swc_atoms::JsWord;
+use swc_common::{BytePos, Span};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::Checker;
+
+/// A binding, identified by the module which declared it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Symbol {
+    pub module: ModuleId,
+    pub id: Id,
+}
+
+/// Identifiers of a module.
+///
+/// Imports and exports are not stored here, because the analyzer records
+/// them in [ModuleIndex](stc_ts_file_analyzer::index::ModuleIndex) with the
+/// modules it resolved.
+#[derive(Debug, Default)]
+pub struct ModuleSymbols {
+    /// Spans of declarations.
+    pub decls: FxHashMap<Id, Vec<Span>>,
+
+    /// All identifiers referring to a binding, including declarations.
+    pub idents: Vec<(Span, Id)>,
+
+    /// Properties accessed on identifiers, like `ns.foo` or `ns.Foo`, which
+    /// refer to exports if the object is a namespace import.
+    pub members: Vec<(Span, Id, JsWord)>,
+}
+
+impl ModuleSymbols {
+    /// `module` should be processed by the resolver.
+    pub fn new(module: &Module) -> Self {
+        let mut v = SymbolCollector::default();
+        module.visit_with(&mut v);
+
+        v.symbols
+    }
+
+    /// Returns the innermost identifier containing `pos`.
+    pub fn ident_at(&self, pos: BytePos) -> Option<&(Span, Id)> {
+        self.idents
+            .iter()
+            .filter(|(span, _)| span.lo <= pos && pos <= span.hi)
+            .min_by_key(|(span, _)| span.hi - span.lo)
+    }
+
+    /// Returns the property of a member access at `pos`.
+    pub fn member_at(&self, pos: BytePos) -> Option<&(Span, Id, JsWord)> {
+        self.members.iter().find(|(span, ..)| span.lo <= pos && pos <= span.hi)
+    }
+}
+
+#[derive(Default)]
+struct SymbolCollector {
+    symbols: ModuleSymbols,
+    /// True while visiting a pattern which declares bindings.
+    in_decl: bool,
+}
+
+impl SymbolCollector {
+    fn reference(&mut self, i: &Ident) {
+        self.symbols.idents.push((i.span, i.to_id()));
+    }
+
+    fn declare(&mut self, i: &Ident) {
+        self.symbols.decls.entry(i.to_id()).or_default().push(i.span);
+        self.reference(i);
+    }
+
+    fn with_decl(&mut self, in_decl: bool, op: impl FnOnce(&mut Self)) {
+        let old = self.in_decl;
+        self.in_decl = in_decl;
+        op(self);
+        self.in_decl = old;
+    }
+
+    fn member(&mut self, obj: &Ident, prop: &Ident) {
+        self.symbols.members.push((prop.span, obj.to_id(), prop.sym.clone()));
+    }
+}
+
+impl Visit for SymbolCollector {
+    fn visit_arrow_expr(&mut self, n: &ArrowExpr) {
+        self.with_decl(true, |v| n.params.visit_with(v));
+        self.with_decl(false, |v| {
+            n.body.visit_with(v);
+            n.type_params.visit_with(v);
+            n.return_type.visit_with(v);
+        });
+    }
+
+    fn visit_assign_pat(&mut self, n: &AssignPat) {
+        n.left.visit_with(self);
+        self.with_decl(false, |v| {
+            n.right.visit_with(v);
+            n.type_ann.visit_with(v);
+        });
+    }
+
+    fn visit_assign_pat_prop(&mut self, n: &AssignPatProp) {
+        if self.in_decl {
+            self.declare(&n.key);
+        } else {
+            self.reference(&n.key);
+        }
+        self.with_decl(false, |v| n.value.visit_with(v));
+    }
+
+    fn visit_binding_ident(&mut self, n: &BindingIdent) {
+        if self.in_decl {
+            self.declare(&n.id);
+        } else {
+            self.reference(&n.id);
+        }
+        self.with_decl(false, |v| n.type_ann.visit_with(v));
+    }
+
+    fn visit_break_stmt(&mut self, _: &BreakStmt) {}
+
+    fn visit_catch_clause(&mut self, n: &CatchClause) {
+        self.with_decl(true, |v| n.param.visit_with(v));
+        n.body.visit_with(self);
+    }
+
+    fn visit_class_decl(&mut self, n: &ClassDecl) {
+        self.declare(&n.ident);
+        n.class.visit_with(self);
+    }
+
+    fn visit_class_expr(&mut self, n: &ClassExpr) {
+        if let Some(i) = &n.ident {
+            self.declare(i);
+        }
+        n.class.visit_with(self);
+    }
+
+    fn visit_continue_stmt(&mut self, _: &ContinueStmt) {}
+
+    fn visit_export_named_specifier(&mut self, n: &ExportNamedSpecifier) {
+        // Exported names are not bindings.
+        if let ModuleExportName::Ident(i) = &n.orig {
+            self.reference(i);
+        }
+    }
+
+    fn visit_expr(&mut self, n: &Expr) {
+        // Expressions in patterns are not declarations.
+        self.with_decl(false, |v| n.visit_children_with(v));
+    }
+
+    fn visit_fn_decl(&mut self, n: &FnDecl) {
+        self.declare(&n.ident);
+        n.function.visit_with(self);
+    }
+
+    fn visit_fn_expr(&mut self, n: &FnExpr) {
+        if let Some(i) = &n.ident {
+            self.declare(i);
+        }
+        n.function.visit_with(self);
+    }
+
+    fn visit_ident(&mut self, n: &Ident) {
+        self.reference(n);
+    }
+
+    fn visit_import_decl(&mut self, n: &ImportDecl) {
+        for s in &n.specifiers {
+            match s {
+                ImportSpecifier::Named(s) => self.declare(&s.local),
+                ImportSpecifier::Default(s) => self.declare(&s.local),
+                ImportSpecifier::Namespace(s) => self.declare(&s.local),
+            }
+        }
+    }
+
+    fn visit_jsx_attr_name(&mut self, _: &JSXAttrName) {}
+
+    fn visit_jsx_member_expr(&mut self, n: &JSXMemberExpr) {
+        n.obj.visit_with(self);
+    }
+
+    fn visit_labeled_stmt(&mut self, n: &LabeledStmt) {
+        n.body.visit_with(self);
+    }
+
+    fn visit_member_expr(&mut self, n: &MemberExpr) {
+        if let (Expr::Ident(obj), MemberProp::Ident(prop)) = (&*n.obj, &n.prop) {
+            self.member(obj, prop);
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_member_prop(&mut self, n: &MemberProp) {
+        if let MemberProp::Computed(c) = n {
+            c.visit_with(self);
+        }
+    }
+
+    fn visit_named_export(&mut self, n: &NamedExport) {
+        // Names of re-exports are not bindings of this module.
+        if n.src.is_none() {
+            n.visit_children_with(self);
+        }
+    }
+
+    fn visit_param(&mut self, n: &Param) {
+        self.with_decl(false, |v| n.decorators.visit_with(v));
+        self.with_decl(true, |v| n.pat.visit_with(v));
+    }
+
+    fn visit_prop_name(&mut self, n: &PropName) {
+        if let PropName::Computed(c) = n {
+            c.visit_with(self);
+        }
+    }
+
+    fn visit_super_prop(&mut self, n: &SuperProp) {
+        if let SuperProp::Computed(c) = n {
+            c.visit_with(self);
+        }
+    }
+
+    fn visit_ts_enum_decl(&mut self, n: &TsEnumDecl) {
+        self.declare(&n.id);
+        n.members.visit_with(self);
+    }
+
+    fn visit_ts_enum_member(&mut self, n: &TsEnumMember) {
+        n.init.visit_with(self);
+    }
+
+    fn visit_ts_getter_signature(&mut self, n: &TsGetterSignature) {
+        if n.computed {
+            n.key.visit_with(self);
+        }
+        n.type_ann.visit_with(self);
+    }
+
+    fn visit_ts_import_equals_decl(&mut self, n: &TsImportEqualsDecl) {
+        self.declare(&n.id);
+        if let TsModuleRef::TsEntityName(name) = &n.module_ref {
+            name.visit_with(self);
+        }
+    }
+
+    fn visit_ts_import_type(&mut self, n: &TsImportType) {
+        n.type_args.visit_with(self);
+    }
+
+    fn visit_ts_interface_decl(&mut self, n: &TsInterfaceDecl) {
+        self.declare(&n.id);
+        n.type_params.visit_with(self);
+        n.extends.visit_with(self);
+        n.body.visit_with(self);
+    }
+
+    fn visit_ts_method_signature(&mut self, n: &TsMethodSignature) {
+        if n.computed {
+            n.key.visit_with(self);
+        }
+        n.type_params.visit_with(self);
+        self.with_decl(true, |v| n.params.visit_with(v));
+        n.type_ann.visit_with(self);
+    }
+
+    fn visit_ts_module_decl(&mut self, n: &TsModuleDecl) {
+        if let TsModuleName::Ident(i) = &n.id {
+            self.declare(i);
+        }
+        n.body.visit_with(self);
+    }
+
+    fn visit_ts_param_prop_param(&mut self, n: &TsParamPropParam) {
+        self.with_decl(true, |v| n.visit_children_with(v));
+    }
+
+    fn visit_ts_property_signature(&mut self, n: &TsPropertySignature) {
+        if n.computed {
+            n.key.visit_with(self);
+        }
+        n.init.visit_with(self);
+        n.type_ann.visit_with(self);
+    }
+
+    fn visit_ts_qualified_name(&mut self, n: &TsQualifiedName) {
+        if let TsEntityName::Ident(obj) = &n.left {
+            self.member(obj, &n.right);
+        }
+        n.left.visit_with(self);
+    }
+
+    fn visit_ts_setter_signature(&mut self, n: &TsSetterSignature) {
+        if n.computed {
+            n.key.visit_with(self);
+        }
+        self.with_decl(true, |v| n.param.visit_with(v));
+    }
+
+    fn visit_ts_type_alias_decl(&mut self, n: &TsTypeAliasDecl) {
+        self.declare(&n.id);
+        n.type_params.visit_with(self);
+        n.type_ann.visit_with(self);
+    }
+
+    fn visit_ts_type_param(&mut self, n: &TsTypeParam) {
+        self.declare(&n.name);
+        n.constraint.visit_with(self);
+        n.default.visit_with(self);
+    }
+
+    fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+        self.with_decl(true, |v| n.name.visit_with(v));
+        self.with_decl(false, |v| n.init.visit_with(v));
+    }
+}
+
+impl Checker {
+    /// Get the symbol table of a module, if [Checker::enable_index] was called.
+    pub fn symbols(&self, id: ModuleId) -> Option<Arc<ModuleSymbols>> {
+        self.symbols.get(&id).map(|v| v.clone())
+    }
+
+    /// Returns the symbol referenced by the identifier at `pos`.
+    ///
+    /// Imported bindings are resolved to the declaration in the exporting
+    /// module.
+    pub fn symbol_at(&self, module: ModuleId, pos: BytePos) -> Option<Symbol> {
+        let symbols = self.symbols(module)?;
+
+        if let Some((_, obj, prop)) = symbols.member_at(pos) {
+            if let Some(symbol) = self.resolve_member(module, obj, prop) {
+                return Some(symbol);
+            }
+        }
+
+        let (_, id) = symbols.ident_at(pos)?;
+
+        Some(self.resolve_local(module, id.clone(), &mut Default::default()))
+    }
+
+    /// Spans of the declarations of `symbol`.
+    pub fn definitions(&self, symbol: &Symbol) -> Vec<Span> {
+        self.symbols(symbol.module)
+            .and_then(|symbols| symbols.decls.get(&symbol.id).cloned())
+            .unwrap_or_default()
+    }
+
+    /// Spans of identifiers referring to `symbol`, including declarations.
+    ///
+    /// Only modules loaded by this checker are searched.
+    pub fn references(&self, symbol: &Symbol) -> Vec<Span> {
+        let modules = self.symbols.iter().map(|e| (*e.key(), e.value().clone())).collect::<Vec<_>>();
+
+        let mut spans = vec![];
+        for (module, symbols) in modules {
+            let mut cache: FxHashMap<Id, bool> = Default::default();
+
+            for (span, id) in &symbols.idents {
+                let matches = *cache
+                    .entry(id.clone())
+                    .or_insert_with(|| self.resolve_local(module, id.clone(), &mut Default::default()) == *symbol);
+
+                if matches {
+                    spans.push(*span);
+                }
+            }
+
+            let mut cache: FxHashMap<(Id, JsWord), bool> = Default::default();
+
+            for (span, obj, prop) in &symbols.members {
+                let matches = *cache
+                    .entry((obj.clone(), prop.clone()))
+                    .or_insert_with(|| self.resolve_member(module, obj, prop).as_ref() == Some(symbol));
+
+                if matches {
+                    spans.push(*span);
+                }
+            }
+        }
+
+        spans.sort_by_key(|span| span.lo);
+        spans.dedup();
+        spans
+    }
+
+    fn resolve_local(&self, module: ModuleId, id: Id, visited: &mut FxHashSet<(ModuleId, JsWord)>) -> Symbol {
+        let local = Symbol { module, id: id.clone() };
+
+        let index = match self.index(module) {
+            Some(index) => index,
+            None => return local,
+        };
+
+        match index.import_of(&to_type_id(&id)) {
+            Some((dep, ImportedName::Named(name))) => self.resolve_export(*dep, name, visited).unwrap_or(local),
+            _ => local,
+        }
+    }
+
+    /// Resolves `obj.prop` if `obj` is a namespace import.
+    fn resolve_member(&self, module: ModuleId, obj: &Id, prop: &JsWord) -> Option<Symbol> {
+        let index = self.index(module)?;
+
+        match index.import_of(&to_type_id(obj))? {
+            (dep, ImportedName::Namespace) => self.resolve_export(*dep, prop, &mut Default::default()),
+            _ => None,
+        }
+    }
+
+    fn resolve_export(&self, module: ModuleId, name: &JsWord, visited: &mut FxHashSet<(ModuleId, JsWord)>) -> Option<Symbol> {
+        if !visited.insert((module, name.clone())) {
+            return None;
+        }
+
+        let index = self.index(module)?;
+
+        match index.export_of(name) {
+            Some(Export::Local(id)) => Some(self.resolve_local(module, (id.sym().clone(), id.ctxt()), visited)),
+            Some(Export::Reexport { module: dep, name }) => self.resolve_export(*dep, name, visited),
+            None => index.star_exports().iter().find_map(|&dep| self.resolve_export(dep, name, visited)),
+        }
+    }
+}
+
+fn to_type_id(id: &Id) -> stc_ts_types::Id {
+    stc_ts_types::Id::new(id.0.clone(), id.1)
+}
@@ -6,73 +6,140 @@ use std::{
 };
 
 use rayon::prelude::*;
-use stc_ts_module_loader::resolvers::node::NodeResolver;
-use swc_common::FileName;
+use stc_ts_errors::Error;
+use stc_ts_module_loader::resolvers::{node::NodeResolver, types_package_name};
+use swc_common::{FileName, DUMMY_SP};
 
 use crate::Checker;
 
 impl Checker {
-    fn try_loading_typing_of_one_package(&self, dir: &Path) {
-        if !dir.is_dir() {
-            return;
-        }
-
-        let result = NodeResolver
-            .resolve_as_file(&dir)
-            .or_else(|_| NodeResolver.resolve_as_directory(&dir));
-
-        match result {
-            Ok(entry) => {
-                let entry = Arc::new(FileName::Real(entry));
-                let start = Instant::now();
-                self.module_graph.load_all(&entry).unwrap();
-
-                self.analyze_module(None, entry.clone());
-
-                let end = Instant::now();
-                log::debug!("Loading typings at `{}` took {:?}", dir.display(), end - start);
+    fn load_typing(&self, name: String, entry: PathBuf) {
+        let entry = Arc::new(FileName::Real(entry));
+        let start = Instant::now();
+        if let Err((id, err)) = self.module_graph.load_all(&entry) {
+            // Errors of the module graph are shared, so `err` may be about a module
+            // loaded by another package. Unresolved imports are reported by the
+            // analyzer, so only the package itself is reported here.
+            log::debug!("Failed to load typings at `{}`: {:?}", entry, err);
+
+            if !self.module_graph.is_loaded(id) {
+                self.errors.lock().push(Error::TypeDefinitionNotFound {
+                    span: DUMMY_SP,
+                    name: name.into(),
+                });
+                return;
             }
-            Err(_) => {}
-        }
-    }
-
-    fn load_typings_from_dir(&self, dir: &Path, types: Option<&[String]>) {
-        let types_dir = dir.join("node_modules").join("@types");
-
-        if !types_dir.is_dir() {
-            return Default::default();
         }
 
-        let dirs = types.map(|s| s.iter().map(|s| PathBuf::from(s.clone())).collect()).or_else(|| {
-            let pkgs = read_dir(&types_dir).ok()?;
+        // Typings are analyzed by `check_all`, after references of libraries are
+        // applied.
+        self.typings.lock().push(entry.clone());
 
-            let f = pkgs.into_iter().filter_map(Result::ok).map(|e| e.path()).collect::<Vec<_>>();
-
-            Some(f)
-        });
+        let end = Instant::now();
+        log::debug!("Loading typings at `{}` took {:?}", entry, end - start);
+    }
 
-        if let Some(dirs) = dirs {
-            dirs.into_par_iter().for_each(|dir| {
-                self.try_loading_typing_of_one_package(&types_dir.join(dir));
-            });
-        }
+    /// Returns the entry file of the type package `name`, using the nearest
+    /// type root containing it.
+    fn resolve_in_type_roots(&self, roots: &[PathBuf], name: &str) -> Option<PathBuf> {
+        roots
+            .iter()
+            .map(|root| {
+                // Like `tsc`, scoped packages are mangled only in `node_modules/@types`.
+                if root.ends_with("node_modules/@types") {
+                    root.join(types_package_name(name))
+                } else {
+                    root.join(name)
+                }
+            })
+            .filter(|dir| dir.is_dir())
+            .find_map(|dir| {
+                NodeResolver
+                    .resolve_as_file(&dir)
+                    .or_else(|_| NodeResolver.resolve_as_directory(&dir))
+                    .ok()
+            })
     }
 
     /// Load typings from node_modules.
     ///
+    /// If `type_roots` is [None], `node_modules/@types` of `base` and its
+    /// ancestors are used. If `types` is [None], all packages in type roots
+    /// are loaded, and a package found in several type roots is loaded from
+    /// the nearest one.
+    ///
+    /// Packages which cannot be resolved are reported as errors. Loaded
+    /// typings are analyzed by the next call to [Checker::check_all].
+    ///
     /// - https://www.typescriptlang.org/tsconfig#typeRoots
     /// - https://www.typescriptlang.org/tsconfig#types
-    pub fn load_typings(&self, base: &Path, _type_roots: Option<&[PathBuf]>, types: Option<&[String]>) {
-        let mut dirs = vec![];
-
-        let mut cur = Some(base);
-        while let Some(c) = cur {
-            dirs.push(c.to_path_buf());
-            cur = c.parent();
+    pub fn load_typings(&self, base: &Path, type_roots: Option<&[PathBuf]>, types: Option<&[String]>) {
+        let roots = match type_roots {
+            Some(roots) => roots.to_vec(),
+            None => base.ancestors().map(|dir| dir.join("node_modules").join("@types")).collect(),
+        };
+
+        let explicit = types.is_some();
+        let names = match types {
+            Some(types) => types.to_vec(),
+            None => {
+                let mut names = vec![];
+                for root in &roots {
+                    let entries = match read_dir(root) {
+                        Ok(entries) => entries,
+                        Err(_) => continue,
+                    };
+
+                    for entry in entries.filter_map(Result::ok) {
+                        let name = entry.file_name().to_string_lossy().into_owned();
+                        if !name.starts_with('.') && entry.path().is_dir() && !names.contains(&name) {
+                            names.push(name);
+                        }
+                    }
+                }
+                names
+            }
+        };
+
+        let entries = names
+            .into_par_iter()
+            .map(|name| {
+                let entry = self.resolve_in_type_roots(&roots, &name);
+                // Packages which are not in type roots can be specified by `types`.
+                let entry = entry.or_else(|| {
+                    if !explicit {
+                        return None;
+                    }
+
+                    let base = FileName::Real(base.join("__inferred type names__.ts"));
+                    match &*self.module_graph.resolve_type_reference(&base, &name.as_str().into(), None).ok()? {
+                        FileName::Real(path) => Some(path.clone()),
+                        _ => None,
+                    }
+                });
+
+                match entry {
+                    Some(entry) => Ok((name, entry)),
+                    None => Err(name),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut errors = vec![];
+        let mut found = vec![];
+        for entry in entries {
+            match entry {
+                Ok(entry) => found.push(entry),
+                Err(name) => errors.push(Error::TypeDefinitionNotFound {
+                    span: DUMMY_SP,
+                    name: name.into(),
+                }),
+            }
         }
+        self.errors.lock().extend(errors);
 
-        dirs.into_par_iter().for_each(|dir| {
-            self.load_typings_from_dir(&dir, types);
+        found.into_par_iter().for_each(|(name, entry)| {
+            self.load_typing(name, entry);
         });
     }
 }
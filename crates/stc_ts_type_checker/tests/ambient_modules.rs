@@ -0,0 +1,86 @@
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig};
+use stc_ts_file_analyzer::env::EnvFactory;
+use swc_ecma_ast::EsVersion;
+
+#[path = "common/project.rs"]
+mod project;
+
+const DECLARATIONS: &str = r#"declare module "*.svg" {
+    const src: string;
+    export default src;
+}
+declare module "assets/*" {
+    const asset: number;
+    export default asset;
+}
+declare module "assets/icons/*" {
+    const icon: boolean;
+    export default icon;
+}
+"#;
+
+/// Writes `files` into a new project, checks `index.ts` and returns codes of
+/// errors.
+fn codes(name: &str, files: &[(&str, &str)]) -> Vec<usize> {
+    let dir = project::write(name, files);
+
+    project::codes(
+        Env::simple(Default::default(), EsVersion::Es2015, ModuleConfig::EsNext, &[Lib::Es5]),
+        &dir.join("index.ts"),
+    )
+}
+
+#[test]
+fn extension_pattern() {
+    assert_eq!(
+        codes(
+            "extension",
+            &[
+                ("global.d.ts", DECLARATIONS),
+                (
+                    "index.ts",
+                    "/// <reference path=\"./global.d.ts\" />\nimport logo from \"./logo.svg\";\nconst a: string = logo;\nconst b: number \
+                     = logo;\n",
+                ),
+            ]
+        ),
+        vec![2322]
+    );
+}
+
+#[test]
+fn longest_prefix() {
+    assert_eq!(
+        codes(
+            "prefix",
+            &[
+                ("global.d.ts", DECLARATIONS),
+                (
+                    "index.ts",
+                    "/// <reference path=\"./global.d.ts\" />\nimport asset from \"assets/a\";\nimport icon from \
+                     \"assets/icons/a\";\nconst a: number = asset;\nconst b: boolean = icon;\n",
+                ),
+            ]
+        ),
+        Vec::<usize>::new()
+    );
+}
+
+#[test]
+fn typescript_files_first() {
+    assert_eq!(
+        codes(
+            "files_first",
+            &[
+                ("global.d.ts", "declare module \"*/util\" {\n    export const util: string;\n}\n"),
+                ("util.ts", "export const util = 1;\n"),
+                (
+                    "index.ts",
+                    "/// <reference path=\"./global.d.ts\" />\nimport { util } from \"./util\";\nconst a: number = util;\n",
+                ),
+            ]
+        ),
+        Vec::<usize>::new()
+    );
+}
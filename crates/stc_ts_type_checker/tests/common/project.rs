@@ -0,0 +1,70 @@
+//! Helpers for tests which check small projects written by the test itself.
+#![allow(dead_code)]
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use stc_ts_env::Env;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::Checker;
+use swc_common::FileName;
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Writes `files` into a new directory and returns the canonicalized path of
+/// it.
+///
+/// The directory is unique to each call, so tests running in parallel never
+/// see files of each other. It's not placed in the repository because
+/// `node_modules` of ancestor directories affects resolution.
+pub fn write(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = env::temp_dir()
+        .join("stc-tests")
+        .join(format!("{}-{}-{}", name, process::id(), NEXT_ID.fetch_add(1, Ordering::SeqCst)));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let dir = dir.canonicalize().unwrap();
+
+    for (file, src) in files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, src).unwrap();
+    }
+
+    dir
+}
+
+/// Checks `file` using `env` and returns codes of errors.
+pub fn codes(env: Env, file: &Path) -> Vec<usize> {
+    codes_with(env, |checker| {
+        checker.check(Arc::new(FileName::Real(file.to_path_buf())));
+    })
+}
+
+/// Invokes `op` with a new checker and returns codes of errors reported by
+/// it.
+pub fn codes_with(env: Env, op: impl FnOnce(&mut Checker)) -> Vec<usize> {
+    let mut codes = vec![];
+    ::testing::run_test2(false, |cm, handler| {
+        let mut checker = Checker::new(cm, Arc::new(handler), env, Default::default(), None, Arc::new(NodeResolver));
+
+        op(&mut checker);
+
+        codes = stc_ts_errors::Error::flatten(checker.take_errors())
+            .iter()
+            .map(|err| err.code())
+            .collect();
+
+        Ok(())
+    })
+    .unwrap();
+
+    codes
+}
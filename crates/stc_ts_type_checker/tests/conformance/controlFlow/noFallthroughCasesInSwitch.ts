@@ -0,0 +1,25 @@
+// @noFallthroughCasesInSwitch: true
+declare const x: number;
+declare function fail(): never;
+
+switch (x) {
+    case 0:
+        x;
+    case 1:
+        break;
+    default:
+}
+
+switch (x) {
+    case 0:
+    case 1:
+        break;
+    default:
+}
+
+switch (x) {
+    case 0:
+        fail();
+    case 1:
+        break;
+}
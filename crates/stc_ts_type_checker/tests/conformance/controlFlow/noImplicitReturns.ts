@@ -0,0 +1,34 @@
+// @noImplicitReturns: true
+function f1(x: boolean) {
+    if (x) {
+        return 1;
+    }
+}
+
+function f2(x: boolean): number {
+    if (x) {
+        return 1;
+    }
+}
+
+function f3(x: boolean) {
+    if (x) {
+        return 1;
+    } else {
+        return 2;
+    }
+}
+
+function f4(x: boolean) {
+    if (x) {
+        return 1;
+    }
+    throw new Error();
+}
+
+function f5(x: boolean) {
+    if (x) {
+        return 1;
+    }
+    return;
+}
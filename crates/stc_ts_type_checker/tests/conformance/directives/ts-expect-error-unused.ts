@@ -0,0 +1,5 @@
+/* @ts-expect-error */
+const a: string = 1;
+
+// @ts-expect-error
+const b: string = "";
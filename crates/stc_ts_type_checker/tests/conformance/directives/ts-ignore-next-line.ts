@@ -0,0 +1,11 @@
+// @ts-ignore
+const a: string = 1;
+
+// @ts-ignore
+
+// comment
+const b: string = 1;
+
+// @ts-ignore
+const c = 1;
+const d: string = 1;
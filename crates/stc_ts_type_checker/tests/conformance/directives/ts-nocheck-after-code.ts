@@ -0,0 +1,3 @@
+const a: string = 1;
+// @ts-nocheck
+const b: string = 1;
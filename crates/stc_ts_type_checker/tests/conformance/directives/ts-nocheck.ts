@@ -0,0 +1,4 @@
+// @ts-nocheck
+// @ts-expect-error
+const a: string = 1;
+const b: string = 1;
@@ -0,0 +1,12 @@
+// @jsx: react
+// @jsxFactory: h
+declare namespace JSX {
+    interface Element {}
+    interface ElementAttributesProperty { props: {} }
+    interface ElementChildrenAttribute { children: {} }
+    interface IntrinsicElements {
+        div: { id?: string; children?: any };
+    }
+}
+declare function h(): any;
+const a = <></>;
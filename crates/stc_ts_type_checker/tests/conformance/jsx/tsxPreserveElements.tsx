@@ -0,0 +1,31 @@
+// @jsx: preserve
+declare namespace JSX {
+    interface Element {}
+    interface ElementAttributesProperty { props: {} }
+    interface ElementChildrenAttribute { children: {} }
+    interface IntrinsicElements {
+        div: { id?: string; children?: any };
+    }
+}
+declare function Foo(props: { name: string }): JSX.Element;
+declare class Bar {
+    props: { x: number };
+}
+declare function Baz(props: { children?: string }): JSX.Element;
+
+const a1 = <div id="a" />;
+const a2 = <span />;
+const a3 = <div id={1} />;
+const a4 = <div data-foo="a" />;
+
+const b1 = <Foo name="a" />;
+const b2 = <Foo nme="a" />;
+
+const c1 = <Bar x={1} />;
+const c2 = <Bar x="a" />;
+
+const d = <Baz children="a">b</Baz>;
+
+const e = <>
+    <div />
+</>;
@@ -0,0 +1,11 @@
+// @jsx: react
+declare namespace JSX {
+    interface Element {}
+    interface ElementAttributesProperty { props: {} }
+    interface ElementChildrenAttribute { children: {} }
+    interface IntrinsicElements {
+        div: { id?: string; children?: any };
+    }
+}
+declare const React: { createElement: any };
+const a = <></>;
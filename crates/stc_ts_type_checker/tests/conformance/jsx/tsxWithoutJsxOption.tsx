@@ -0,0 +1,9 @@
+declare namespace JSX {
+    interface Element {}
+    interface ElementAttributesProperty { props: {} }
+    interface ElementChildrenAttribute { children: {} }
+    interface IntrinsicElements {
+        div: { id?: string; children?: any };
+    }
+}
+const a = <div />;
@@ -0,0 +1,4 @@
+// @strictNullChecks: true
+// @exactOptionalPropertyTypes: true
+export const o1: { a?: number } = { a: undefined };
+export const o2: { a?: number | undefined } = { a: undefined };
@@ -0,0 +1,4 @@
+// @strictNullChecks: true
+declare const a: number[];
+
+export const n: number = a[0];
@@ -0,0 +1,9 @@
+// @strictNullChecks: true
+// @noUncheckedIndexedAccess: true
+declare const a: number[];
+declare const r: { [key: string]: number };
+
+export const n1: number = a[0];
+export const n2: number = r["a"];
+
+a[0] = 1;
@@ -0,0 +1,2 @@
+// @strictNullChecks: true
+export const o: { a?: number } = { a: undefined };
@@ -0,0 +1,53 @@
+// @noUnusedLocals: true
+// @noUnusedParameters: true
+export function f1() {
+    const a = 1;
+}
+
+interface A {}
+
+const b = 1;
+export type B = typeof b;
+
+export function f2() {
+    let c = 1;
+    c = 2;
+}
+
+function f3(): void {
+    f3();
+}
+
+export function f4(d: number, _e: number, f: number) {
+    return f;
+}
+
+export function f5(o: { a: number; b: number }) {
+    const { a, b } = o;
+}
+
+export function f6(o: { a: number; b: number }) {
+    const { a, ...rest } = o;
+    return rest;
+}
+
+export function f7(o: { a: number; b: number }) {
+    const { a: _a, b } = o;
+    return b;
+}
+
+export function f8<T, U>(u: U) {
+    return u;
+}
+
+export type C<T, U> = {};
+
+namespace N {
+    export const g = 1;
+}
+import _g = N.g;
+
+export function f9() {
+    let h = 1;
+    return h++;
+}
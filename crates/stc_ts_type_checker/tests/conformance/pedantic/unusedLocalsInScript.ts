@@ -0,0 +1,3 @@
+// @noUnusedLocals: true
+// @noUnusedParameters: true
+const a = 1;
@@ -0,0 +1,2 @@
+/// <reference lib="es2015.core" />
+const b: boolean = "a".startsWith("a");
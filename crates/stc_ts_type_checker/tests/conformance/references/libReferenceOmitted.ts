@@ -0,0 +1 @@
+const b: boolean = "a".startsWith("a");
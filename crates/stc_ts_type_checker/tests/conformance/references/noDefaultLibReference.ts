@@ -0,0 +1,4 @@
+// @lib: es2015
+/// <reference no-default-lib="true" />
+/// <reference lib="es5" />
+const b: boolean = "a".startsWith("a");
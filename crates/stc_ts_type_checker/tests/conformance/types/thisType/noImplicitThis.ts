@@ -0,0 +1,41 @@
+// @noImplicitThis: true
+export function f1() {
+    return this;
+}
+
+export function f2() {
+    return () => this;
+}
+
+export function f3(this: { a: number }) {
+    return this;
+}
+
+export const o = {
+    f: function () {
+        return this;
+    },
+};
+
+export class A {
+    m() {
+        return this;
+    }
+}
+
+interface F {
+    (this: { a: number }): number;
+}
+export const f4: F = function () {
+    return this.a;
+};
+
+export const f5: ((this: { a: number }) => number) | undefined = function () {
+    return this.a;
+};
+
+export const f6: (this: { a: number }) => void = function () {
+    function g() {
+        return this;
+    }
+};
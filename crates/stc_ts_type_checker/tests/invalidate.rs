@@ -0,0 +1,139 @@
+use std::{fs, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::Checker;
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+
+#[path = "common/project.rs"]
+mod project;
+
+#[test]
+fn spare_dependents_with_same_exports() {
+    let dir = project::write(
+        "spare_dependents_with_same_exports",
+        &[
+            ("a.ts", "export const a = 1;\n"),
+            ("b.ts", "import { a } from './a';\nexport const b: number = a;\n"),
+            ("main.ts", "import { b } from './b';\nconst c: string = b;\n"),
+        ],
+    );
+    let path = |name: &str| Arc::new(FileName::Real(dir.join(name)));
+
+    ::testing::run_test2(false, |cm, handler| {
+        let mut checker = Checker::new(
+            cm,
+            Arc::new(handler),
+            Env::simple(Default::default(), EsVersion::Es5, ModuleConfig::None, &[Lib::Es5]),
+            Default::default(),
+            None,
+            Arc::new(NodeResolver),
+        );
+
+        checker.check(path("main.ts"));
+        assert_eq!(checker.take_errors().len(), 1);
+
+        // Exports of `a.ts` are not changed.
+        fs::write(dir.join("a.ts"), "// a\nexport const a = 1;\n").unwrap();
+        let analyzed = checker.invalidate(&path("a.ts"));
+        assert_eq!(analyzed, vec![checker.id(&path("a.ts"))]);
+        assert_eq!(checker.take_errors().len(), 0);
+
+        // `b.ts` should be checked again, but exports of it are not changed.
+        fs::write(dir.join("a.ts"), "export const a = '';\n").unwrap();
+        let analyzed = checker.invalidate(&path("a.ts"));
+        assert_eq!(analyzed, vec![checker.id(&path("a.ts")), checker.id(&path("b.ts"))]);
+        assert_eq!(checker.take_errors().len(), 1);
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn recompute_lib_references() {
+    let dir = project::write(
+        "recompute_lib_references",
+        &[
+            ("a.ts", "/// <reference lib=\"es2015.core\" />\nexport const a = 1;\n"),
+            ("main.ts", "import { a } from './a';\nconst b: boolean = \"a\".startsWith(\"a\");\n"),
+        ],
+    );
+    let path = |name: &str| Arc::new(FileName::Real(dir.join(name)));
+
+    ::testing::run_test2(false, |cm, handler| {
+        let mut checker = Checker::new(
+            cm,
+            Arc::new(handler),
+            Env::simple(Default::default(), EsVersion::Es5, ModuleConfig::None, &[Lib::Es5]),
+            Default::default(),
+            None,
+            Arc::new(NodeResolver),
+        );
+
+        checker.check(path("main.ts"));
+        assert_eq!(checker.take_errors().len(), 0);
+
+        // `main.ts` uses a library which is not referenced anymore.
+        fs::write(dir.join("a.ts"), "export const a = 1;\n").unwrap();
+        let analyzed = checker.invalidate(&path("a.ts"));
+        assert!(analyzed.contains(&checker.id(&path("main.ts"))));
+        assert_eq!(
+            stc_ts_errors::Error::flatten(checker.take_errors())
+                .iter()
+                .map(|err| err.code())
+                .collect::<Vec<_>>(),
+            vec![2339]
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn redeclare_modules() {
+    let dir = project::write(
+        "redeclare_modules",
+        &[
+            ("decl.d.ts", "declare module \"foo\" {\n    export const a: number;\n}\n"),
+            (
+                "main.ts",
+                "/// <reference path=\"./decl.d.ts\" />\nimport { a } from \"foo\";\nconst b: number = a;\n",
+            ),
+        ],
+    );
+    let path = |name: &str| Arc::new(FileName::Real(dir.join(name)));
+
+    ::testing::run_test2(false, |cm, handler| {
+        let mut checker = Checker::new(
+            cm,
+            Arc::new(handler),
+            Env::simple(Default::default(), EsVersion::Es5, ModuleConfig::None, &[Lib::Es5]),
+            Default::default(),
+            None,
+            Arc::new(NodeResolver),
+        );
+
+        checker.check(path("main.ts"));
+        assert_eq!(checker.take_errors().len(), 0);
+
+        // The previous declaration of `foo` should not be used.
+        fs::write(dir.join("decl.d.ts"), "declare module \"foo\" {\n    export const a: string;\n}\n").unwrap();
+        let analyzed = checker.invalidate(&path("decl.d.ts"));
+        assert!(analyzed.contains(&checker.id(&path("main.ts"))));
+        assert_eq!(
+            stc_ts_errors::Error::flatten(checker.take_errors())
+                .iter()
+                .map(|err| err.code())
+                .collect::<Vec<_>>(),
+            vec![2322]
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
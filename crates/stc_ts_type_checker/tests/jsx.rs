@@ -0,0 +1,58 @@
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, JsxConfig, JsxMode, ModuleConfig};
+use stc_ts_file_analyzer::env::EnvFactory;
+use swc_ecma_ast::EsVersion;
+
+#[path = "common/project.rs"]
+mod project;
+
+/// Checks `src` and returns codes of errors.
+///
+/// `node_modules/react/jsx-runtime.d.ts` is available to `src`.
+fn codes(name: &str, jsx: JsxConfig, src: &str) -> Vec<usize> {
+    let dir = project::write(
+        "jsx",
+        &[
+            (
+                "node_modules/react/jsx-runtime.d.ts",
+                "export namespace JSX {
+    interface Element {}
+    interface IntrinsicElements {
+        span: {};
+    }
+}
+",
+            ),
+            (name, src),
+        ],
+    );
+
+    project::codes(
+        Env::simple(Default::default(), EsVersion::Es5, ModuleConfig::None, &[Lib::Es5]).with_jsx(jsx),
+        &dir.join(name),
+    )
+}
+
+#[test]
+fn namespace_of_import_source() {
+    let jsx = JsxConfig {
+        mode: Some(JsxMode::ReactJsx),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        codes("import_source.tsx", jsx, "const a = <span />;\nconst b = <div />;\n"),
+        vec![2339]
+    );
+}
+
+#[test]
+fn missing_import_source() {
+    let jsx = JsxConfig {
+        mode: Some(JsxMode::ReactJsx),
+        import_source: Some("preact".into()),
+        ..Default::default()
+    };
+
+    assert_eq!(codes("no_import_source.tsx", jsx, "const a = <span />;\n"), vec![2875]);
+}
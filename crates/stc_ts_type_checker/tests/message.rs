@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::Checker;
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+
+#[path = "common/project.rs"]
+mod project;
+
+/// Checks `src` and returns messages of errors.
+fn messages(name: &str, src: &str) -> Vec<String> {
+    errors(name, &[(name, src)]).into_iter().map(|(_, msg)| msg).collect()
+}
+
+/// Checks `entry` of `files` and returns codes and messages of errors.
+fn errors(entry: &str, files: &[(&str, &str)]) -> Vec<(usize, String)> {
+    let file = project::write("message", files).join(entry);
+
+    let mut errors = vec![];
+    ::testing::run_test2(false, |cm, handler| {
+        let mut checker = Checker::new(
+            cm,
+            Arc::new(handler),
+            Env::simple(Default::default(), EsVersion::Es5, ModuleConfig::None, &[Lib::Es5]),
+            Default::default(),
+            None,
+            Arc::new(NodeResolver),
+        );
+
+        checker.check(Arc::new(FileName::Real(file.clone())));
+
+        let taken = stc_ts_errors::Error::flatten(checker.take_errors());
+        errors = checker.run(|| taken.iter().map(|err| (err.code(), err.msg_with_causes())).collect());
+
+        Ok(())
+    })
+    .unwrap();
+
+    errors
+}
+
+#[test]
+fn assign_failure() {
+    let messages = messages("assign.ts", "const a: string = 1;\n");
+
+    assert_eq!(messages.len(), 1, "{:#?}", messages);
+    assert!(messages[0].starts_with("Type '"), "{}", messages[0]);
+    assert!(messages[0].contains("is not assignable to type 'string'."), "{}", messages[0]);
+}
+
+#[test]
+fn name_not_found() {
+    let messages = messages("name.ts", "foo;\n");
+
+    assert_eq!(messages, vec!["Cannot find name 'foo'.".to_string()]);
+}
+
+/// Messages of errors reported often should be the same as `tsc`, so users
+/// can search for them.
+#[test]
+fn tsc_messages() {
+    let a = ("a.ts", "export const a = 1;\n");
+    let cases: &[(&[(&str, &str)], usize, &str)] = &[
+        (&[("main.ts", "foo;\n")], 2304, "Cannot find name 'foo'."),
+        (
+            &[("main.ts", "import { a } from './missing';\n")],
+            2307,
+            "Cannot find module './missing' or its corresponding type declarations.",
+        ),
+        (
+            &[("main.ts", "import { b } from './a';\n"), a],
+            2305,
+            "Module '\"./a\"' has no exported member 'b'.",
+        ),
+        (
+            &[("main.ts", "import b from './a';\n"), a],
+            1192,
+            "Module '\"./a\"' has no default export.",
+        ),
+        (
+            &[("main.ts", "class C {\n    a: number;\n    a: number;\n}\n")],
+            2300,
+            "Duplicate identifier 'a'.",
+        ),
+        (
+            &[("main.ts", "enum E {\n    A,\n}\nE.B;\n")],
+            2339,
+            "Property 'B' does not exist on type 'typeof E'.",
+        ),
+        (
+            &[("main.ts", "let a = 1;\nlet a = 2;\n")],
+            2451,
+            "Cannot redeclare block-scoped variable 'a'.",
+        ),
+        (
+            &[("main.ts", "function f(a: number) {}\nf();\n")],
+            2554,
+            "Expected 1 arguments, but got 0.",
+        ),
+    ];
+
+    for (files, code, msg) in cases {
+        let errors = errors("main.ts", files);
+
+        assert!(
+            errors.contains(&(*code, msg.to_string())),
+            "expected TS{}: {}\nbut got {:#?}",
+            code,
+            msg,
+            errors
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig};
+use stc_ts_file_analyzer::env::EnvFactory;
+use swc_ecma_ast::EsVersion;
+
+#[path = "common/project.rs"]
+mod project;
+
+/// Writes `files` into a new project, checks `index.ts` and returns codes of
+/// errors.
+fn codes(name: &str, files: &[(&str, &str)]) -> Vec<usize> {
+    let dir = project::write(name, files);
+
+    project::codes(
+        Env::simple(Default::default(), EsVersion::Es5, ModuleConfig::None, &[Lib::Es5]),
+        &dir.join("index.ts"),
+    )
+}
+
+#[test]
+fn types() {
+    assert_eq!(
+        codes(
+            "types",
+            &[
+                ("node_modules/@types/foo/index.d.ts", "declare const fooGlobal: string;\n"),
+                ("index.ts", "/// <reference types=\"foo\" />\nconst a: number = fooGlobal;\n"),
+            ]
+        ),
+        vec![2322]
+    );
+}
+
+#[test]
+fn types_of_scoped_package() {
+    assert_eq!(
+        codes(
+            "scoped",
+            &[
+                ("node_modules/@types/scope__foo/index.d.ts", "declare const fooGlobal: string;\n"),
+                ("index.ts", "/// <reference types=\"@scope/foo\" />\nconst a: string = fooGlobal;\n"),
+            ]
+        ),
+        Vec::<usize>::new()
+    );
+}
+
+#[test]
+fn types_with_resolution_mode() {
+    assert_eq!(
+        codes(
+            "resolution_mode",
+            &[
+                ("node_modules/@types/foo/index.d.ts", "declare const fooGlobal: string;\n"),
+                (
+                    "index.ts",
+                    "/// <reference types=\"foo\" resolution-mode=\"require\" />\nconst a: number = fooGlobal;\n",
+                ),
+            ]
+        ),
+        vec![2322]
+    );
+}
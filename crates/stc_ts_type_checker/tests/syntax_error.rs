@@ -0,0 +1,50 @@
+use std::{path::Path, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig};
+use stc_ts_errors::Error;
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_type_checker::Checker;
+use swc_common::{FileName, Spanned};
+use swc_ecma_ast::EsVersion;
+
+#[path = "common/project.rs"]
+mod project;
+
+fn syntax_errors(dir: &Path, entry: &str) -> Vec<Error> {
+    ::testing::run_test2(false, |cm, handler| {
+        let mut checker = Checker::new(
+            cm,
+            Arc::new(handler),
+            Env::simple(Default::default(), EsVersion::Es5, ModuleConfig::None, &[Lib::Es5]),
+            Default::default(),
+            None,
+            Arc::new(NodeResolver),
+        );
+
+        checker.check(Arc::new(FileName::Real(dir.join(entry))));
+
+        Ok(checker.take_errors().into_iter().filter(|err| err.code() < 2000).collect())
+    })
+    .unwrap()
+}
+
+#[test]
+fn syntax_errors_are_reported() {
+    let dir = project::write(
+        "syntax-error",
+        &[
+            ("broken.ts", "export const a = ;\n"),
+            ("main.ts", "import { a } from './broken';\nimport { a as b } from './broken';\n"),
+        ],
+    );
+
+    // `broken.ts` fails to parse, but `main.ts` which imports it is still analyzed.
+    let errors = syntax_errors(&dir, "main.ts");
+    assert_eq!(errors.len(), 1, "{:#?}", errors);
+    assert!(!errors[0].span().is_dummy());
+
+    let errors = syntax_errors(&dir, "broken.ts");
+    assert_eq!(errors.len(), 1, "{:#?}", errors);
+}
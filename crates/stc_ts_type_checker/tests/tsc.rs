@@ -25,7 +25,7 @@ use parking_lot::Mutex;
 use serde::Deserialize;
 use stc_testing::init_tracing;
 use stc_ts_builtin_types::Lib;
-use stc_ts_env::{Env, ModuleConfig, Rule};
+use stc_ts_env::{Env, JsxConfig, ModuleConfig, Rule};
 use stc_ts_file_analyzer::env::EnvFactory;
 use stc_ts_module_loader::resolvers::node::NodeResolver;
 use stc_ts_type_checker::Checker;
@@ -302,6 +302,7 @@ struct TestSpec {
     ts_config: TsConfig,
     target: EsVersion,
     module_config: ModuleConfig,
+    jsx: JsxConfig,
 }
 
 fn parse_targets(s: &str) -> Vec<EsVersion> {
@@ -324,6 +325,14 @@ fn parse_targets(s: &str) -> Vec<EsVersion> {
     s.split(",").map(|s| s.trim()).flat_map(parse_targets).collect()
 }
 
+/// Returns true if `comment` is a compiler option like `@strict: true`.
+///
+/// Comments like `@ts-ignore` are directives for the checker, not options.
+fn is_option(comment: &str) -> bool {
+    let s = comment.trim();
+    s.starts_with("@") && !s.starts_with("@ts-")
+}
+
 fn parse_test(file_name: &Path) -> Vec<TestSpec> {
     let mut err_shift_n = 0;
     let mut first_stmt_line = 0;
@@ -382,6 +391,7 @@ fn parse_test(file_name: &Path) -> Vec<TestSpec> {
         };
         let mut module_config = ModuleConfig::None;
         let ts_config = TsConfig::default();
+        let mut jsx = JsxConfig::default();
 
         let mut had_comment = false;
 
@@ -389,19 +399,19 @@ fn parse_test(file_name: &Path) -> Vec<TestSpec> {
         let cmts = comments.leading.get(&span.lo());
         match cmts {
             Some(ref cmts) => {
-                let directive_start = cmts.iter().position(|cmt| cmt.text.trim().starts_with("@")).unwrap_or(0);
+                let directive_start = cmts.iter().position(|cmt| is_option(&cmt.text)).unwrap_or(0);
                 let cmt_start_line = if directive_start == 0 {
                     0
                 } else {
                     cmts.iter()
-                        .find(|cmt| cmt.text.trim().starts_with("@"))
+                        .find(|cmt| is_option(&cmt.text))
                         .map(|cmt| cm.lookup_char_pos(cmt.span.hi).line)
                         .unwrap_or(0)
                 };
 
                 for cmt in cmts.iter().skip(directive_start) {
                     let s = cmt.text.trim();
-                    if !s.starts_with("@") {
+                    if !is_option(s) {
                         if had_comment {
                             err_shift_n = cm.lookup_char_pos(cmt.span.hi).line - 1 - cmt_start_line;
                             break;
@@ -433,6 +443,15 @@ fn parse_test(file_name: &Path) -> Vec<TestSpec> {
                     } else if s.starts_with("noImplicitReturns:") {
                         let v = s["noImplicitReturns:".len()..].trim().parse().unwrap();
                         rule.no_implicit_returns = v;
+                    } else if s.starts_with("noFallthroughCasesInSwitch:") {
+                        let v = s["noFallthroughCasesInSwitch:".len()..].trim().parse().unwrap();
+                        rule.no_fallthrough_cases_in_switch = v;
+                    } else if s.starts_with("noUnusedLocals:") {
+                        let v = s["noUnusedLocals:".len()..].trim().parse().unwrap();
+                        rule.no_unused_locals = v;
+                    } else if s.starts_with("noUnusedParameters:") {
+                        let v = s["noUnusedParameters:".len()..].trim().parse().unwrap();
+                        rule.no_unused_parameters = v;
                     } else if s.starts_with("declaration") {
                     } else if s.starts_with("stripInternal:") {
                         // TODO(kdy1): Handle
@@ -465,6 +484,12 @@ fn parse_test(file_name: &Path) -> Vec<TestSpec> {
                     } else if s.starts_with("noImplicitThis:") {
                         let v = s["noImplicitThis:".len()..].trim().parse().unwrap();
                         rule.no_implicit_this = v;
+                    } else if s.starts_with("noUncheckedIndexedAccess:") {
+                        let v = s["noUncheckedIndexedAccess:".len()..].trim().parse().unwrap();
+                        rule.no_unchecked_indexed_access = v;
+                    } else if s.starts_with("exactOptionalPropertyTypes:") {
+                        let v = s["exactOptionalPropertyTypes:".len()..].trim().parse().unwrap();
+                        rule.exact_optional_property_types = v;
                     } else if s.starts_with("skipDefaultLibCheck") {
                         // TODO
                     } else if s.starts_with("suppressImplicitAnyIndexErrors:") {
@@ -478,6 +503,14 @@ fn parse_test(file_name: &Path) -> Vec<TestSpec> {
                         // Ignored as we don't generate them.
                     } else if s.to_lowercase().starts_with("usedefineforclassfields") {
                         rule.use_define_property_for_class_fields = true;
+                    } else if s.to_lowercase().starts_with("jsx:") {
+                        jsx.mode = s["jsx:".len()..].trim().to_lowercase().parse().ok();
+                    } else if s.to_lowercase().starts_with("jsxfactory:") {
+                        jsx.factory = Some(s["jsxFactory:".len()..].trim().into());
+                    } else if s.to_lowercase().starts_with("jsxfragmentfactory:") {
+                        jsx.fragment_factory = Some(s["jsxFragmentFactory:".len()..].trim().into());
+                    } else if s.to_lowercase().starts_with("jsximportsource:") {
+                        jsx.import_source = Some(s["jsxImportSource:".len()..].trim().into());
                     } else if s.to_lowercase().starts_with("noemit")
                         || s.to_lowercase().starts_with("jsx")
                         || s.to_lowercase().starts_with("preserveconstenums")
@@ -533,6 +566,7 @@ fn parse_test(file_name: &Path) -> Vec<TestSpec> {
                     ts_config,
                     target,
                     module_config,
+                    jsx: jsx.clone(),
                 }
             })
             .collect())
@@ -556,6 +590,7 @@ fn do_test(file_name: &Path) -> Result<(), StdErr> {
         ts_config,
         target,
         module_config,
+        jsx,
     } in specs
     {
         let mut time_of_check = Duration::new(0, 0);
@@ -592,7 +627,7 @@ fn do_test(file_name: &Path) -> Result<(), StdErr> {
                 let mut checker = Checker::new(
                     cm.clone(),
                     handler.clone(),
-                    Env::simple(rule, target, module_config, &libs),
+                    Env::simple(rule, target, module_config, &libs).with_jsx(jsx.clone()),
                     TsConfig {
                         tsx: fname.contains("tsx"),
                         ..ts_config
@@ -0,0 +1,84 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig};
+use stc_ts_file_analyzer::env::EnvFactory;
+use swc_common::FileName;
+use swc_ecma_ast::EsVersion;
+
+#[path = "common/project.rs"]
+mod project;
+
+/// Loads typings for `base`, checks `file` and returns codes of errors.
+fn codes(base: &Path, file: &Path, type_roots: Option<&[PathBuf]>, types: Option<&[String]>) -> Vec<usize> {
+    project::codes_with(
+        Env::simple(Default::default(), EsVersion::Es5, ModuleConfig::None, &Lib::load("es5")),
+        |checker| {
+            checker.load_typings(base, type_roots, types);
+            checker.check(Arc::new(FileName::Real(file.to_path_buf())));
+        },
+    )
+}
+
+#[test]
+fn empty_types() {
+    let dir = project::write(
+        "empty_types",
+        &[
+            ("node_modules/@types/foo/index.d.ts", "declare const fooGlobal: string;\n"),
+            ("index.ts", "const a: string = fooGlobal;\n"),
+        ],
+    );
+
+    assert_eq!(codes(&dir, &dir.join("index.ts"), None, None), Vec::<usize>::new());
+    assert_eq!(codes(&dir, &dir.join("index.ts"), None, Some(&[])), vec![2304]);
+}
+
+#[test]
+fn type_roots() {
+    let dir = project::write(
+        "type_roots",
+        &[
+            ("types/foo/index.d.ts", "declare const fooGlobal: string;\n"),
+            ("node_modules/@types/bar/index.d.ts", "declare const barGlobal: string;\n"),
+            ("index.ts", "const a: string = fooGlobal;\nconst b: string = barGlobal;\n"),
+        ],
+    );
+
+    // `node_modules/@types` is not used if `typeRoots` is specified.
+    assert_eq!(codes(&dir, &dir.join("index.ts"), Some(&[dir.join("types")]), None), vec![2304]);
+}
+
+#[test]
+fn nearest_package() {
+    let dir = project::write(
+        "nearest",
+        &[
+            ("node_modules/@types/foo/index.d.ts", "declare const fooGlobal: number;\n"),
+            ("app/node_modules/@types/foo/index.d.ts", "declare const fooGlobal: string;\n"),
+            ("app/index.ts", "const a: string = fooGlobal;\n"),
+        ],
+    );
+    let app = dir.join("app");
+
+    assert_eq!(codes(&app, &app.join("index.ts"), None, None), Vec::<usize>::new());
+}
+
+#[test]
+fn missing_package() {
+    let dir = project::write(
+        "missing",
+        &[
+            ("node_modules/@types/foo/index.d.ts", "declare const fooGlobal: string;\n"),
+            ("index.ts", "const a: string = fooGlobal;\n"),
+        ],
+    );
+
+    assert_eq!(
+        codes(&dir, &dir.join("index.ts"), None, Some(&["foo".to_string(), "missing".to_string()])),
+        vec![2688]
+    );
+}
@@ -31,6 +31,10 @@ impl Id {
         &self.sym
     }
 
+    pub fn ctxt(&self) -> SyntaxContext {
+        self.ctxt
+    }
+
     pub fn word(sym: JsWord) -> Self {
         Id {
             sym,
@@ -8,23 +8,41 @@ use swc_common::{
 pub enum ImportRef {
     /// path="foo"
     Path(JsWord),
-    /// type="foo"
-    Types(JsWord),
+    /// types="foo"
+    ///
+    /// This should be resolved using `@types` packages first.
+    Types(JsWord, Option<ResolutionMode>),
+    /// lib="es2015"
+    Lib(JsWord),
+    /// no-default-lib="true"
+    NoDefaultLib,
 
     /// ES6 import.
     Normal(JsWord),
 }
 
 impl ImportRef {
-    pub fn to_path(self) -> JsWord {
+    /// Returns [None] for references which are not modules.
+    pub fn to_path(self) -> Option<JsWord> {
         match self {
-            ImportRef::Path(s) => format!("./{}", s).into(),
-            ImportRef::Types(s) => s,
-            ImportRef::Normal(s) => s,
+            ImportRef::Path(s) => Some(format!("./{}", s).into()),
+            ImportRef::Types(s, _) => Some(s),
+            ImportRef::Normal(s) => Some(s),
+            ImportRef::Lib(..) | ImportRef::NoDefaultLib => None,
         }
     }
 }
 
+/// `resolution-mode` of `/// <reference types="foo" />`, which overrides the
+/// kind of the referencing module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResolutionMode {
+    /// resolution-mode="import"
+    Import,
+    /// resolution-mode="require"
+    Require,
+}
+
 pub fn find_imports_in_comments<C>(comments: C, span: Span) -> Vec<ImportRef>
 where
     C: Comments,
@@ -49,15 +67,30 @@ where
                 .strip_prefix("/")
                 .map(|s| s.trim())
                 .and_then(|s| s.strip_prefix("<reference"))
-                .and_then(|s| s.strip_suffix("\" />"))
+                .and_then(|s| s.strip_suffix("/>"))
                 .map(|s| s.trim())
             {
-                if let Some(path) = cmt_text.strip_prefix("path=\"") {
-                    deps.push(ImportRef::Path(path.into()));
-                } else if let Some(path) = cmt_text.strip_prefix("types=\"") {
-                    deps.push(ImportRef::Types(path.into()));
-                } else {
-                    // TODO: Handle lib, types
+                let attrs = match parse_attrs(cmt_text) {
+                    Some(v) => v,
+                    None => continue,
+                };
+                let mode = attrs
+                    .iter()
+                    .find(|(name, _)| *name == "resolution-mode")
+                    .and_then(|(_, value)| match *value {
+                        "import" => Some(ResolutionMode::Import),
+                        "require" => Some(ResolutionMode::Require),
+                        _ => None,
+                    });
+
+                for (name, value) in attrs {
+                    match name {
+                        "path" => deps.push(ImportRef::Path(value.into())),
+                        "types" => deps.push(ImportRef::Types(value.into(), mode)),
+                        "lib" => deps.push(ImportRef::Lib(value.into())),
+                        "no-default-lib" if value == "true" => deps.push(ImportRef::NoDefaultLib),
+                        _ => {}
+                    }
                 }
             }
         }
@@ -65,3 +98,23 @@ where
 
     deps
 }
+
+/// Parses attributes like `types="foo" resolution-mode="import"`.
+///
+/// Returns [None] if an attribute is malformed.
+fn parse_attrs(mut s: &str) -> Option<Vec<(&str, &str)>> {
+    let mut attrs = vec![];
+
+    while !s.is_empty() {
+        let (name, rest) = s.split_once('=')?;
+        let rest = rest.trim_start();
+        let quote = rest.chars().next().filter(|&c| c == '"' || c == '\'')?;
+        let rest = &rest[1..];
+        let end = rest.find(quote)?;
+
+        attrs.push((name.trim(), &rest[..end]));
+        s = rest[end + 1..].trim_start();
+    }
+
+    Some(attrs)
+}
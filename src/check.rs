@@ -1,4 +1,19 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+
+use anyhow::{bail, Error};
 use clap::Args;
+use stc_ts_config::ParsedConfig;
+use stc_ts_type_checker::Checker;
+use swc_common::{errors::Handler, FileName, SourceMap};
+
+use crate::{
+    report::{report, Format},
+    watch::watch,
+};
 
 /// Perform type checking, but this command is not public api and is only used
 /// for testing.
@@ -16,3 +31,96 @@ pub struct TestCommand {
     #[clap(long)]
     pub types: Option<Vec<String>>,
 }
+
+/// Type check a project using `tsconfig.json`.
+#[derive(Debug, Args)]
+#[clap(rename_all = "camel-case")]
+pub struct CheckCommand {
+    /// Path to `tsconfig.json`, or to a directory containing it.
+    #[clap(short = 'p', long = "project", default_value = "tsconfig.json")]
+    pub project: PathBuf,
+
+    /// Watch input files, and check affected files again on changes.
+    #[clap(short = 'w', long)]
+    pub watch: bool,
+
+    /// Format of diagnostics.
+    #[clap(long, value_enum, default_value = "pretty")]
+    pub format: Format,
+}
+
+impl CheckCommand {
+    /// Returns the number of errors.
+    ///
+    /// In watch mode, this returns only on errors, like when the project
+    /// cannot be loaded initially or the file watcher stops.
+    pub fn run(self, cm: Arc<SourceMap>, handler: Arc<Handler>) -> Result<usize, Error> {
+        if self.watch {
+            return watch(&self.project, self.format, cm, handler);
+        }
+
+        let config = load_config(&self.project)?;
+        let mut checker = create_checker(&config, cm.clone(), handler.clone());
+        let errors = check_files(&mut checker, &config);
+        let count = errors.len();
+
+        checker.run(|| report(self.format, &cm, &handler, errors))?;
+
+        Ok(count)
+    }
+}
+
+pub(crate) fn load_config(project: &Path) -> Result<ParsedConfig, Error> {
+    let config = stc_ts_config::load(project)?;
+    if config.files.is_empty() {
+        bail!("no inputs were found in config file `{}`", config.path.display());
+    }
+
+    Ok(config)
+}
+
+pub(crate) fn create_checker(config: &ParsedConfig, cm: Arc<SourceMap>, handler: Arc<Handler>) -> Checker {
+    let env = {
+        let start = Instant::now();
+        let env = config.env();
+        let end = Instant::now();
+
+        log::info!("Loading builtin libraries took {:?}", end - start);
+
+        env
+    };
+
+    let checker = Checker::new(cm, handler, env, config.parser_config(), None, Arc::new(config.resolver()));
+
+    {
+        let start = Instant::now();
+
+        checker.load_typings(
+            config.dir(),
+            config.compiler_options.type_roots.as_deref(),
+            config.compiler_options.types.as_deref(),
+        );
+
+        let end = Instant::now();
+
+        log::info!("Loading typing libraries took {:?}", end - start);
+    }
+
+    checker
+}
+
+/// Checks all files of the project, and returns errors.
+pub(crate) fn check_files(checker: &mut Checker, config: &ParsedConfig) -> Vec<stc_ts_errors::Error> {
+    let start = Instant::now();
+    let entries = config
+        .files
+        .iter()
+        .map(|file| Arc::new(FileName::Real(file.clone())))
+        .collect::<Vec<_>>();
+    checker.check_all(&entries);
+    let end = Instant::now();
+
+    log::info!("Checking {} files took {:?}", config.files.len(), end - start);
+
+    stc_ts_errors::Error::flatten(checker.take_errors())
+}
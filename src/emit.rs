@@ -0,0 +1,193 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+
+use anyhow::{bail, Context, Error};
+use clap::Args;
+use rayon::prelude::*;
+use rnode::{NodeIdGenerator, RNode};
+use stc_ts_ast_rnode::RModule;
+use stc_ts_config::ParsedConfig;
+use stc_ts_dts::{isolated_dts, print_dts};
+use stc_ts_type_checker::{syntax_error, Checker};
+use swc_common::{errors::Handler, FileName, SourceMap};
+use swc_ecma_ast::Module;
+use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
+
+use crate::{
+    check::{check_files, create_checker, load_config},
+    report::{report, Format},
+};
+
+/// Type check a project using `tsconfig.json`, and write declaration files.
+///
+/// Emitting javascript files is not supported.
+#[derive(Debug, Args)]
+#[clap(rename_all = "camel-case")]
+pub struct EmitCommand {
+    /// Path to `tsconfig.json`, or to a directory containing it.
+    #[clap(short = 'p', long = "project", default_value = "tsconfig.json")]
+    pub project: PathBuf,
+
+    /// Write `.d.ts` files. Not required if `declaration` is enabled in the
+    /// config file.
+    #[clap(short = 'd', long)]
+    pub declaration: bool,
+
+    /// Write `.d.ts.map` files along with `.d.ts` files. Not required if
+    /// `declarationMap` is enabled in the config file.
+    #[clap(long)]
+    pub declaration_map: bool,
+
+    /// Generate declaration files from explicit types of each file, without
+    /// type checking. Declarations without explicit types are reported.
+    #[clap(long)]
+    pub isolated_declarations: bool,
+
+    /// Format of diagnostics.
+    #[clap(long, value_enum, default_value = "pretty")]
+    pub format: Format,
+}
+
+impl EmitCommand {
+    /// Returns the number of errors.
+    ///
+    /// Like `tsc`, files are written even if there are errors.
+    pub fn run(self, cm: Arc<SourceMap>, handler: Arc<Handler>) -> Result<usize, Error> {
+        let config = load_config(&self.project)?;
+        if !self.declaration && !config.compiler_options.declaration.unwrap_or(false) {
+            bail!("nothing to emit; only declaration files are supported, so `--declaration` is required");
+        }
+        let outputs = config.declaration_files()?;
+        let map = self.declaration_map || config.compiler_options.declaration_map.unwrap_or(false);
+
+        if self.isolated_declarations {
+            return emit_isolated(&config, &outputs, map, self.format, cm, handler);
+        }
+
+        let mut checker = create_checker(&config, cm.clone(), handler.clone());
+        let errors = check_files(&mut checker, &config);
+        let count = errors.len();
+
+        checker.run(|| -> Result<(), Error> {
+            report(self.format, &cm, &handler, errors)?;
+
+            let start = Instant::now();
+            for (file, dts) in &outputs {
+                emit_dts(&checker, &cm, file, dts, map)?;
+            }
+            let end = Instant::now();
+
+            log::info!("Emitting {} declaration files took {:?}", outputs.len(), end - start);
+
+            Ok(())
+        })?;
+
+        Ok(count)
+    }
+}
+
+/// Generates declaration files without [Checker], so each file is processed
+/// in parallel.
+///
+/// Returns the number of errors.
+fn emit_isolated(
+    config: &ParsedConfig,
+    outputs: &[(PathBuf, PathBuf)],
+    map: bool,
+    format: Format,
+    cm: Arc<SourceMap>,
+    handler: Arc<Handler>,
+) -> Result<usize, Error> {
+    let start = Instant::now();
+
+    let results = outputs
+        .par_iter()
+        .map(|(file, dts)| -> Result<_, Error> {
+            let mut module = match parse(config, &cm, file)? {
+                Ok(module) => RModule::from_orig(&mut NodeIdGenerator::invalid(), module),
+                Err(errors) => return Ok(errors),
+            };
+
+            let errors = isolated_dts(&mut module.body);
+            write_dts(&cm, &module.into_orig(), file, dts, map)?;
+
+            Ok(errors)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let end = Instant::now();
+
+    log::info!("Emitting {} declaration files took {:?}", outputs.len(), end - start);
+
+    let errors = results.into_iter().flatten().collect::<Vec<_>>();
+    let count = errors.len();
+    report(format, &cm, &handler, errors)?;
+
+    Ok(count)
+}
+
+/// Returns syntax errors as the inner [Err] if `file` cannot be parsed.
+fn parse(config: &ParsedConfig, cm: &SourceMap, file: &Path) -> Result<Result<Module, Vec<stc_ts_errors::Error>>, Error> {
+    let fm = cm.load_file(file).with_context(|| format!("failed to load `{}`", file.display()))?;
+
+    let lexer = Lexer::new(
+        Syntax::Typescript(TsConfig {
+            tsx: file.extension().map(|v| v == "tsx").unwrap_or(false),
+            ..config.parser_config()
+        }),
+        config.target(),
+        StringInput::from(&*fm),
+        None,
+    );
+    let mut parser = Parser::new_from(lexer);
+
+    let result = parser.parse_module();
+    let mut errors = parser.take_errors();
+    let module = match result {
+        Ok(module) => Some(module),
+        Err(err) => {
+            errors.push(err);
+            None
+        }
+    };
+
+    match module {
+        Some(module) if errors.is_empty() => Ok(Ok(module)),
+        _ => Ok(Err(errors.iter().map(syntax_error).collect())),
+    }
+}
+
+fn emit_dts(checker: &Checker, cm: &Arc<SourceMap>, file: &Path, dts: &Path, map: bool) -> Result<(), Error> {
+    let id = checker.id(&Arc::new(FileName::Real(file.to_path_buf())));
+    let module = match checker.take_dts(id) {
+        Some(module) => module,
+        None => {
+            // The file could not be parsed, and the error is already reported.
+            log::warn!("Skipping declaration file of `{}`", file.display());
+            return Ok(());
+        }
+    };
+
+    write_dts(cm, &module, file, dts, map)
+}
+
+fn write_dts(cm: &Arc<SourceMap>, module: &Module, file: &Path, dts: &Path, map: bool) -> Result<(), Error> {
+    let printed = print_dts(cm.clone(), module, if map { Some(dts) } else { None })
+        .with_context(|| format!("failed to print declaration file of `{}`", file.display()))?;
+
+    if let Some(dir) = dts.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("failed to create `{}`", dir.display()))?;
+    }
+    fs::write(dts, printed.code).with_context(|| format!("failed to write `{}`", dts.display()))?;
+
+    if let Some(content) = printed.map {
+        let map_file = PathBuf::from(format!("{}.map", dts.display()));
+        fs::write(&map_file, content).with_context(|| format!("failed to write `{}`", map_file.display()))?;
+    }
+
+    Ok(())
+}
@@ -18,13 +18,23 @@ use swc_ecma_ast::EsVersion;
 use swc_ecma_parser::TsConfig;
 use tracing_subscriber::EnvFilter;
 
-use crate::check::TestCommand;
+use crate::{
+    check::{CheckCommand, TestCommand},
+    emit::EmitCommand,
+    report::summary,
+};
 
 mod check;
+mod emit;
+mod report;
+mod watch;
 
 #[derive(Debug, Parser)]
 #[command(name = "stc", about = "Super fast type checker for typescript", author, rename_all = "camel")]
 enum Command {
+    Check(CheckCommand),
+    Emit(EmitCommand),
+    #[command(hide = true)]
     Test(TestCommand),
     Lsp(LspCommand),
 }
@@ -61,7 +71,25 @@ async fn main() -> Result<(), Error> {
         log::info!("Initialization took {:?}", end - start);
     }
 
+    let mut exit_code = 0;
+
     match command {
+        Command::Check(cmd) => {
+            let error_count = cmd.run(cm.clone(), handler.clone())?;
+
+            if error_count > 0 {
+                eprintln!("{}", summary(error_count));
+                exit_code = 1;
+            }
+        }
+        Command::Emit(cmd) => {
+            let error_count = cmd.run(cm.clone(), handler.clone())?;
+
+            if error_count > 0 {
+                eprintln!("{}", summary(error_count));
+                exit_code = 1;
+            }
+        }
         Command::Test(cmd) => {
             let libs = {
                 let start = Instant::now();
@@ -97,6 +125,7 @@ async fn main() -> Result<(), Error> {
                 );
 
                 checker.load_typings(&path, None, cmd.types.as_deref());
+                checker.check_all(&[]);
 
                 let end = Instant::now();
 
@@ -144,5 +173,9 @@ async fn main() -> Result<(), Error> {
 
     log::info!("Done in {:?}", end - start);
 
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
+
     Ok(())
 }
@@ -0,0 +1,282 @@
+//! Rendering of diagnostics.
+//!
+//! [Format::Pretty] is rendered by [Handler], and other formats are written to
+//! stdout so that they can be piped to other tools.
+
+use std::{
+    env,
+    io::{self, Write},
+    path::Path,
+};
+
+use anyhow::Error;
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::json;
+use swc_common::{errors::Handler, FileName, Loc, SourceMap, Span, Spanned};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human-readable output with code frames.
+    Pretty,
+    /// One JSON object per line.
+    Json,
+    /// SARIF 2.1.0
+    Sarif,
+    /// `file(line,col): error TSxxxx: message`, like `tsc --pretty false`.
+    Tsc,
+}
+
+/// `Found 1 error.` or `Found 2 errors.`, like `tsc`.
+pub(crate) fn summary(error_count: usize) -> String {
+    format!("Found {} error{}.", error_count, if error_count == 1 { "" } else { "s" })
+}
+
+/// Reports `errors`. This should be called with `swc_common::GLOBALS` set,
+/// because types in messages are printed.
+pub(crate) fn report(format: Format, cm: &SourceMap, handler: &Handler, errors: Vec<stc_ts_errors::Error>) -> Result<(), Error> {
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+
+    match format {
+        Format::Pretty => {
+            for err in errors {
+                err.emit(handler);
+            }
+        }
+        Format::Json => {
+            for err in &errors {
+                serde_json::to_writer(&mut out, &Diagnostic::new(cm, err))?;
+                writeln!(out)?;
+            }
+        }
+        Format::Sarif => {
+            let diagnostics = errors.iter().map(|err| Diagnostic::new(cm, err)).collect::<Vec<_>>();
+
+            serde_json::to_writer_pretty(&mut out, &sarif(&diagnostics))?;
+            writeln!(out)?;
+        }
+        Format::Tsc => {
+            for err in &errors {
+                write_tsc(&mut out, &Diagnostic::new(cm, err))?;
+            }
+        }
+    }
+
+    out.flush()?;
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+struct Diagnostic {
+    #[serde(flatten)]
+    location: Option<Location>,
+    /// `TSxxxx`
+    code: String,
+    message: String,
+    related: Vec<Related>,
+}
+
+#[derive(Debug, Serialize)]
+struct Related {
+    #[serde(flatten)]
+    location: Option<Location>,
+    message: String,
+    /// Nesting depth of the cause, starting from 0.
+    depth: usize,
+}
+
+/// Lines and columns are 1-based, and columns are counted in UTF-16 code
+/// units.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    file: String,
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+impl Diagnostic {
+    fn new(cm: &SourceMap, err: &stc_ts_errors::Error) -> Self {
+        Diagnostic {
+            location: Location::new(cm, err.span()),
+            code: format!("TS{}", stc_ts_errors::Error::normalize_error_code(err.code())),
+            message: err.msg().into_owned(),
+            related: err
+                .cause_chain()
+                .into_iter()
+                .map(|(depth, cause)| Related {
+                    location: Location::new(cm, cause.span()),
+                    message: cause.msg().into_owned(),
+                    depth,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Location {
+    fn new(cm: &SourceMap, span: Span) -> Option<Self> {
+        if span.is_dummy() {
+            return None;
+        }
+
+        let lo = cm.lookup_char_pos(span.lo);
+        let hi = cm.lookup_char_pos(span.hi);
+
+        Some(Location {
+            file: display_file(&lo.file.name),
+            line: lo.line,
+            column: utf16_column(&lo) + 1,
+            end_line: hi.line,
+            end_column: utf16_column(&hi) + 1,
+        })
+    }
+}
+
+/// `tsc` and SARIF count columns in UTF-16 code units, while swc counts chars.
+fn utf16_column(loc: &Loc) -> usize {
+    loc.file
+        .get_line(loc.line - 1)
+        .map(|text| text.chars().take(loc.col.0).map(char::len_utf16).sum::<usize>())
+        .unwrap_or(loc.col.0)
+}
+
+/// Paths are relative to the current directory if possible, like `tsc`.
+fn display_file(name: &FileName) -> String {
+    match name {
+        FileName::Real(path) => {
+            let cwd = env::current_dir().ok();
+            let path = cwd.as_deref().and_then(|cwd| path.strip_prefix(cwd).ok()).unwrap_or(path);
+
+            to_slash(path)
+        }
+        _ => name.to_string(),
+    }
+}
+
+fn to_slash(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn write_tsc(out: &mut impl Write, d: &Diagnostic) -> io::Result<()> {
+    if let Some(l) = &d.location {
+        write!(out, "{}({},{}): ", l.file, l.line, l.column)?;
+    }
+    writeln!(out, "error {}: {}", d.code, d.message)?;
+
+    for related in &d.related {
+        writeln!(out, "{}{}", "  ".repeat(related.depth + 1), related.message)?;
+    }
+
+    Ok(())
+}
+
+fn sarif(diagnostics: &[Diagnostic]) -> serde_json::Value {
+    let mut rules = diagnostics.iter().map(|d| d.code.as_str()).collect::<Vec<_>>();
+    rules.sort_unstable();
+    rules.dedup();
+
+    let results = diagnostics
+        .iter()
+        .map(|d| {
+            json!({
+                "ruleId": d.code,
+                "level": "error",
+                "message": { "text": d.message },
+                "locations": d.location.iter().map(sarif_location).collect::<Vec<_>>(),
+                "relatedLocations": d
+                    .related
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(id, related)| {
+                        let mut location = sarif_location(related.location.as_ref()?);
+                        location["id"] = json!(id);
+                        location["message"] = json!({ "text": related.message });
+                        Some(location)
+                    })
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "stc",
+                    "rules": rules.iter().map(|id| json!({ "id": id })).collect::<Vec<_>>(),
+                }
+            },
+            "originalUriBaseIds": original_uri_base_ids(),
+            "results": results,
+        }],
+    })
+}
+
+fn sarif_location(l: &Location) -> serde_json::Value {
+    json!({
+        "physicalLocation": {
+            "artifactLocation": artifact_location(&l.file),
+            "region": {
+                "startLine": l.line,
+                "startColumn": l.column,
+                "endLine": l.end_line,
+                "endColumn": l.end_column,
+            }
+        }
+    })
+}
+
+/// `uriBaseId` of artifacts in the current directory.
+const SRCROOT: &str = "%SRCROOT%";
+
+/// Relative artifact locations are resolved against the current directory.
+fn original_uri_base_ids() -> serde_json::Value {
+    let cwd = match env::current_dir() {
+        Ok(cwd) => cwd,
+        Err(..) => return json!({}),
+    };
+    let mut uri = file_url(&to_slash(&cwd));
+    if !uri.ends_with('/') {
+        uri.push('/');
+    }
+
+    json!({ SRCROOT: { "uri": uri } })
+}
+
+/// SARIF requires a valid URI, while [Location::file] is a path.
+fn artifact_location(file: &str) -> serde_json::Value {
+    if Path::new(file).is_absolute() {
+        json!({ "uri": file_url(file) })
+    } else {
+        json!({ "uri": encode_uri_path(file), "uriBaseId": SRCROOT })
+    }
+}
+
+/// `/a b/c.ts` becomes `file:///a%20b/c.ts`, and `C:/a.ts` becomes
+/// `file:///C%3A/a.ts`.
+fn file_url(path: &str) -> String {
+    if path.starts_with('/') {
+        format!("file://{}", encode_uri_path(path))
+    } else {
+        format!("file:///{}", encode_uri_path(path))
+    }
+}
+
+/// Percent-encodes everything except unreserved characters and `/`.
+fn encode_uri_path(path: &str) -> String {
+    let mut s = String::with_capacity(path.len());
+    for b in path.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => s.push(b as char),
+            _ => s.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    s
+}
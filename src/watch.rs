@@ -0,0 +1,332 @@
+//! Watch mode of `stc check`.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{channel, Receiver},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Error};
+use notify::{event::ModifyKind, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use stc_ts_config::ParsedConfig;
+use stc_ts_type_checker::Checker;
+use swc_common::{errors::Handler, FileName, SourceMap, Spanned};
+
+use crate::{
+    check::{check_files, create_checker, load_config},
+    report::{report, summary, Format},
+};
+
+/// Time to wait for more changes, because editors and tools like `git` change
+/// multiple files at once.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Checks the project, and checks affected modules again whenever a file in
+/// the module graph or `tsconfig.json` changes. Files added to the project by
+/// creating or renaming a file are checked as well.
+///
+/// This returns only on errors, like when the project cannot be loaded
+/// initially or the file watcher stops.
+pub(crate) fn watch(project: &Path, format: Format, cm: Arc<SourceMap>, handler: Arc<Handler>) -> Result<usize, Error> {
+    let (tx, rx) = channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+        let _ = tx.send(res);
+    })?;
+
+    let mut session = Session::start(project, cm.clone(), handler.clone())?;
+    let mut watched = HashMap::new();
+
+    loop {
+        session.report(format, &cm, &handler)?;
+        session.watch(&mut watcher, &mut watched);
+
+        let (changed, new_files) = loop {
+            let changes = wait_for_changes(&rx)?;
+            // Creating, removing or renaming a file can change files matched by `include`.
+            let new_files = if changes.renamed && changes.paths.iter().any(|p| session.config.inputs.may_change_inputs(p)) {
+                session.expand_files()
+            } else {
+                None
+            };
+            let changed = session.filter_relevant(changes.paths);
+
+            if !changed.is_empty() || new_files.is_some() {
+                break (changed, new_files);
+            }
+        };
+
+        eprintln!("File change detected. Starting incremental check...");
+
+        if changed.contains(&session.config.path) {
+            match Session::start(project, cm.clone(), handler.clone()) {
+                Ok(new) => session = new,
+                Err(err) => eprintln!("error: failed to reload `{}`: {:?}", session.config.path.display(), err),
+            }
+            continue;
+        }
+
+        session.recheck(&cm, changed);
+        if let Some(files) = new_files {
+            session.update_files(&cm, files);
+        }
+    }
+}
+
+fn watch_dir(watcher: &mut RecommendedWatcher, watched: &mut HashMap<PathBuf, RecursiveMode>, dir: &Path, mode: RecursiveMode) {
+    match watcher.watch(dir, mode) {
+        Ok(()) => {
+            watched.insert(dir.to_path_buf(), mode);
+        }
+        Err(err) => log::warn!("failed to watch `{}`: {:?}", dir.display(), err),
+    }
+}
+
+/// Paths changed by events of the file watcher.
+#[derive(Default)]
+struct Changes {
+    paths: Vec<PathBuf>,
+
+    /// True if a file is created, removed or renamed.
+    renamed: bool,
+}
+
+/// Blocks until a file changes, and returns changed paths.
+fn wait_for_changes(rx: &Receiver<notify::Result<Event>>) -> Result<Changes, Error> {
+    let mut next = match rx.recv() {
+        Ok(v) => Some(v),
+        Err(..) => bail!("file watcher stopped"),
+    };
+
+    let mut changes = Changes::default();
+    while let Some(res) = next {
+        match res {
+            Ok(event) => {
+                changes.renamed |= matches!(
+                    event.kind,
+                    EventKind::Create(..) | EventKind::Remove(..) | EventKind::Modify(ModifyKind::Name(..))
+                );
+
+                if !matches!(event.kind, EventKind::Access(..)) {
+                    changes.paths.extend(event.paths);
+                }
+            }
+            Err(err) => log::warn!("file watcher error: {:?}", err),
+        }
+
+        next = rx.recv_timeout(DEBOUNCE).ok();
+    }
+
+    Ok(changes)
+}
+
+struct Session {
+    config: ParsedConfig,
+    checker: Checker,
+
+    /// Errors grouped by file, so that errors of files which are not checked
+    /// again can be reported again.
+    errors: HashMap<FileName, Vec<stc_ts_errors::Error>>,
+}
+
+impl Session {
+    fn start(project: &Path, cm: Arc<SourceMap>, handler: Arc<Handler>) -> Result<Self, Error> {
+        let config = load_config(project)?;
+        let mut checker = create_checker(&config, cm.clone(), handler);
+        let errors = check_files(&mut checker, &config);
+
+        let mut session = Session {
+            config,
+            checker,
+            errors: Default::default(),
+        };
+        session.add_errors(&cm, errors, &FileName::Anon);
+
+        Ok(session)
+    }
+
+    /// Errors without a location are stored with `fallback`.
+    fn add_errors(&mut self, cm: &SourceMap, errors: Vec<stc_ts_errors::Error>, fallback: &FileName) {
+        for err in errors {
+            let span = err.span();
+            let file = if span.is_dummy() {
+                fallback.clone()
+            } else {
+                cm.span_to_filename(span)
+            };
+
+            self.errors.entry(file).or_default().push(err);
+        }
+    }
+
+    /// Files which should be watched, which are all files in the module graph
+    /// and `tsconfig.json`.
+    fn files(&self) -> HashSet<PathBuf> {
+        self.checker
+            .files()
+            .into_iter()
+            .filter_map(|file| match &*file {
+                FileName::Real(path) => Some(path.clone()),
+                _ => None,
+            })
+            .chain(Some(self.config.path.clone()))
+            .collect()
+    }
+
+    /// Watches roots of `include` recursively, so files created in new
+    /// directories are found.
+    ///
+    /// Other files are watched using their parent directories instead of
+    /// files, because editors often replace files on save. The directory of
+    /// `tsconfig.json` is always watched, because it may contain roots which do
+    /// not exist yet.
+    fn watch(&self, watcher: &mut RecommendedWatcher, watched: &mut HashMap<PathBuf, RecursiveMode>) {
+        for root in self.config.inputs.roots() {
+            if root.is_dir() && watched.get(root) != Some(&RecursiveMode::Recursive) {
+                if watched.remove(root).is_some() {
+                    let _ = watcher.unwatch(root);
+                }
+                watch_dir(watcher, watched, root, RecursiveMode::Recursive);
+            }
+        }
+
+        for file in self.files() {
+            let dir = match file.parent() {
+                Some(dir) => dir,
+                None => continue,
+            };
+
+            let covered = watched
+                .iter()
+                .any(|(w, mode)| dir == w || (*mode == RecursiveMode::Recursive && dir.starts_with(w)));
+            if !covered {
+                watch_dir(watcher, watched, dir, RecursiveMode::NonRecursive);
+            }
+        }
+    }
+
+    fn filter_relevant(&self, paths: Vec<PathBuf>) -> Vec<PathBuf> {
+        let files = self.files();
+
+        let mut changed = vec![];
+        for path in paths {
+            if files.contains(&path) && !changed.contains(&path) {
+                changed.push(path);
+            }
+        }
+        changed
+    }
+
+    fn recheck(&mut self, cm: &SourceMap, changed: Vec<PathBuf>) {
+        let start = Instant::now();
+
+        for path in changed {
+            let path = Arc::new(FileName::Real(path));
+
+            for id in self.checker.invalidate(&path) {
+                self.errors.remove(&*self.checker.path(id));
+            }
+
+            let errors = stc_ts_errors::Error::flatten(self.checker.take_errors());
+            self.add_errors(cm, errors, &path);
+        }
+
+        let end = Instant::now();
+        log::info!("Incremental check took {:?}", end - start);
+    }
+
+    /// Expands `files`, `include` and `exclude` of the config again, and
+    /// returns the result if it's changed.
+    fn expand_files(&self) -> Option<Vec<PathBuf>> {
+        let files = match stc_ts_config::load(&self.config.path) {
+            Ok(config) => config.files,
+            Err(err) => {
+                eprintln!("error: failed to reload `{}`: {:?}", self.config.path.display(), err);
+                return None;
+            }
+        };
+
+        if files == self.config.files {
+            return None;
+        }
+
+        Some(files)
+    }
+
+    /// Checks files which are added to the project, and drops errors of
+    /// removed files.
+    fn update_files(&mut self, cm: &SourceMap, files: Vec<PathBuf>) {
+        let added = files
+            .iter()
+            .filter(|file| !self.config.files.contains(file))
+            .map(|file| Arc::new(FileName::Real(file.clone())))
+            .collect::<Vec<_>>();
+
+        for file in &self.config.files {
+            if !files.contains(file) {
+                self.errors.remove(&FileName::Real(file.clone()));
+            }
+        }
+        self.config.files = files;
+
+        if !added.is_empty() {
+            self.checker.check_all(&added);
+
+            let errors = stc_ts_errors::Error::flatten(self.checker.take_errors());
+            self.add_errors(cm, errors, &FileName::Anon);
+        }
+    }
+
+    fn report(&self, format: Format, cm: &SourceMap, handler: &Handler) -> Result<(), Error> {
+        let mut errors = self.errors.values().flatten().cloned().collect::<Vec<_>>();
+        errors.sort_by_key(|err| err.span().lo);
+        let count = errors.len();
+
+        self.checker.run(|| report(format, cm, handler, errors))?;
+
+        eprintln!("{} Watching for file changes.", summary(count));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use notify::event::{AccessKind, CreateKind};
+
+    use super::*;
+
+    fn event(kind: EventKind, path: &str) -> notify::Result<Event> {
+        Ok(Event::new(kind).add_path(PathBuf::from(path)))
+    }
+
+    #[test]
+    fn debounce() {
+        let (tx, rx) = channel();
+        tx.send(event(EventKind::Modify(ModifyKind::Any), "a.ts")).unwrap();
+        tx.send(event(EventKind::Access(AccessKind::Any), "b.ts")).unwrap();
+        tx.send(event(EventKind::Create(CreateKind::File), "c.ts")).unwrap();
+
+        let late = thread::spawn(move || {
+            thread::sleep(DEBOUNCE * 5);
+            tx.send(event(EventKind::Modify(ModifyKind::Any), "d.ts")).unwrap();
+        });
+
+        // Events sent at once are merged, and accesses are ignored.
+        let changes = wait_for_changes(&rx).unwrap();
+        assert_eq!(changes.paths, vec![PathBuf::from("a.ts"), PathBuf::from("c.ts")]);
+        assert!(changes.renamed);
+
+        late.join().unwrap();
+        let changes = wait_for_changes(&rx).unwrap();
+        assert_eq!(changes.paths, vec![PathBuf::from("d.ts")]);
+        assert!(!changes.renamed);
+
+        assert!(wait_for_changes(&rx).is_err());
+    }
+}
@@ -0,0 +1,194 @@
+use std::{
+    env, fs,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{self, Child, Command, Output, Stdio},
+    sync::mpsc::{channel, Receiver},
+    thread,
+    time::{Duration, Instant},
+};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixture").join(name)
+}
+
+/// Copies files of the fixture into a new directory, for commands which write
+/// files.
+fn copy_fixture(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join("stc-cli-tests").join(format!("{}-{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    for entry in fs::read_dir(fixture(name)).unwrap() {
+        let entry = entry.unwrap();
+        fs::copy(entry.path(), dir.join(entry.file_name())).unwrap();
+    }
+
+    dir
+}
+
+fn stc(args: &[&str], project: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_stc"))
+        .args(args)
+        .arg("-p")
+        .arg(project)
+        .env_remove("RUST_LOG")
+        .output()
+        .expect("failed to run stc")
+}
+
+/// Runs `stc` for `project`, and returns the exit code and the last line of
+/// stderr.
+fn run(args: &[&str], project: &Path) -> (Option<i32>, String) {
+    let output = stc(args, project);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    (output.status.code(), stderr.lines().last().unwrap_or_default().to_string())
+}
+
+#[test]
+fn check_without_errors() {
+    assert_eq!(run(&["check"], &fixture("check/clean")), (Some(0), String::new()));
+}
+
+#[test]
+fn check_with_an_error() {
+    assert_eq!(
+        run(&["check"], &fixture("check/one_error")),
+        (Some(1), "Found 1 error.".to_string())
+    );
+}
+
+#[test]
+fn check_with_errors() {
+    assert_eq!(
+        run(&["check"], &fixture("check/two_errors")),
+        (Some(1), "Found 2 errors.".to_string())
+    );
+}
+
+#[test]
+fn emit_with_an_error() {
+    let dir = copy_fixture("emit");
+
+    assert_eq!(run(&["emit"], &dir), (Some(1), "Found 1 error.".to_string()));
+    // Like `tsc`, files are written even if there are errors.
+    let dts = fs::read_to_string(dir.join("index.d.ts")).unwrap();
+    assert!(dts.contains("declare const a: string;"), "{}", dts);
+}
+
+#[test]
+fn emit_isolated_with_syntax_error() {
+    let dir = copy_fixture("syntax_error");
+    let output = stc(&["emit", "--isolatedDeclarations", "--format", "tsc"], &dir);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(1));
+    // Syntax errors are printed using `--format`.
+    assert!(stdout.contains("index.ts(1,26): error TS1"), "{}", stdout);
+    assert!(!dir.join("index.d.ts").exists());
+}
+
+/// Checks the `report` fixture from its directory, so paths in the output are
+/// relative, and returns stdout.
+///
+/// The line of the error contains a non-BMP character, which is 2 UTF-16 code
+/// units long, and the name of the file contains a space.
+fn report(format: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_stc"))
+        .args(["check", "-p", ".", "--format", format])
+        .current_dir(fixture("report").canonicalize().unwrap())
+        .env_remove("RUST_LOG")
+        .output()
+        .expect("failed to run stc");
+
+    assert_eq!(output.status.code(), Some(1));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn expected(file: &str) -> String {
+    fs::read_to_string(fixture("report").join(file)).unwrap()
+}
+
+#[test]
+fn report_json() {
+    assert_eq!(report("json"), expected("expected.json"));
+}
+
+#[test]
+fn report_tsc() {
+    assert_eq!(report("tsc"), expected("expected.txt"));
+}
+
+#[test]
+fn report_sarif() {
+    let mut actual: serde_json::Value = serde_json::from_str(&report("sarif")).unwrap();
+    // It's the current directory, which depends on the machine.
+    let base = actual["runs"][0].as_object_mut().unwrap().remove("originalUriBaseIds").unwrap();
+    let base = base["%SRCROOT%"]["uri"].as_str().unwrap();
+    assert!(base.starts_with("file:///") && base.ends_with("/report/"), "{}", base);
+
+    let expected: serde_json::Value = serde_json::from_str(&expected("expected.sarif")).unwrap();
+    assert_eq!(actual, expected);
+}
+
+/// Kills the process even if the test fails.
+struct KillOnDrop(Child);
+
+impl Drop for KillOnDrop {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// Reads lines of stderr of `child` on another thread.
+fn stderr_lines(child: &mut Child) -> Receiver<String> {
+    let stderr = child.stderr.take().unwrap();
+    let (tx, rx) = channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stderr).lines() {
+            if tx.send(line.unwrap()).is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+fn wait_for_line(lines: &Receiver<String>, expected: &str) {
+    let deadline = Instant::now() + Duration::from_secs(60);
+    loop {
+        match lines.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(line) if line == expected => return,
+            Ok(..) => {}
+            Err(err) => panic!("{:?} while waiting for `{}`", err, expected),
+        }
+    }
+}
+
+#[test]
+fn watch_new_directory() {
+    let dir = copy_fixture("watch");
+    let mut child = KillOnDrop(
+        Command::new(env!("CARGO_BIN_EXE_stc"))
+            .args(["check", "--watch", "-p"])
+            .arg(&dir)
+            .env_remove("RUST_LOG")
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("failed to run stc"),
+    );
+    let lines = stderr_lines(&mut child.0);
+
+    wait_for_line(&lines, "Found 1 error. Watching for file changes.");
+
+    // Files in a new directory are matched by `include`.
+    fs::create_dir(dir.join("nested")).unwrap();
+    // The watcher starts watching the directory after it's created.
+    thread::sleep(Duration::from_secs(1));
+    fs::write(dir.join("nested").join("a.ts"), "export const b: string = 1;\n").unwrap();
+
+    wait_for_line(&lines, "Found 2 errors. Watching for file changes.");
+}
@@ -0,0 +1 @@
+const s = "😀"; foo;
@@ -0,0 +1 @@
+const a: string = 1;